@@ -22,7 +22,8 @@ const settleFeeSchema = z.object({
 
 /**
  * POST /fee/initialize-accounts
- * Initialize fee distribution accounts for a specific token mint
+ * Initialize fee distribution accounts for a specific token mint; safe to
+ * call again for a mint whose accounts already exist
  */
 router.post('/initialize-accounts', async (req: any, res: any) => {
   const startTime = Date.now();
@@ -46,7 +47,7 @@ router.post('/initialize-accounts', async (req: any, res: any) => {
 
     // Derive all PDA addresses
     const [feeCollectionAuthority, feeCollectionAuthorityBump] = PublicKey.findProgramAddressSync(
-      [Buffer.from("fee_authority")],
+      [Buffer.from("fee_authority"), tokenMintPub.toBuffer()],
       PROGRAM_ID
     );
 
@@ -70,37 +71,16 @@ router.post('/initialize-accounts', async (req: any, res: any) => {
       PROGRAM_ID
     );
 
-    // Check if accounts already exist
-    const existingAccounts = await Promise.all([
-      connection.getAccountInfo(feeCollectionAuthority),
-      connection.getAccountInfo(liquidityStakerAccount),
-      connection.getAccountInfo(treasuryAccount),
-      connection.getAccountInfo(bountyAccount),
-      connection.getAccountInfo(feeCollectionAccount)
-    ]);
+    const [feeLedger, feeLedgerBump] = PublicKey.findProgramAddressSync(
+      [Buffer.from("fee_ledger"), tokenMintPub.toBuffer()],
+      PROGRAM_ID
+    );
 
-    const accountNames = ['authority', 'liquidity', 'treasury', 'bounty', 'collection'];
-    const existingAccountNames = existingAccounts
-      .map((account, index) => account ? accountNames[index] : null)
-      .filter(Boolean);
-
-    if (existingAccountNames.length > 0) {
-      return res.status(409).json({
-        error: 'Fee accounts already exist',
-        existingAccounts: existingAccountNames,
-        addresses: {
-          feeCollectionAuthority: feeCollectionAuthority.toString(),
-          liquidityStakerAccount: liquidityStakerAccount.toString(),
-          treasuryAccount: treasuryAccount.toString(),
-          bountyAccount: bountyAccount.toString(),
-          feeCollectionAccount: feeCollectionAccount.toString()
-        },
-        timestamp: new Date().toISOString()
-      });
-    }
+    // initialize_mint_fees is idempotent: accounts that already exist are
+    // kept and only the missing ones are created
 
     // Create initialization instruction
-    const discriminator = Buffer.from([233, 63, 142, 11, 168, 28, 143, 222]); // initialize_fee_accounts
+    const discriminator = Buffer.from([137, 126, 1, 252, 204, 7, 36, 23]); // initialize_mint_fees
     const instructionData = discriminator;
 
     const initializeInstruction = {
@@ -110,6 +90,7 @@ router.post('/initialize-accounts', async (req: any, res: any) => {
         { pubkey: treasuryAccount, isSigner: false, isWritable: true },
         { pubkey: bountyAccount, isSigner: false, isWritable: true },
         { pubkey: feeCollectionAccount, isSigner: false, isWritable: true },
+        { pubkey: feeLedger, isSigner: false, isWritable: true },
         { pubkey: tokenMintPub, isSigner: false, isWritable: false },
         { pubkey: wallet.publicKey, isSigner: true, isWritable: true },
         { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
@@ -155,14 +136,16 @@ router.post('/initialize-accounts', async (req: any, res: any) => {
         liquidityStakerAccount: liquidityStakerAccount.toString(),
         treasuryAccount: treasuryAccount.toString(),
         bountyAccount: bountyAccount.toString(),
-        feeCollectionAccount: feeCollectionAccount.toString()
+        feeCollectionAccount: feeCollectionAccount.toString(),
+        feeLedger: feeLedger.toString()
       },
       bumps: {
         feeCollectionAuthorityBump,
         liquidityStakerBump,
         treasuryBump,
         bountyBump,
-        feeCollectionBump
+        feeCollectionBump,
+        feeLedgerBump
       },
       metadata: {
         requestId: randomUUID(),
@@ -189,7 +172,8 @@ router.post('/initialize-accounts', async (req: any, res: any) => {
 
 /**
  * POST /fee/settle
- * Distribute collected fees to different pools
+ * Distribute collected fees to different pools; the backend wallet must be
+ * the protocol admin
  */
 router.post('/settle', async (req: any, res: any) => {
   const startTime = Date.now();
@@ -212,16 +196,37 @@ router.post('/settle', async (req: any, res: any) => {
     logger.info('Settling fees', { tokenMint, feeAmount });
 
     // Derive PDA addresses
+    const [config] = PublicKey.findProgramAddressSync(
+      [Buffer.from("config")],
+      PROGRAM_ID
+    );
+
     const [feeCollectionAuthority] = PublicKey.findProgramAddressSync(
-      [Buffer.from("fee_authority")],
+      [Buffer.from("fee_authority"), tokenMintPub.toBuffer()],
       PROGRAM_ID
     );
 
+    // Fees are settled from the mint's fee collection PDA
     const [feeCollectionAccount] = PublicKey.findProgramAddressSync(
       [Buffer.from("fee_collection"), tokenMintPub.toBuffer()],
       PROGRAM_ID
     );
 
+    const [feeLedger] = PublicKey.findProgramAddressSync(
+      [Buffer.from("fee_ledger"), tokenMintPub.toBuffer()],
+      PROGRAM_ID
+    );
+
+    const [stakePool] = PublicKey.findProgramAddressSync(
+      [Buffer.from("stake_pool")],
+      PROGRAM_ID
+    );
+
+    const [rewardPool] = PublicKey.findProgramAddressSync(
+      [Buffer.from("reward_pool"), tokenMintPub.toBuffer()],
+      PROGRAM_ID
+    );
+
     const [liquidityStakerAccount] = PublicKey.findProgramAddressSync(
       [Buffer.from("liq_stakers"), tokenMintPub.toBuffer()],
       PROGRAM_ID
@@ -240,6 +245,7 @@ router.post('/settle', async (req: any, res: any) => {
     // Verify accounts exist
     const accountInfos = await Promise.all([
       connection.getAccountInfo(feeCollectionAccount),
+      connection.getAccountInfo(feeLedger),
       connection.getAccountInfo(liquidityStakerAccount),
       connection.getAccountInfo(treasuryAccount),
       connection.getAccountInfo(bountyAccount)
@@ -253,6 +259,11 @@ router.post('/settle', async (req: any, res: any) => {
       });
     }
 
+    // Until initialize_reward_pool attaches staker rewards to this mint, the
+    // optional stake and reward pool accounts are omitted (passed as the
+    // program id) and the stakers' share is held in the fee ledger
+    const hasRewardPool = !!(await connection.getAccountInfo(rewardPool));
+
     // Create settle instruction
     const discriminator = Buffer.from([252, 176, 98, 248, 73, 123, 8, 157]); // settle_trade
     const feeAmountBuffer = Buffer.alloc(8);
@@ -262,8 +273,12 @@ router.post('/settle', async (req: any, res: any) => {
 
     const settleInstruction = {
       keys: [
+        { pubkey: config, isSigner: false, isWritable: false },
         { pubkey: feeCollectionAuthority, isSigner: false, isWritable: false },
         { pubkey: feeCollectionAccount, isSigner: false, isWritable: true },
+        { pubkey: feeLedger, isSigner: false, isWritable: true },
+        { pubkey: hasRewardPool ? stakePool : PROGRAM_ID, isSigner: false, isWritable: false },
+        { pubkey: hasRewardPool ? rewardPool : PROGRAM_ID, isSigner: false, isWritable: hasRewardPool },
         { pubkey: liquidityStakerAccount, isSigner: false, isWritable: true },
         { pubkey: treasuryAccount, isSigner: false, isWritable: true },
         { pubkey: bountyAccount, isSigner: false, isWritable: true },
@@ -364,7 +379,7 @@ router.get('/accounts/:tokenMint', async (req: any, res: any) => {
 
     // Derive PDA addresses
     const [feeCollectionAuthority] = PublicKey.findProgramAddressSync(
-      [Buffer.from("fee_authority")],
+      [Buffer.from("fee_authority"), tokenMintPub.toBuffer()],
       PROGRAM_ID
     );
 
@@ -248,7 +248,7 @@ export class RevealService {
    */
   private async deriveFeeAccounts(tokenMint: PublicKey) {
     const [feeCollectionAuthority] = PublicKey.findProgramAddressSync(
-      [Buffer.from("fee_authority")],
+      [Buffer.from("fee_authority"), tokenMint.toBuffer()],
       PROGRAM_ID
     );
 
@@ -257,9 +257,31 @@ export class RevealService {
       PROGRAM_ID
     );
 
+    const [feeLedger] = PublicKey.findProgramAddressSync(
+      [Buffer.from("fee_ledger"), tokenMint.toBuffer()],
+      PROGRAM_ID
+    );
+
     return {
       feeCollectionAuthority,
-      feeCollectionAccount
+      feeCollectionAccount,
+      feeLedger
+    };
+  }
+
+  /**
+   * Derive the protocol accounts every reveal reads or updates
+   */
+  private deriveProtocolAccounts(userPub: PublicKey, tokenIn: PublicKey, tokenOut: PublicKey) {
+    const pda = (...seeds: Buffer[]) => PublicKey.findProgramAddressSync(seeds, PROGRAM_ID)[0];
+
+    return {
+      config: pda(Buffer.from("config")),
+      denylist: pda(Buffer.from("denylist")),
+      // Created by the relayer on the user's first fill
+      userStats: pda(Buffer.from("user_stats"), userPub.toBuffer()),
+      tokenInConfig: pda(Buffer.from("mint_config"), tokenIn.toBuffer()),
+      tokenOutConfig: pda(Buffer.from("mint_config"), tokenOut.toBuffer())
     };
   }
 
@@ -290,29 +312,51 @@ export class RevealService {
     const intentData = this.serializeTradeIntentData(intent);
     const expectedHashBytes = Buffer.from(expectedHash, 'hex');
     const signatureBytes = Buffer.from(signature, 'hex');
+    const fillAmount = Buffer.alloc(8);
+    fillAmount.writeBigUInt64LE(BigInt(intent.amountIn), 0);
 
     // Build instruction data buffer
     const instructionData = Buffer.concat([
       Buffer.from([72, 86, 206, 182, 223, 187, 228, 226]), // discriminator
       intentData,
       expectedHashBytes,
-      signatureBytes
+      signatureBytes,
+      fillAmount
     ]);
 
-    // Create reveal instruction
+    const tokenIn = new PublicKey(intent.tokenIn);
+    const tokenOut = new PublicKey(intent.tokenOut);
+    const protocolAccounts = this.deriveProtocolAccounts(userPub, tokenIn, tokenOut);
+
+    // Optional accounts that are not passed are set to the program id
+    const none = { pubkey: PROGRAM_ID, isSigner: false, isWritable: false };
+
+    // Create reveal instruction; keys follow the order of `RevealTrade`
     const revealInstruction = new TransactionInstruction({
       keys: [
         { pubkey: swapIntentPda, isSigner: false, isWritable: true },
-        { pubkey: userPub, isSigner: false, isWritable: true },
+        { pubkey: userPub, isSigner: true, isWritable: true },
         { pubkey: SYSVAR_INSTRUCTIONS_PUBKEY, isSigner: false, isWritable: false },
+        { pubkey: protocolAccounts.config, isSigner: false, isWritable: false },
+        { pubkey: protocolAccounts.denylist, isSigner: false, isWritable: false },
+        { pubkey: protocolAccounts.userStats, isSigner: false, isWritable: true },
         { pubkey: tokenAccounts.userTokenIn, isSigner: false, isWritable: true },
         { pubkey: tokenAccounts.userTokenOut, isSigner: false, isWritable: true },
         { pubkey: tokenAccounts.relayerTokenIn, isSigner: false, isWritable: true },
         { pubkey: tokenAccounts.relayerTokenOut, isSigner: false, isWritable: true },
         { pubkey: relayerPub, isSigner: true, isWritable: true },
-        { pubkey: new PublicKey(intent.tokenIn), isSigner: false, isWritable: false },
-        { pubkey: new PublicKey(intent.tokenOut), isSigner: false, isWritable: false },
+        none, // relayer_registration
+        { pubkey: tokenIn, isSigner: false, isWritable: false },
+        { pubkey: tokenOut, isSigner: false, isWritable: false },
+        { pubkey: protocolAccounts.tokenInConfig, isSigner: false, isWritable: true },
+        { pubkey: protocolAccounts.tokenOutConfig, isSigner: false, isWritable: true },
+        none, // token_in_oracle
+        none, // token_out_oracle
         { pubkey: feeAccounts.feeCollectionAccount, isSigner: false, isWritable: true },
+        { pubkey: feeAccounts.feeLedger, isSigner: false, isWritable: true },
+        none, // bounty_account
+        none, // referrer_token_account
+        none, // referrer_stats
         { pubkey: feeAccounts.feeCollectionAuthority, isSigner: false, isWritable: false },
         { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
         { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }
@@ -334,7 +378,7 @@ export class RevealService {
    * Serialize TradeIntentData for instruction
    */
   private serializeTradeIntentData(intent: TradeIntent): Buffer {
-    const buffer = Buffer.alloc(256); // Allocate enough space
+    const buffer = Buffer.alloc(512); // Allocate enough space
     let offset = 0;
 
     // user (32 bytes)
@@ -357,6 +401,10 @@ export class RevealService {
     buffer.writeBigUInt64LE(BigInt(intent.relayerFee), offset);
     offset += 8;
 
+    // relayer_fee_token (1 byte): TokenOut
+    buffer.writeUInt8(1, offset);
+    offset += 1;
+
     // token_in (32 bytes)
     new PublicKey(intent.tokenIn).toBuffer().copy(buffer, offset);
     offset += 32;
@@ -373,6 +421,36 @@ export class RevealService {
     buffer.writeBigUInt64LE(BigInt(intent.minOut), offset);
     offset += 8;
 
+    // quoted_out (8 bytes): no surplus sharing
+    buffer.writeBigUInt64LE(0n, offset);
+    offset += 8;
+
+    // recipient (32 bytes): output goes to the user
+    new PublicKey(intent.user).toBuffer().copy(buffer, offset);
+    offset += 32;
+
+    // referrer (32 bytes) and referral_bps (2 bytes): no referral
+    PublicKey.default.toBuffer().copy(buffer, offset);
+    offset += 32;
+    buffer.writeUInt16LE(0, offset);
+    offset += 2;
+
+    // exclusivity_slots (8 bytes)
+    buffer.writeBigUInt64LE(0n, offset);
+    offset += 8;
+
+    // kind (1 byte): Standard
+    buffer.writeUInt8(0, offset);
+    offset += 1;
+
+    // allow_partial (1 byte)
+    buffer.writeUInt8(0, offset);
+    offset += 1;
+
+    // route (4-byte length): direct fill
+    buffer.writeUInt32LE(0, offset);
+    offset += 4;
+
     return buffer.slice(0, offset);
   }
 
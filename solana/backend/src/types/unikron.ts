@@ -14,327 +14,271 @@ export type Unikron = {
   },
   "instructions": [
     {
-      "name": "commitTrade",
+      "name": "addToDenylist",
       "docs": [
-        "Commit a trade intent hash to the blockchain"
+        "Add a wallet or mint to the denylist"
       ],
       "discriminator": [
-        225,
-        172,
-        49,
-        43,
-        30,
-        198,
-        216,
-        89
+        194,
+        213,
+        51,
+        109,
+        157,
+        0,
+        252,
+        157
       ],
       "accounts": [
         {
-          "name": "swapIntent",
-          "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "const",
-                "value": [
-                  105,
-                  110,
-                  116,
-                  101,
-                  110,
-                  116
-                ]
-              },
-              {
-                "kind": "account",
-                "path": "user"
-              },
-              {
-                "kind": "arg",
-                "path": "nonce"
-              }
-            ]
-          }
+          "name": "config"
         },
         {
-          "name": "user",
-          "writable": true,
-          "signer": true
+          "name": "denylist",
+          "writable": true
         },
         {
-          "name": "systemProgram",
-          "address": "11111111111111111111111111111111"
+          "name": "admin",
+          "signer": true
         }
       ],
       "args": [
         {
-          "name": "intentHash",
+          "name": "kind",
           "type": {
-            "array": [
-              "u8",
-              32
-            ]
+            "defined": {
+              "name": "denylistKind"
+            }
           }
         },
         {
-          "name": "nonce",
-          "type": "u64"
-        },
-        {
-          "name": "expiry",
-          "type": "u64"
+          "name": "key",
+          "type": "pubkey"
         }
       ]
     },
     {
-      "name": "initializeFeeAccounts",
+      "name": "claimBatchFill",
       "docs": [
-        "Initialize fee accounts for a specific token mint"
+        "Claim the fill and refund of a settled batch order"
       ],
       "discriminator": [
-        233,
-        63,
-        142,
-        11,
-        168,
-        28,
-        143,
-        222
+        169,
+        166,
+        234,
+        203,
+        122,
+        234,
+        240,
+        195
       ],
       "accounts": [
         {
-          "name": "feeCollectionAuthority",
-          "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "const",
-                "value": [
-                  102,
-                  101,
-                  101,
-                  95,
-                  97,
-                  117,
-                  116,
-                  104,
-                  111,
-                  114,
-                  105,
-                  116,
-                  121
-                ]
-              }
-            ]
-          }
+          "name": "batch",
+          "writable": true
         },
         {
-          "name": "liquidityStakerAccount",
-          "docs": [
-            "Initialize liquidity stakers fee account"
-          ],
-          "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "const",
-                "value": [
-                  108,
-                  105,
-                  113,
-                  95,
-                  115,
-                  116,
-                  97,
-                  107,
-                  101,
-                  114,
-                  115
-                ]
-              },
-              {
-                "kind": "account",
-                "path": "tokenMint"
-              }
-            ]
-          }
+          "name": "baseVault",
+          "writable": true
         },
         {
-          "name": "treasuryAccount",
+          "name": "quoteVault",
+          "writable": true
+        },
+        {
+          "name": "baseAccount",
           "docs": [
-            "Initialize treasury fee account"
+            "Base account of the owner when refunding a base sale, or of the",
+            "recipient when receiving bought base"
           ],
-          "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "const",
-                "value": [
-                  116,
-                  114,
-                  101,
-                  97,
-                  115,
-                  117,
-                  114,
-                  121
-                ]
-              },
-              {
-                "kind": "account",
-                "path": "tokenMint"
-              }
-            ]
-          }
+          "writable": true
         },
         {
-          "name": "bountyAccount",
+          "name": "quoteAccount",
           "docs": [
-            "Initialize MEV bounty fee account"
+            "Quote account of the owner when refunding a quote sale, or of the",
+            "recipient when receiving sold-base proceeds"
           ],
-          "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "const",
-                "value": [
-                  109,
-                  101,
-                  118,
-                  95,
-                  98,
-                  111,
-                  117,
-                  110,
-                  116,
-                  121
-                ]
-              },
-              {
-                "kind": "account",
-                "path": "tokenMint"
-              }
-            ]
-          }
+          "writable": true
         },
         {
           "name": "feeCollectionAccount",
           "docs": [
-            "Initialize fee collection account"
+            "Fee collection account of the order's input mint, receiving the",
+            "protocol fee on the filled input"
           ],
-          "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "const",
-                "value": [
-                  102,
-                  101,
-                  101,
-                  95,
-                  99,
-                  111,
-                  108,
-                  108,
-                  101,
-                  99,
-                  116,
-                  105,
-                  111,
-                  110
-                ]
-              },
-              {
-                "kind": "account",
-                "path": "tokenMint"
-              }
-            ]
-          }
+          "writable": true
         },
         {
-          "name": "tokenMint",
+          "name": "feeLedger",
           "docs": [
-            "The token mint for the fee accounts"
-          ]
+            "Fee totals of the order's input mint"
+          ],
+          "writable": true
         },
         {
-          "name": "payer",
+          "name": "owner",
+          "signer": true
+        },
+        {
+          "name": "tokenProgram"
+        }
+      ],
+      "args": [
+        {
+          "name": "orderIndex",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "claimRewards",
+      "docs": [
+        "Claim accrued staker rewards in one fee mint"
+      ],
+      "discriminator": [
+        4,
+        144,
+        132,
+        71,
+        116,
+        23,
+        151,
+        80
+      ],
+      "accounts": [
+        {
+          "name": "stakePool"
+        },
+        {
+          "name": "stakePosition",
+          "writable": true
+        },
+        {
+          "name": "rewardPool",
+          "writable": true
+        },
+        {
+          "name": "liquidityStakerAccount",
           "docs": [
-            "Payer for account creation"
+            "Stakers' share of `fee_mint` fees, paid out by claims"
           ],
-          "writable": true,
-          "signer": true
+          "writable": true
+        },
+        {
+          "name": "feeCollectionAuthority"
+        },
+        {
+          "name": "ownerTokenAccount",
+          "writable": true
         },
         {
-          "name": "tokenProgram",
-          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+          "name": "feeMint"
         },
         {
-          "name": "systemProgram",
-          "address": "11111111111111111111111111111111"
+          "name": "owner",
+          "signer": true
         },
         {
-          "name": "rent",
-          "address": "SysvarRent111111111111111111111111111111111"
+          "name": "tokenProgram"
         }
       ],
       "args": []
     },
     {
-      "name": "revealTrade",
+      "name": "commitTrade",
       "docs": [
-        "Reveal and execute a committed trade"
+        "Commit a trade intent hash to the blockchain"
       ],
       "discriminator": [
-        72,
-        86,
-        206,
-        182,
-        223,
-        187,
-        228,
-        226
+        225,
+        172,
+        49,
+        43,
+        30,
+        198,
+        216,
+        89
       ],
       "accounts": [
         {
           "name": "swapIntent",
+          "writable": true
+        },
+        {
+          "name": "user",
           "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "const",
-                "value": [
-                  105,
-                  110,
-                  116,
-                  101,
-                  110,
-                  116
-                ]
-              },
-              {
-                "kind": "account",
-                "path": "user"
-              },
-              {
-                "kind": "account",
-                "path": "swap_intent.nonce",
-                "account": "swapIntent"
-              }
+          "signer": true
+        },
+        {
+          "name": "denylist"
+        },
+        {
+          "name": "systemProgram"
+        }
+      ],
+      "args": [
+        {
+          "name": "intentHash",
+          "type": {
+            "array": [
+              "u8",
+              32
             ]
           }
         },
+        {
+          "name": "nonce",
+          "type": "u64"
+        },
+        {
+          "name": "expiry",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "executeSlice",
+      "docs": [
+        "Execute the next slice of a recurring intent on the user's behalf"
+      ],
+      "discriminator": [
+        124,
+        60,
+        40,
+        35,
+        122,
+        84,
+        240,
+        176
+      ],
+      "accounts": [
+        {
+          "name": "swapIntent",
+          "writable": true
+        },
         {
           "name": "user",
-          "writable": true,
-          "signer": true,
-          "relations": [
-            "swapIntent"
-          ]
+          "docs": [
+            "hash (checked against the ed25519 instruction); receives the intent's",
+            "rent once the last slice fills"
+          ],
+          "writable": true
+        },
+        {
+          "name": "instructionsSysvar"
+        },
+        {
+          "name": "config"
+        },
+        {
+          "name": "denylist"
         },
         {
-          "name": "instructionsSysvar",
-          "address": "Sysvar1nstructions1111111111111111111111111"
+          "name": "userStats",
+          "docs": [
+            "Rolling volume of the user, which sets their fee tier"
+          ],
+          "writable": true
         },
         {
           "name": "userTokenInAccount",
@@ -342,6 +286,9 @@ export type Unikron = {
         },
         {
           "name": "userTokenOutAccount",
+          "docs": [
+            "Output account, owned by the intent's recipient"
+          ],
           "writable": true
         },
         {
@@ -357,48 +304,54 @@ export type Unikron = {
           "writable": true,
           "signer": true
         },
+        {
+          "name": "relayerRegistration",
+          "docs": [
+            "Registry entry of the relayer, required when filling an open intent"
+          ],
+          "optional": true
+        },
         {
           "name": "tokenInMint"
         },
         {
           "name": "tokenOutMint"
         },
+        {
+          "name": "tokenInConfig",
+          "writable": true
+        },
+        {
+          "name": "tokenOutConfig",
+          "writable": true
+        },
+        {
+          "name": "tokenInOracle",
+          "optional": true
+        },
+        {
+          "name": "tokenOutOracle",
+          "optional": true
+        },
         {
           "name": "feeCollectionAccount",
           "writable": true
         },
         {
-          "name": "feeCollectionAuthority",
-          "pda": {
-            "seeds": [
-              {
-                "kind": "const",
-                "value": [
-                  102,
-                  101,
-                  101,
-                  95,
-                  97,
-                  117,
-                  116,
-                  104,
-                  111,
-                  114,
-                  105,
-                  116,
-                  121
-                ]
-              }
-            ]
-          }
+          "name": "feeLedger",
+          "docs": [
+            "Fee totals of `token_in_mint`"
+          ],
+          "writable": true
+        },
+        {
+          "name": "feeCollectionAuthority"
         },
         {
-          "name": "tokenProgram",
-          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+          "name": "tokenProgram"
         },
         {
-          "name": "systemProgram",
-          "address": "11111111111111111111111111111111"
+          "name": "systemProgram"
         }
       ],
       "args": [
@@ -431,381 +384,4358 @@ export type Unikron = {
       ]
     },
     {
-      "name": "settleTrade",
+      "name": "initializeDenylist",
       "docs": [
-        "Distribute collected fees to different pools"
+        "Create the empty compliance denylist"
       ],
       "discriminator": [
-        252,
-        176,
+        38,
+        165,
+        5,
+        57,
+        71,
+        20,
         98,
-        248,
-        73,
-        123,
-        8,
-        157
+        127
       ],
       "accounts": [
         {
-          "name": "feeCollectionAuthority",
-          "pda": {
-            "seeds": [
-              {
-                "kind": "const",
-                "value": [
-                  102,
-                  101,
-                  101,
-                  95,
-                  97,
-                  117,
-                  116,
-                  104,
-                  111,
-                  114,
-                  105,
-                  116,
-                  121
-                ]
-              }
-            ]
-          }
+          "name": "config"
         },
         {
-          "name": "sourceFeeAccount",
-          "docs": [
-            "The token account that holds collected protocol fees"
-          ],
+          "name": "denylist",
+          "writable": true
+        },
+        {
+          "name": "admin",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "initializeMintConfig",
+      "docs": [
+        "Create the risk configuration for a token mint"
+      ],
+      "discriminator": [
+        61,
+        141,
+        161,
+        167,
+        9,
+        153,
+        85,
+        170
+      ],
+      "accounts": [
+        {
+          "name": "config"
+        },
+        {
+          "name": "mintConfig",
           "writable": true
         },
+        {
+          "name": "mint"
+        },
+        {
+          "name": "admin",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram"
+        }
+      ],
+      "args": [
+        {
+          "name": "oracle",
+          "type": "pubkey"
+        },
+        {
+          "name": "maxDeviationBps",
+          "type": "u16"
+        },
+        {
+          "name": "maxStalenessSecs",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "initializeMintFees",
+      "docs": [
+        "Initialize the fee accounts of a token mint; safe to call again"
+      ],
+      "discriminator": [
+        137,
+        126,
+        1,
+        252,
+        204,
+        7,
+        36,
+        23
+      ],
+      "accounts": [
+        {
+          "name": "feeCollectionAuthority"
+        },
         {
           "name": "liquidityStakerAccount",
           "docs": [
-            "Token account for liquidity stakers (50% of fees)"
+            "Initialize liquidity stakers fee account"
           ],
-          "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "const",
-                "value": [
-                  108,
-                  105,
-                  113,
-                  95,
-                  115,
-                  116,
-                  97,
-                  107,
-                  101,
-                  114,
-                  115
-                ]
-              },
-              {
-                "kind": "account",
-                "path": "source_fee_account.mint",
-                "account": "tokenAccount"
-              }
-            ]
-          }
+          "writable": true
         },
         {
           "name": "treasuryAccount",
           "docs": [
-            "Token account for protocol treasury (30% of fees)"
+            "Initialize treasury fee account"
           ],
-          "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "const",
-                "value": [
-                  116,
-                  114,
-                  101,
-                  97,
-                  115,
-                  117,
-                  114,
-                  121
-                ]
-              },
-              {
-                "kind": "account",
-                "path": "source_fee_account.mint",
-                "account": "tokenAccount"
-              }
-            ]
-          }
+          "writable": true
         },
         {
           "name": "bountyAccount",
           "docs": [
-            "Token account for MEV bounty pool (20% of fees)"
+            "Initialize MEV bounty fee account"
           ],
-          "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "const",
-                "value": [
-                  109,
-                  101,
-                  118,
-                  95,
-                  98,
-                  111,
-                  117,
-                  110,
-                  116,
-                  121
-                ]
-              },
-              {
-                "kind": "account",
-                "path": "source_fee_account.mint",
-                "account": "tokenAccount"
-              }
-            ]
-          }
+          "writable": true
+        },
+        {
+          "name": "feeCollectionAccount",
+          "docs": [
+            "Initialize fee collection account"
+          ],
+          "writable": true
+        },
+        {
+          "name": "feeLedger",
+          "docs": [
+            "Initialize fee ledger"
+          ],
+          "writable": true
         },
         {
           "name": "tokenMint",
           "docs": [
-            "The token mint for the fees being distributed"
+            "The token mint for the fee accounts"
           ]
         },
         {
-          "name": "caller",
+          "name": "payer",
           "docs": [
-            "Relayer or authorized caller who triggers fee distribution"
+            "Payer for account creation"
           ],
           "writable": true,
           "signer": true
         },
         {
-          "name": "tokenProgram",
-          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+          "name": "tokenProgram"
+        },
+        {
+          "name": "systemProgram"
+        },
+        {
+          "name": "rent"
         }
       ],
-      "args": [
+      "args": []
+    },
+    {
+      "name": "initializeProtocol",
+      "docs": [
+        "Initialize the global protocol config and its admin"
+      ],
+      "discriminator": [
+        188,
+        233,
+        252,
+        106,
+        134,
+        146,
+        202,
+        91
+      ],
+      "accounts": [
         {
-          "name": "feeAmount",
-          "type": "u64"
+          "name": "config",
+          "writable": true
+        },
+        {
+          "name": "admin",
+          "docs": [
+            "Admin that will manage protocol configuration and relayers"
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "program"
+        },
+        {
+          "name": "programData"
+        },
+        {
+          "name": "systemProgram"
         }
-      ]
-    }
-  ],
-  "accounts": [
+      ],
+      "args": []
+    },
     {
-      "name": "swapIntent",
+      "name": "initializeReferrerStats",
+      "docs": [
+        "Create the earnings record of a referrer in one mint"
+      ],
       "discriminator": [
-        242,
-        212,
-        249,
-        216,
-        109,
-        94,
-        238,
-        134
-      ]
-    }
-  ],
-  "events": [
+        235,
+        237,
+        46,
+        118,
+        219,
+        224,
+        112,
+        82
+      ],
+      "accounts": [
+        {
+          "name": "referrerStats",
+          "writable": true
+        },
+        {
+          "name": "referrer"
+        },
+        {
+          "name": "mint"
+        },
+        {
+          "name": "payer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram"
+        }
+      ],
+      "args": []
+    },
     {
-      "name": "feeAccountsInitialized",
+      "name": "initializeRewardPool",
+      "docs": [
+        "Attach a fee mint's staker rewards to the stake pool"
+      ],
       "discriminator": [
-        24,
-        244,
-        176,
-        43,
-        165,
-        149,
-        67,
-        124
-      ]
+        139,
+        189,
+        60,
+        130,
+        44,
+        211,
+        218,
+        99
+      ],
+      "accounts": [
+        {
+          "name": "config"
+        },
+        {
+          "name": "stakePool",
+          "writable": true
+        },
+        {
+          "name": "rewardPool",
+          "writable": true
+        },
+        {
+          "name": "feeMint"
+        },
+        {
+          "name": "admin",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram"
+        }
+      ],
+      "args": []
     },
     {
-      "name": "feeDistributed",
+      "name": "initializeStakePool",
+      "docs": [
+        "Create the stake pool that earns the liquidity staker share of fees"
+      ],
       "discriminator": [
-        6,
-        133,
-        116,
-        50,
-        44,
-        151,
-        179,
-        65
-      ]
+        48,
+        189,
+        243,
+        73,
+        19,
+        67,
+        36,
+        83
+      ],
+      "accounts": [
+        {
+          "name": "config"
+        },
+        {
+          "name": "stakePool",
+          "writable": true
+        },
+        {
+          "name": "stakeVault",
+          "docs": [
+            "Vault holding staked tokens, owned by the stake pool PDA"
+          ],
+          "writable": true
+        },
+        {
+          "name": "stakeMint"
+        },
+        {
+          "name": "admin",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "tokenProgram"
+        },
+        {
+          "name": "systemProgram"
+        },
+        {
+          "name": "rent"
+        }
+      ],
+      "args": []
     },
     {
-      "name": "tradeExecuted",
+      "name": "matchIntents",
+      "docs": [
+        "Reveal two opposing intents together and settle them peer-to-peer"
+      ],
       "discriminator": [
-        41,
-        110,
-        64,
-        129,
-        60,
-        79,
-        179,
-        80
+        239,
+        68,
+        117,
+        58,
+        240,
+        152,
+        14,
+        224
+      ],
+      "accounts": [
+        {
+          "name": "swapIntentA",
+          "writable": true
+        },
+        {
+          "name": "swapIntentB",
+          "writable": true
+        },
+        {
+          "name": "userA",
+          "docs": [
+            "User selling token A for token B"
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "userB",
+          "docs": [
+            "User selling token B for token A; a different wallet from `user_a`,",
+            "since both legs would otherwise write the same stats account"
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "instructionsSysvar"
+        },
+        {
+          "name": "config"
+        },
+        {
+          "name": "denylist"
+        },
+        {
+          "name": "userAStats",
+          "docs": [
+            "Rolling volume of user A, which sets their fee tier"
+          ],
+          "writable": true
+        },
+        {
+          "name": "userBStats",
+          "docs": [
+            "Rolling volume of user B, which sets their fee tier"
+          ],
+          "writable": true
+        },
+        {
+          "name": "userATokenInAccount",
+          "writable": true
+        },
+        {
+          "name": "userATokenOutAccount",
+          "docs": [
+            "Output account, owned by the recipient of intent A"
+          ],
+          "writable": true
+        },
+        {
+          "name": "userBTokenInAccount",
+          "writable": true
+        },
+        {
+          "name": "userBTokenOutAccount",
+          "docs": [
+            "Output account, owned by the recipient of intent B"
+          ],
+          "writable": true
+        },
+        {
+          "name": "relayerTokenAAccount",
+          "docs": [
+            "Relayer token A account, used for leftovers and relayer fees"
+          ],
+          "writable": true
+        },
+        {
+          "name": "relayerTokenBAccount",
+          "docs": [
+            "Relayer token B account, used for leftovers and relayer fees"
+          ],
+          "writable": true
+        },
+        {
+          "name": "relayer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "relayerRegistration",
+          "docs": [
+            "Registry entry of the relayer, required when matching open intents"
+          ],
+          "optional": true
+        },
+        {
+          "name": "tokenAMint"
+        },
+        {
+          "name": "tokenBMint"
+        },
+        {
+          "name": "tokenAConfig",
+          "writable": true
+        },
+        {
+          "name": "tokenBConfig",
+          "writable": true
+        },
+        {
+          "name": "tokenAOracle",
+          "optional": true
+        },
+        {
+          "name": "tokenBOracle",
+          "optional": true
+        },
+        {
+          "name": "feeCollectionAAccount",
+          "writable": true
+        },
+        {
+          "name": "feeLedgerA",
+          "docs": [
+            "Fee totals of `token_a_mint`"
+          ],
+          "writable": true
+        },
+        {
+          "name": "feeCollectionBAccount",
+          "writable": true
+        },
+        {
+          "name": "feeLedgerB",
+          "docs": [
+            "Fee totals of `token_b_mint`"
+          ],
+          "writable": true
+        },
+        {
+          "name": "feeCollectionAAuthority"
+        },
+        {
+          "name": "feeCollectionBAuthority"
+        },
+        {
+          "name": "tokenProgram"
+        },
+        {
+          "name": "systemProgram"
+        }
+      ],
+      "args": [
+        {
+          "name": "intentA",
+          "type": {
+            "defined": {
+              "name": "tradeIntentData"
+            }
+          }
+        },
+        {
+          "name": "expectedHashA",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        },
+        {
+          "name": "signatureA",
+          "type": {
+            "array": [
+              "u8",
+              64
+            ]
+          }
+        },
+        {
+          "name": "intentB",
+          "type": {
+            "defined": {
+              "name": "tradeIntentData"
+            }
+          }
+        },
+        {
+          "name": "expectedHashB",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        },
+        {
+          "name": "signatureB",
+          "type": {
+            "array": [
+              "u8",
+              64
+            ]
+          }
+        }
       ]
-    }
-  ],
-  "errors": [
+    },
     {
-      "code": 6000,
-      "name": "alreadyRevealed",
-      "msg": "Intent already revealed"
+      "name": "openBatch",
+      "docs": [
+        "Open the batch for a token pair and batch window"
+      ],
+      "discriminator": [
+        103,
+        163,
+        23,
+        69,
+        63,
+        155,
+        108,
+        163
+      ],
+      "accounts": [
+        {
+          "name": "batch",
+          "writable": true
+        },
+        {
+          "name": "baseVault",
+          "docs": [
+            "Escrow for base tokens sold into the batch"
+          ],
+          "writable": true
+        },
+        {
+          "name": "quoteVault",
+          "docs": [
+            "Escrow for quote tokens sold into the batch"
+          ],
+          "writable": true
+        },
+        {
+          "name": "baseMint",
+          "docs": [
+            "Pair mints in canonical order, base key lower than quote key"
+          ]
+        },
+        {
+          "name": "quoteMint"
+        },
+        {
+          "name": "payer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "tokenProgram"
+        },
+        {
+          "name": "systemProgram"
+        },
+        {
+          "name": "rent"
+        }
+      ],
+      "args": [
+        {
+          "name": "epoch",
+          "type": "u64"
+        }
+      ]
     },
     {
-      "code": 6001,
-      "name": "intentExpired",
-      "msg": "Trade intent expired"
+      "name": "payBountyClaim",
+      "docs": [
+        "Pay an approved bounty claim from the bounty pool"
+      ],
+      "discriminator": [
+        175,
+        68,
+        140,
+        107,
+        214,
+        10,
+        12,
+        208
+      ],
+      "accounts": [
+        {
+          "name": "bountyClaim",
+          "writable": true
+        },
+        {
+          "name": "bountyAccount",
+          "writable": true
+        },
+        {
+          "name": "feeCollectionAuthority"
+        },
+        {
+          "name": "reporterTokenAccount",
+          "writable": true
+        },
+        {
+          "name": "mint"
+        },
+        {
+          "name": "caller",
+          "signer": true
+        },
+        {
+          "name": "tokenProgram"
+        }
+      ],
+      "args": []
     },
     {
-      "code": 6002,
-      "name": "nonceMismatch",
-      "msg": "Nonce does not match"
+      "name": "registerRelayer",
+      "docs": [
+        "Register a relayer that may fill open intents"
+      ],
+      "discriminator": [
+        98,
+        213,
+        0,
+        0,
+        27,
+        134,
+        109,
+        48
+      ],
+      "accounts": [
+        {
+          "name": "config"
+        },
+        {
+          "name": "relayerRegistration",
+          "writable": true
+        },
+        {
+          "name": "relayer"
+        },
+        {
+          "name": "admin",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "removeFromDenylist",
+      "docs": [
+        "Remove a wallet or mint from the denylist"
+      ],
+      "discriminator": [
+        48,
+        248,
+        96,
+        109,
+        152,
+        170,
+        87,
+        38
+      ],
+      "accounts": [
+        {
+          "name": "config"
+        },
+        {
+          "name": "denylist",
+          "writable": true
+        },
+        {
+          "name": "admin",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "kind",
+          "type": {
+            "defined": {
+              "name": "denylistKind"
+            }
+          }
+        },
+        {
+          "name": "key",
+          "type": "pubkey"
+        }
+      ]
+    },
+    {
+      "name": "revealToBatch",
+      "docs": [
+        "Reveal an intent into the current batch, escrowing its input"
+      ],
+      "discriminator": [
+        65,
+        106,
+        204,
+        141,
+        90,
+        72,
+        162,
+        57
+      ],
+      "accounts": [
+        {
+          "name": "swapIntent",
+          "writable": true
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "instructionsSysvar"
+        },
+        {
+          "name": "config"
+        },
+        {
+          "name": "denylist"
+        },
+        {
+          "name": "userStats",
+          "docs": [
+            "Rolling volume of the user, which sets their fee tier"
+          ],
+          "writable": true
+        },
+        {
+          "name": "batch",
+          "writable": true
+        },
+        {
+          "name": "vaultIn",
+          "writable": true
+        },
+        {
+          "name": "userTokenInAccount",
+          "writable": true
+        },
+        {
+          "name": "tokenInMint"
+        },
+        {
+          "name": "tokenInConfig",
+          "writable": true
+        },
+        {
+          "name": "tokenInOracle",
+          "optional": true
+        },
+        {
+          "name": "tokenProgram"
+        },
+        {
+          "name": "systemProgram"
+        }
+      ],
+      "args": [
+        {
+          "name": "intent",
+          "type": {
+            "defined": {
+              "name": "tradeIntentData"
+            }
+          }
+        },
+        {
+          "name": "expectedHash",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        },
+        {
+          "name": "signature",
+          "type": {
+            "array": [
+              "u8",
+              64
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "revealTrade",
+      "docs": [
+        "Reveal and execute a committed trade, or part of it for partial intents"
+      ],
+      "discriminator": [
+        72,
+        86,
+        206,
+        182,
+        223,
+        187,
+        228,
+        226
+      ],
+      "accounts": [
+        {
+          "name": "swapIntent",
+          "writable": true
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "instructionsSysvar"
+        },
+        {
+          "name": "config"
+        },
+        {
+          "name": "denylist"
+        },
+        {
+          "name": "userStats",
+          "docs": [
+            "Rolling volume of the user, which sets their fee tier; created by the",
+            "relayer on the user's first fill"
+          ],
+          "writable": true
+        },
+        {
+          "name": "userTokenInAccount",
+          "writable": true
+        },
+        {
+          "name": "userTokenOutAccount",
+          "docs": [
+            "Output account, owned by the intent's recipient"
+          ],
+          "writable": true
+        },
+        {
+          "name": "relayerTokenInAccount",
+          "writable": true
+        },
+        {
+          "name": "relayerTokenOutAccount",
+          "writable": true
+        },
+        {
+          "name": "relayer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "relayerRegistration",
+          "docs": [
+            "Registry entry of the relayer, required when filling an open intent"
+          ],
+          "optional": true
+        },
+        {
+          "name": "tokenInMint"
+        },
+        {
+          "name": "tokenOutMint"
+        },
+        {
+          "name": "tokenInConfig",
+          "writable": true
+        },
+        {
+          "name": "tokenOutConfig",
+          "writable": true
+        },
+        {
+          "name": "tokenInOracle",
+          "optional": true
+        },
+        {
+          "name": "tokenOutOracle",
+          "optional": true
+        },
+        {
+          "name": "feeCollectionAccount",
+          "writable": true
+        },
+        {
+          "name": "feeLedger",
+          "docs": [
+            "Fee totals of `token_in_mint`"
+          ],
+          "writable": true
+        },
+        {
+          "name": "bountyAccount",
+          "docs": [
+            "MEV bounty pool for `token_out`, required when a fill produces surplus",
+            "to share"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "referrerTokenAccount",
+          "docs": [
+            "Referrer's `token_in` account, required when the intent pays a referral"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "referrerStats",
+          "docs": [
+            "Referrer's earnings record for `token_in`, required alongside",
+            "`referrer_token_account`"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "feeCollectionAuthority"
+        },
+        {
+          "name": "tokenProgram"
+        },
+        {
+          "name": "systemProgram"
+        }
+      ],
+      "args": [
+        {
+          "name": "intent",
+          "type": {
+            "defined": {
+              "name": "tradeIntentData"
+            }
+          }
+        },
+        {
+          "name": "expectedHash",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        },
+        {
+          "name": "signature",
+          "type": {
+            "array": [
+              "u8",
+              64
+            ]
+          }
+        },
+        {
+          "name": "fillAmount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "setBountyCommittee",
+      "docs": [
+        "Set the committee voting on MEV bounty claims and its approval threshold"
+      ],
+      "discriminator": [
+        174,
+        77,
+        243,
+        109,
+        45,
+        100,
+        209,
+        175
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "writable": true
+        },
+        {
+          "name": "admin",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "members",
+          "type": {
+            "vec": "pubkey"
+          }
+        },
+        {
+          "name": "threshold",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "setFeeTiers",
+      "docs": [
+        "Replace the volume-tiered protocol fee discount table"
+      ],
+      "discriminator": [
+        162,
+        35,
+        72,
+        250,
+        39,
+        183,
+        30,
+        7
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "writable": true
+        },
+        {
+          "name": "admin",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "feeTiers",
+          "type": {
+            "vec": {
+              "defined": {
+                "name": "feeTier"
+              }
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "setMintVolumeLimit",
+      "docs": [
+        "Set the per-epoch volume limit of a token mint"
+      ],
+      "discriminator": [
+        123,
+        24,
+        80,
+        8,
+        118,
+        104,
+        116,
+        33
+      ],
+      "accounts": [
+        {
+          "name": "config"
+        },
+        {
+          "name": "mintConfig",
+          "writable": true
+        },
+        {
+          "name": "admin",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "volumeLimit",
+          "type": "u64"
+        },
+        {
+          "name": "volumeEpochSecs",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "setReferralCap",
+      "docs": [
+        "Set the cap on an intent's referral fee"
+      ],
+      "discriminator": [
+        130,
+        168,
+        99,
+        159,
+        208,
+        148,
+        12,
+        151
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "writable": true
+        },
+        {
+          "name": "admin",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "maxReferralBps",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "setRelayerFeeCap",
+      "docs": [
+        "Set the cap on relayer fees, in bps of the amount they are paid from"
+      ],
+      "discriminator": [
+        180,
+        31,
+        220,
+        148,
+        195,
+        28,
+        20,
+        2
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "writable": true
+        },
+        {
+          "name": "admin",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "maxRelayerFeeBps",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "setRelayerStatus",
+      "docs": [
+        "Activate or deactivate a registered relayer"
+      ],
+      "discriminator": [
+        134,
+        199,
+        89,
+        233,
+        117,
+        209,
+        122,
+        5
+      ],
+      "accounts": [
+        {
+          "name": "config"
+        },
+        {
+          "name": "relayerRegistration",
+          "writable": true
+        },
+        {
+          "name": "admin",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "active",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "setSurplusShare",
+      "docs": [
+        "Set the share of output above `quoted_out` paid to the MEV bounty pool"
+      ],
+      "discriminator": [
+        69,
+        186,
+        144,
+        202,
+        104,
+        255,
+        234,
+        176
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "writable": true
+        },
+        {
+          "name": "admin",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "surplusBountyBps",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "setSweepParams",
+      "docs": [
+        "Set the keeper reward and minimum interval of fee sweeps, and how",
+        "long settled staker rewards take to stream to stakers"
+      ],
+      "discriminator": [
+        45,
+        95,
+        157,
+        122,
+        140,
+        223,
+        137,
+        40
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "writable": true
+        },
+        {
+          "name": "admin",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "keeperRewardBps",
+          "type": "u16"
+        },
+        {
+          "name": "minSweepIntervalSecs",
+          "type": "i64"
+        },
+        {
+          "name": "rewardStreamSecs",
+          "type": "i64"
+        }
+      ]
+    },
+    {
+      "name": "setUserVolumeLimit",
+      "docs": [
+        "Set the maximum notional a user may trade per day"
+      ],
+      "discriminator": [
+        200,
+        178,
+        232,
+        119,
+        225,
+        171,
+        209,
+        136
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "writable": true
+        },
+        {
+          "name": "admin",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "maxUserDailyNotional",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "settleBatch",
+      "docs": [
+        "Settle a closed batch at a single clearing price"
+      ],
+      "discriminator": [
+        22,
+        2,
+        21,
+        223,
+        225,
+        122,
+        163,
+        214
+      ],
+      "accounts": [
+        {
+          "name": "batch",
+          "writable": true
+        },
+        {
+          "name": "baseMint"
+        },
+        {
+          "name": "quoteMint"
+        },
+        {
+          "name": "baseConfig"
+        },
+        {
+          "name": "quoteConfig"
+        },
+        {
+          "name": "baseOracle",
+          "optional": true
+        },
+        {
+          "name": "quoteOracle",
+          "optional": true
+        },
+        {
+          "name": "caller",
+          "docs": [
+            "Anyone may settle a batch once its window has closed"
+          ],
+          "signer": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "settleMany",
+      "docs": [
+        "Settle fees of several mints in one transaction, one group of",
+        "remaining accounts per entry of `fee_amounts`"
+      ],
+      "discriminator": [
+        160,
+        73,
+        49,
+        36,
+        154,
+        79,
+        166,
+        53
+      ],
+      "accounts": [
+        {
+          "name": "config"
+        },
+        {
+          "name": "stakePool",
+          "docs": [
+            "Needed when any group's mint has a reward pool"
+          ],
+          "optional": true
+        },
+        {
+          "name": "caller",
+          "docs": [
+            "Protocol admin"
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "tokenProgram"
+        }
+      ],
+      "args": [
+        {
+          "name": "feeAmounts",
+          "type": {
+            "vec": "u64"
+          }
+        }
+      ]
+    },
+    {
+      "name": "settleTrade",
+      "docs": [
+        "Distribute a chosen amount of collected fees to different pools; admin only"
+      ],
+      "discriminator": [
+        252,
+        176,
+        98,
+        248,
+        73,
+        123,
+        8,
+        157
+      ],
+      "accounts": [
+        {
+          "name": "config"
+        },
+        {
+          "name": "feeCollectionAuthority"
+        },
+        {
+          "name": "sourceFeeAccount",
+          "docs": [
+            "The token account that holds collected protocol fees"
+          ],
+          "writable": true
+        },
+        {
+          "name": "feeLedger",
+          "docs": [
+            "Fee totals of `token_mint`"
+          ],
+          "writable": true
+        },
+        {
+          "name": "stakePool",
+          "optional": true
+        },
+        {
+          "name": "rewardPool",
+          "docs": [
+            "Staker reward accumulator of `token_mint`, created by",
+            "`initialize_reward_pool`. Omitted while the mint has none, holding",
+            "the stakers' share in the fee ledger instead."
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "liquidityStakerAccount",
+          "docs": [
+            "Token account for liquidity stakers (50% of fees)"
+          ],
+          "writable": true
+        },
+        {
+          "name": "treasuryAccount",
+          "docs": [
+            "Token account for protocol treasury (30% of fees)"
+          ],
+          "writable": true
+        },
+        {
+          "name": "bountyAccount",
+          "docs": [
+            "Token account for MEV bounty pool (20% of fees)"
+          ],
+          "writable": true
+        },
+        {
+          "name": "tokenMint",
+          "docs": [
+            "The token mint for the fees being distributed"
+          ]
+        },
+        {
+          "name": "caller",
+          "docs": [
+            "Protocol admin"
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "tokenProgram"
+        }
+      ],
+      "args": [
+        {
+          "name": "feeAmount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "stake",
+      "docs": [
+        "Stake tokens, passing every reward pool as remaining accounts"
+      ],
+      "discriminator": [
+        206,
+        176,
+        202,
+        18,
+        200,
+        209,
+        179,
+        108
+      ],
+      "accounts": [
+        {
+          "name": "stakePool",
+          "writable": true
+        },
+        {
+          "name": "stakeVault",
+          "writable": true
+        },
+        {
+          "name": "stakePosition",
+          "writable": true
+        },
+        {
+          "name": "ownerTokenAccount",
+          "writable": true
+        },
+        {
+          "name": "owner",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "tokenProgram"
+        },
+        {
+          "name": "systemProgram"
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "submitBountyClaim",
+      "docs": [
+        "Submit a claim on a mint's MEV bounty pool with transaction evidence"
+      ],
+      "discriminator": [
+        171,
+        80,
+        83,
+        88,
+        84,
+        51,
+        180,
+        131
+      ],
+      "accounts": [
+        {
+          "name": "bountyClaim",
+          "writable": true
+        },
+        {
+          "name": "mint",
+          "docs": [
+            "Mint of the bounty pool the claim is paid from"
+          ]
+        },
+        {
+          "name": "reporter",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram"
+        }
+      ],
+      "args": [
+        {
+          "name": "claimId",
+          "type": "u64"
+        },
+        {
+          "name": "amount",
+          "type": "u64"
+        },
+        {
+          "name": "evidence",
+          "type": {
+            "vec": {
+              "defined": {
+                "name": "bountyEvidence"
+              }
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "sweepFees",
+      "docs": [
+        "Distribute the whole fee balance of a mint, paying the caller a keeper reward"
+      ],
+      "discriminator": [
+        175,
+        225,
+        98,
+        71,
+        118,
+        66,
+        34,
+        148
+      ],
+      "accounts": [
+        {
+          "name": "config"
+        },
+        {
+          "name": "feeCollectionAuthority"
+        },
+        {
+          "name": "sourceFeeAccount",
+          "writable": true
+        },
+        {
+          "name": "feeLedger",
+          "writable": true
+        },
+        {
+          "name": "stakePool",
+          "optional": true
+        },
+        {
+          "name": "rewardPool",
+          "docs": [
+            "Staker reward accumulator of `token_mint`, created by",
+            "`initialize_reward_pool`. Omitted while the mint has none, holding",
+            "the stakers' share in the fee ledger instead."
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "liquidityStakerAccount",
+          "writable": true
+        },
+        {
+          "name": "treasuryAccount",
+          "writable": true
+        },
+        {
+          "name": "bountyAccount",
+          "writable": true
+        },
+        {
+          "name": "callerTokenAccount",
+          "docs": [
+            "Caller's account receiving the keeper reward"
+          ],
+          "writable": true
+        },
+        {
+          "name": "tokenMint"
+        },
+        {
+          "name": "caller",
+          "signer": true
+        },
+        {
+          "name": "tokenProgram"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "unstake",
+      "docs": [
+        "Unstake tokens, passing every reward pool as remaining accounts"
+      ],
+      "discriminator": [
+        90,
+        95,
+        107,
+        42,
+        205,
+        124,
+        50,
+        225
+      ],
+      "accounts": [
+        {
+          "name": "stakePool",
+          "writable": true
+        },
+        {
+          "name": "stakeVault",
+          "writable": true
+        },
+        {
+          "name": "stakePosition",
+          "writable": true
+        },
+        {
+          "name": "ownerTokenAccount",
+          "writable": true
+        },
+        {
+          "name": "owner",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "tokenProgram"
+        },
+        {
+          "name": "systemProgram"
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "updateMintConfig",
+      "docs": [
+        "Update the risk configuration for a token mint"
+      ],
+      "discriminator": [
+        243,
+        113,
+        184,
+        98,
+        212,
+        234,
+        198,
+        193
+      ],
+      "accounts": [
+        {
+          "name": "config"
+        },
+        {
+          "name": "mintConfig",
+          "writable": true
+        },
+        {
+          "name": "admin",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "oracle",
+          "type": "pubkey"
+        },
+        {
+          "name": "maxDeviationBps",
+          "type": "u16"
+        },
+        {
+          "name": "maxStalenessSecs",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "voteBountyClaim",
+      "docs": [
+        "Approve or reject a pending bounty claim as a committee member"
+      ],
+      "discriminator": [
+        242,
+        92,
+        157,
+        232,
+        205,
+        128,
+        178,
+        29
+      ],
+      "accounts": [
+        {
+          "name": "config"
+        },
+        {
+          "name": "bountyClaim",
+          "writable": true
+        },
+        {
+          "name": "member",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "approve",
+          "type": "bool"
+        }
+      ]
+    }
+  ],
+  "accounts": [
+    {
+      "name": "batch",
+      "discriminator": [
+        156,
+        194,
+        70,
+        44,
+        22,
+        88,
+        137,
+        44
+      ]
+    },
+    {
+      "name": "bountyClaim",
+      "discriminator": [
+        130,
+        113,
+        6,
+        57,
+        79,
+        54,
+        78,
+        205
+      ]
+    },
+    {
+      "name": "config",
+      "discriminator": [
+        155,
+        12,
+        170,
+        224,
+        30,
+        250,
+        204,
+        130
+      ]
+    },
+    {
+      "name": "denylist",
+      "discriminator": [
+        108,
+        181,
+        129,
+        179,
+        64,
+        117,
+        203,
+        63
+      ]
+    },
+    {
+      "name": "feeLedger",
+      "discriminator": [
+        224,
+        34,
+        151,
+        237,
+        107,
+        206,
+        212,
+        70
+      ]
+    },
+    {
+      "name": "mintConfig",
+      "discriminator": [
+        168,
+        252,
+        88,
+        182,
+        219,
+        205,
+        39,
+        53
+      ]
+    },
+    {
+      "name": "referrerStats",
+      "discriminator": [
+        181,
+        235,
+        242,
+        229,
+        103,
+        242,
+        144,
+        118
+      ]
+    },
+    {
+      "name": "relayerRegistration",
+      "discriminator": [
+        158,
+        39,
+        133,
+        97,
+        168,
+        128,
+        61,
+        20
+      ]
+    },
+    {
+      "name": "rewardPool",
+      "discriminator": [
+        134,
+        121,
+        197,
+        211,
+        133,
+        154,
+        82,
+        32
+      ]
+    },
+    {
+      "name": "stakePool",
+      "discriminator": [
+        121,
+        34,
+        206,
+        21,
+        79,
+        127,
+        255,
+        28
+      ]
+    },
+    {
+      "name": "stakePosition",
+      "discriminator": [
+        78,
+        165,
+        30,
+        111,
+        171,
+        125,
+        11,
+        220
+      ]
+    },
+    {
+      "name": "swapIntent",
+      "discriminator": [
+        242,
+        212,
+        249,
+        216,
+        109,
+        94,
+        238,
+        134
+      ]
+    },
+    {
+      "name": "userStats",
+      "discriminator": [
+        176,
+        223,
+        136,
+        27,
+        122,
+        79,
+        32,
+        227
+      ]
+    }
+  ],
+  "events": [
+    {
+      "discriminator": [
+        244,
+        224,
+        208,
+        99,
+        15,
+        223,
+        97,
+        235
+      ],
+      "name": "basketLegFilled"
+    },
+    {
+      "discriminator": [
+        34,
+        181,
+        220,
+        141,
+        174,
+        149,
+        74,
+        166
+      ],
+      "name": "batchFillClaimed"
+    },
+    {
+      "discriminator": [
+        136,
+        32,
+        216,
+        192,
+        63,
+        167,
+        120,
+        74
+      ],
+      "name": "batchOrderRevealed"
+    },
+    {
+      "discriminator": [
+        238,
+        14,
+        187,
+        192,
+        127,
+        95,
+        104,
+        9
+      ],
+      "name": "batchSettled"
+    },
+    {
+      "discriminator": [
+        226,
+        85,
+        208,
+        63,
+        46,
+        67,
+        248,
+        168
+      ],
+      "name": "bountyClaimPaid"
+    },
+    {
+      "discriminator": [
+        70,
+        198,
+        155,
+        197,
+        133,
+        148,
+        159,
+        36
+      ],
+      "name": "bountyClaimResolved"
+    },
+    {
+      "discriminator": [
+        67,
+        172,
+        222,
+        179,
+        234,
+        167,
+        201,
+        207
+      ],
+      "name": "bountyClaimSubmitted"
+    },
+    {
+      "discriminator": [
+        123,
+        53,
+        154,
+        200,
+        56,
+        69,
+        39,
+        73
+      ],
+      "name": "bountyClaimVoted"
+    },
+    {
+      "discriminator": [
+        192,
+        204,
+        36,
+        83,
+        184,
+        110,
+        16,
+        94
+      ],
+      "name": "bountyCommitteeUpdated"
+    },
+    {
+      "discriminator": [
+        27,
+        181,
+        243,
+        69,
+        114,
+        219,
+        150,
+        56
+      ],
+      "name": "denylistUpdated"
+    },
+    {
+      "discriminator": [
+        24,
+        244,
+        176,
+        43,
+        165,
+        149,
+        67,
+        124
+      ],
+      "name": "feeAccountsInitialized"
+    },
+    {
+      "discriminator": [
+        6,
+        133,
+        116,
+        50,
+        44,
+        151,
+        179,
+        65
+      ],
+      "name": "feeDistributed"
+    },
+    {
+      "discriminator": [
+        172,
+        186,
+        57,
+        25,
+        74,
+        242,
+        109,
+        42
+      ],
+      "name": "feeTiersUpdated"
+    },
+    {
+      "discriminator": [
+        96,
+        218,
+        115,
+        136,
+        74,
+        170,
+        202,
+        172
+      ],
+      "name": "feesSwept"
+    },
+    {
+      "discriminator": [
+        236,
+        55,
+        229,
+        27,
+        79,
+        167,
+        17,
+        188
+      ],
+      "name": "intentsMatched"
+    },
+    {
+      "discriminator": [
+        94,
+        146,
+        233,
+        57,
+        43,
+        116,
+        216,
+        165
+      ],
+      "name": "mintConfigUpdated"
+    },
+    {
+      "discriminator": [
+        21,
+        105,
+        162,
+        48,
+        145,
+        3,
+        33,
+        96
+      ],
+      "name": "mintVolumeLimitUpdated"
+    },
+    {
+      "discriminator": [
+        227,
+        156,
+        225,
+        65,
+        45,
+        201,
+        121,
+        199
+      ],
+      "name": "referralCapUpdated"
+    },
+    {
+      "discriminator": [
+        61,
+        150,
+        100,
+        8,
+        77,
+        232,
+        225,
+        120
+      ],
+      "name": "relayerFeeCapUpdated"
+    },
+    {
+      "discriminator": [
+        80,
+        201,
+        229,
+        141,
+        153,
+        148,
+        37,
+        32
+      ],
+      "name": "relayerStatusChanged"
+    },
+    {
+      "discriminator": [
+        75,
+        98,
+        88,
+        18,
+        219,
+        112,
+        88,
+        121
+      ],
+      "name": "rewardsClaimed"
+    },
+    {
+      "discriminator": [
+        76,
+        253,
+        121,
+        188,
+        73,
+        178,
+        211,
+        139
+      ],
+      "name": "sliceExecuted"
+    },
+    {
+      "discriminator": [
+        11,
+        146,
+        45,
+        205,
+        230,
+        58,
+        213,
+        240
+      ],
+      "name": "staked"
+    },
+    {
+      "discriminator": [
+        13,
+        185,
+        35,
+        32,
+        93,
+        185,
+        108,
+        6
+      ],
+      "name": "surplusShareUpdated"
+    },
+    {
+      "discriminator": [
+        0,
+        148,
+        240,
+        52,
+        234,
+        154,
+        40,
+        217
+      ],
+      "name": "sweepParamsUpdated"
+    },
+    {
+      "discriminator": [
+        41,
+        110,
+        64,
+        129,
+        60,
+        79,
+        179,
+        80
+      ],
+      "name": "tradeExecuted"
+    },
+    {
+      "discriminator": [
+        27,
+        179,
+        156,
+        215,
+        47,
+        71,
+        195,
+        7
+      ],
+      "name": "unstaked"
+    },
+    {
+      "discriminator": [
+        13,
+        247,
+        72,
+        217,
+        121,
+        239,
+        32,
+        105
+      ],
+      "name": "userVolumeLimitUpdated"
+    }
+  ],
+  "errors": [
+    {
+      "code": 6000,
+      "name": "alreadyRevealed",
+      "msg": "Intent already revealed"
+    },
+    {
+      "code": 6001,
+      "name": "intentExpired",
+      "msg": "Trade intent expired"
+    },
+    {
+      "code": 6002,
+      "name": "nonceMismatch",
+      "msg": "Nonce does not match"
+    },
+    {
+      "code": 6003,
+      "name": "invalidSignature",
+      "msg": "Signature verification failed"
+    },
+    {
+      "code": 6004,
+      "name": "hashMismatch",
+      "msg": "Hash mismatch between reveal and commit"
+    },
+    {
+      "code": 6005,
+      "name": "insufficientBalance",
+      "msg": "Insufficient token balance"
+    },
+    {
+      "code": 6006,
+      "name": "mathOverflow",
+      "msg": "Mathematical overflow occurred"
+    },
+    {
+      "code": 6007,
+      "name": "slippageExceeded",
+      "msg": "Slippage tolerance exceeded"
+    },
+    {
+      "code": 6008,
+      "name": "invalidTokenMint",
+      "msg": "Invalid token mint"
+    },
+    {
+      "code": 6009,
+      "name": "relayerFeeTooHigh",
+      "msg": "Relayer fee too high"
+    },
+    {
+      "code": 6010,
+      "name": "protocolFeeError",
+      "msg": "Protocol fee calculation failed"
+    },
+    {
+      "code": 6011,
+      "name": "feeDistributionError",
+      "msg": "Fee distribution failed"
+    },
+    {
+      "code": 6012,
+      "name": "swapExecutionFailed",
+      "msg": "Swap execution failed"
+    },
+    {
+      "code": 6013,
+      "name": "invalidRelayer",
+      "msg": "Invalid relayer"
+    },
+    {
+      "code": 6014,
+      "name": "amountTooSmall",
+      "msg": "Trade amount too small"
+    },
+    {
+      "code": 6015,
+      "name": "amountTooLarge",
+      "msg": "Trade amount too large"
+    },
+    {
+      "code": 6016,
+      "name": "unauthorized",
+      "msg": "Signer is not authorized for this action"
+    },
+    {
+      "code": 6017,
+      "name": "relayerNotRegistered",
+      "msg": "Relayer is not registered or inactive"
+    },
+    {
+      "code": 6018,
+      "name": "exclusivityWindowActive",
+      "msg": "Preferred relayer exclusivity window still active"
+    },
+    {
+      "code": 6019,
+      "name": "invalidIntentParams",
+      "msg": "Invalid intent parameters"
+    },
+    {
+      "code": 6020,
+      "name": "partialFillNotAllowed",
+      "msg": "Partial fills are not allowed for this intent"
+    },
+    {
+      "code": 6021,
+      "name": "fillExceedsRemaining",
+      "msg": "Fill amount exceeds the unfilled remainder of the intent"
+    },
+    {
+      "code": 6022,
+      "name": "routeTooLong",
+      "msg": "Route has too many hops"
+    },
+    {
+      "code": 6023,
+      "name": "hopSlippageExceeded",
+      "msg": "Intermediate hop output below its minimum"
+    },
+    {
+      "code": 6024,
+      "name": "unsupportedIntentKind",
+      "msg": "Intent kind is not supported by this instruction"
+    },
+    {
+      "code": 6025,
+      "name": "intentsNotCompatible",
+      "msg": "Intents are not compatible for matching"
+    },
+    {
+      "code": 6026,
+      "name": "batchWindowClosed",
+      "msg": "Batch window has closed"
+    },
+    {
+      "code": 6027,
+      "name": "batchWindowOpen",
+      "msg": "Batch window is still open"
+    },
+    {
+      "code": 6028,
+      "name": "batchAlreadySettled",
+      "msg": "Batch already settled"
+    },
+    {
+      "code": 6029,
+      "name": "batchFull",
+      "msg": "Batch has no room for more orders"
+    },
+    {
+      "code": 6030,
+      "name": "alreadyClaimed",
+      "msg": "Already claimed"
+    },
+    {
+      "code": 6031,
+      "name": "invalidOracle",
+      "msg": "Oracle account is missing or invalid"
+    },
+    {
+      "code": 6032,
+      "name": "staleOraclePrice",
+      "msg": "Oracle price is stale"
+    },
+    {
+      "code": 6033,
+      "name": "oraclePriceDeviation",
+      "msg": "Execution price deviates too far from the oracle price"
+    },
+    {
+      "code": 6034,
+      "name": "invalidConfigParams",
+      "msg": "Invalid configuration parameters"
+    },
+    {
+      "code": 6035,
+      "name": "triggerNotReached",
+      "msg": "Oracle price has not crossed the trigger price"
+    },
+    {
+      "code": 6036,
+      "name": "sliceIntervalNotElapsed",
+      "msg": "Recurring intent interval has not elapsed"
+    },
+    {
+      "code": 6037,
+      "name": "invalidRecipient",
+      "msg": "Output account is not owned by the intent recipient"
+    },
+    {
+      "code": 6038,
+      "name": "bountyAccountRequired",
+      "msg": "MEV bounty account is required to share surplus"
+    },
+    {
+      "code": 6039,
+      "name": "referralFeeTooHigh",
+      "msg": "Referral fee exceeds the configured maximum"
+    },
+    {
+      "code": 6040,
+      "name": "invalidReferrer",
+      "msg": "Referrer accounts are missing or invalid"
+    },
+    {
+      "code": 6041,
+      "name": "volumeLimitExceeded",
+      "msg": "Trade exceeds a configured volume limit"
+    },
+    {
+      "code": 6042,
+      "name": "denylisted",
+      "msg": "Wallet or mint is denylisted"
+    },
+    {
+      "code": 6043,
+      "name": "denylistFull",
+      "msg": "Denylist has no room for more entries"
+    },
+    {
+      "code": 6044,
+      "name": "sweepTooSoon",
+      "msg": "Fees of this mint were swept too recently"
+    },
+    {
+      "code": 6045,
+      "name": "invalidSettlementAccounts",
+      "msg": "Fee settlement accounts are missing or not the mint's canonical accounts"
+    },
+    {
+      "code": 6046,
+      "name": "rewardMintsFull",
+      "msg": "Stake pool has no room for more reward mints"
+    },
+    {
+      "code": 6047,
+      "name": "invalidRewardPool",
+      "msg": "Reward pool accounts are missing or do not match the stake pool"
+    },
+    {
+      "code": 6048,
+      "name": "notCommitteeMember",
+      "msg": "Signer is not a bounty committee member"
+    },
+    {
+      "code": 6049,
+      "name": "alreadyVoted",
+      "msg": "Committee member already voted on this claim"
+    },
+    {
+      "code": 6050,
+      "name": "bountyClaimNotPending",
+      "msg": "Bounty claim is no longer pending"
+    },
+    {
+      "code": 6051,
+      "name": "bountyClaimNotApproved",
+      "msg": "Bounty claim has not been approved"
+    },
+    {
+      "code": 6052,
+      "name": "invalidBountyEvidence",
+      "msg": "Bounty claim evidence is empty or too long"
+    },
+    {
+      "code": 6053,
+      "name": "routeNotSupported",
+      "msg": "Multi-hop routes can only be filled through reveal_trade"
+    },
+    {
+      "code": 6054,
+      "name": "referralNotSupported",
+      "msg": "Referral fees are only paid on reveal_trade fills"
+    },
+    {
+      "code": 6055,
+      "name": "notionalOracleRequired",
+      "msg": "A daily notional limit requires an oracle price for the input mint"
+    },
+    {
+      "code": 6056,
+      "name": "sliceDelegateMissing",
+      "msg": "Intent is not delegated enough of the input account to fill this slice"
+    },
+    {
+      "code": 6057,
+      "name": "selfMatch",
+      "msg": "Both sides of a match belong to the same user"
+    }
+  ],
+  "types": [
+    {
+      "name": "basketLeg",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tokenOut",
+            "type": "pubkey"
+          },
+          {
+            "name": "weightBps",
+            "type": "u16"
+          },
+          {
+            "name": "minOut",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "basketLegFilled",
+      "type": {
+        "fields": [
+          {
+            "name": "user",
+            "type": "pubkey"
+          },
+          {
+            "name": "tokenOut",
+            "type": "pubkey"
+          },
+          {
+            "name": "amountOut",
+            "type": "u64"
+          },
+          {
+            "name": "nonce",
+            "type": "u64"
+          }
+        ],
+        "kind": "struct"
+      }
+    },
+    {
+      "name": "batch",
+      "docs": [
+        "Orders for one token pair revealed within one batch window, settled",
+        "together at a single clearing price"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "baseMint",
+            "type": "pubkey"
+          },
+          {
+            "name": "quoteMint",
+            "type": "pubkey"
+          },
+          {
+            "name": "epoch",
+            "type": "u64"
+          },
+          {
+            "name": "settled",
+            "type": "bool"
+          },
+          {
+            "name": "priceNum",
+            "docs": [
+              "Clearing price in quote per base as `price_num / price_den`;",
+              "zero when nothing crossed and every order is refunded"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "priceDen",
+            "type": "u64"
+          },
+          {
+            "name": "baseEligible",
+            "docs": [
+              "Base offered by sellers whose limit the clearing price satisfies"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "baseFilled",
+            "docs": [
+              "Portion of `base_eligible` that was filled"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "quoteEligible",
+            "docs": [
+              "Quote offered by buyers whose limit the clearing price satisfies"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "quoteFilled",
+            "docs": [
+              "Portion of `quote_eligible` that was filled"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "orders",
+            "type": {
+              "vec": {
+                "defined": {
+                  "name": "batchOrder"
+                }
+              }
+            }
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "batchFillClaimed",
+      "type": {
+        "fields": [
+          {
+            "name": "batch",
+            "type": "pubkey"
+          },
+          {
+            "name": "owner",
+            "type": "pubkey"
+          },
+          {
+            "name": "orderIndex",
+            "type": "u8"
+          },
+          {
+            "name": "baseOut",
+            "type": "u64"
+          },
+          {
+            "name": "quoteOut",
+            "type": "u64"
+          },
+          {
+            "docs": [
+              "Escrowed protocol fee collected on the filled input"
+            ],
+            "name": "protocolFee",
+            "type": "u64"
+          }
+        ],
+        "kind": "struct"
+      }
+    },
+    {
+      "name": "batchOrder",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "owner",
+            "type": "pubkey"
+          },
+          {
+            "name": "recipient",
+            "docs": [
+              "Owner of the account that receives the fill"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "sellsBase",
+            "docs": [
+              "True when selling base for quote"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "amountIn",
+            "docs": [
+              "Input escrowed in the batch vault, net of protocol fee"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "protocolFee",
+            "docs": [
+              "Protocol fee escrowed alongside `amount_in`, collected on the part",
+              "that fills and refunded on the rest"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "minOut",
+            "type": "u64"
+          },
+          {
+            "name": "claimed",
+            "type": "bool"
+          }
+        ]
+      }
+    },
+    {
+      "name": "batchOrderRevealed",
+      "type": {
+        "fields": [
+          {
+            "name": "batch",
+            "type": "pubkey"
+          },
+          {
+            "name": "user",
+            "type": "pubkey"
+          },
+          {
+            "name": "orderIndex",
+            "type": "u8"
+          },
+          {
+            "name": "sellsBase",
+            "type": "bool"
+          },
+          {
+            "name": "amountIn",
+            "type": "u64"
+          },
+          {
+            "name": "minOut",
+            "type": "u64"
+          },
+          {
+            "name": "protocolFee",
+            "type": "u64"
+          },
+          {
+            "docs": [
+              "Volume-tier discount applied to the protocol fee"
+            ],
+            "name": "feeDiscountBps",
+            "type": "u16"
+          },
+          {
+            "name": "nonce",
+            "type": "u64"
+          }
+        ],
+        "kind": "struct"
+      }
+    },
+    {
+      "name": "batchSettled",
+      "type": {
+        "fields": [
+          {
+            "name": "batch",
+            "type": "pubkey"
+          },
+          {
+            "name": "baseMint",
+            "type": "pubkey"
+          },
+          {
+            "name": "quoteMint",
+            "type": "pubkey"
+          },
+          {
+            "name": "epoch",
+            "type": "u64"
+          },
+          {
+            "name": "priceNum",
+            "type": "u64"
+          },
+          {
+            "name": "priceDen",
+            "type": "u64"
+          },
+          {
+            "name": "baseFilled",
+            "type": "u64"
+          },
+          {
+            "name": "quoteFilled",
+            "type": "u64"
+          },
+          {
+            "name": "orderCount",
+            "type": "u8"
+          },
+          {
+            "docs": [
+              "Clearing price was discarded for straying from the oracle"
+            ],
+            "name": "oracleRejected",
+            "type": "bool"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ],
+        "kind": "struct"
+      }
+    },
+    {
+      "name": "bountyClaim",
+      "docs": [
+        "A reporter's claim on the MEV bounty pool of one mint, resolved by the",
+        "bounty committee in `Config`"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "reporter",
+            "type": "pubkey"
+          },
+          {
+            "name": "claimId",
+            "type": "u64"
+          },
+          {
+            "name": "mint",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "docs": [
+              "Bounty requested, paid from the mint's bounty pool once approved"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "evidence",
+            "type": {
+              "vec": {
+                "defined": {
+                  "name": "bountyEvidence"
+                }
+              }
+            }
+          },
+          {
+            "name": "approvals",
+            "docs": [
+              "Votes of current committee members; votes of removed members are",
+              "dropped at the next vote, so both together never exceed the committee"
+            ],
+            "type": {
+              "vec": "pubkey"
+            }
+          },
+          {
+            "name": "rejections",
+            "type": {
+              "vec": "pubkey"
+            }
+          },
+          {
+            "name": "status",
+            "type": {
+              "defined": {
+                "name": "bountyClaimStatus"
+              }
+            }
+          },
+          {
+            "name": "createdAt",
+            "type": "i64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "bountyClaimPaid",
+      "type": {
+        "fields": [
+          {
+            "name": "claim",
+            "type": "pubkey"
+          },
+          {
+            "name": "reporter",
+            "type": "pubkey"
+          },
+          {
+            "name": "mint",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "caller",
+            "type": "pubkey"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ],
+        "kind": "struct"
+      }
+    },
+    {
+      "name": "bountyClaimResolved",
+      "type": {
+        "fields": [
+          {
+            "name": "claim",
+            "type": "pubkey"
+          },
+          {
+            "name": "status",
+            "type": {
+              "defined": {
+                "name": "bountyClaimStatus"
+              }
+            }
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ],
+        "kind": "struct"
+      }
+    },
+    {
+      "name": "bountyClaimStatus",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "pending"
+          },
+          {
+            "name": "approved"
+          },
+          {
+            "name": "rejected"
+          },
+          {
+            "name": "paid"
+          }
+        ]
+      }
+    },
+    {
+      "name": "bountyClaimSubmitted",
+      "type": {
+        "fields": [
+          {
+            "name": "claim",
+            "type": "pubkey"
+          },
+          {
+            "name": "reporter",
+            "type": "pubkey"
+          },
+          {
+            "name": "claimId",
+            "type": "u64"
+          },
+          {
+            "name": "mint",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "evidenceCount",
+            "type": "u8"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ],
+        "kind": "struct"
+      }
+    },
+    {
+      "name": "bountyClaimVoted",
+      "type": {
+        "fields": [
+          {
+            "name": "claim",
+            "type": "pubkey"
+          },
+          {
+            "name": "member",
+            "type": "pubkey"
+          },
+          {
+            "name": "approve",
+            "type": "bool"
+          },
+          {
+            "name": "approvals",
+            "type": "u8"
+          },
+          {
+            "name": "rejections",
+            "type": "u8"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ],
+        "kind": "struct"
+      }
+    },
+    {
+      "name": "bountyCommitteeUpdated",
+      "type": {
+        "fields": [
+          {
+            "name": "members",
+            "type": {
+              "vec": "pubkey"
+            }
+          },
+          {
+            "name": "threshold",
+            "type": "u8"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ],
+        "kind": "struct"
+      }
+    },
+    {
+      "name": "bountyEvidence",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "txSignature",
+            "docs": [
+              "Signature of the offending transaction"
+            ],
+            "type": {
+              "array": [
+                "u8",
+                64
+              ]
+            }
+          },
+          {
+            "name": "amount",
+            "docs": [
+              "Value extracted by that transaction"
+            ],
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "config",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "admin",
+            "type": "pubkey"
+          },
+          {
+            "name": "feeBps",
+            "type": "u16"
+          },
+          {
+            "name": "maxRelayerFeeBps",
+            "docs": [
+              "Cap on an intent's relayer fee, in bps of the amount it is paid from"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "surplusBountyBps",
+            "docs": [
+              "Share of output above an intent's `quoted_out` paid to the MEV bounty pool"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "maxReferralBps",
+            "docs": [
+              "Cap on an intent's `referral_bps`"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "feeTiers",
+            "docs": [
+              "Protocol fee discounts by rolling 30-day volume, ascending by `min_volume`"
+            ],
+            "type": {
+              "vec": {
+                "defined": {
+                  "name": "feeTier"
+                }
+              }
+            }
+          },
+          {
+            "name": "maxUserDailyNotional",
+            "docs": [
+              "Maximum notional a user may trade per day; zero disables the limit.",
+              "While set, fills need an oracle price for their input mint."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "keeperRewardBps",
+            "docs": [
+              "Share of a fee sweep paid to the caller, in bps of the swept balance"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "minSweepIntervalSecs",
+            "docs": [
+              "Minimum seconds between two sweeps of the same mint"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "rewardStreamSecs",
+            "docs": [
+              "Seconds over which settled staker rewards are streamed to stakers"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "bountyCommittee",
+            "docs": [
+              "Members voting on MEV bounty claims"
+            ],
+            "type": {
+              "vec": "pubkey"
+            }
+          },
+          {
+            "name": "bountyThreshold",
+            "docs": [
+              "Approvals needed to pay a bounty claim"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "isPaused",
+            "type": "bool"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "denylist",
+      "docs": [
+        "Admin-managed compliance denylist. Both lists are kept sorted so",
+        "membership checks are binary searches."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "wallets",
+            "type": {
+              "vec": "pubkey"
+            }
+          },
+          {
+            "name": "mints",
+            "type": {
+              "vec": "pubkey"
+            }
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "denylistKind",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "wallet"
+          },
+          {
+            "name": "mint"
+          }
+        ]
+      }
+    },
+    {
+      "name": "denylistUpdated",
+      "type": {
+        "fields": [
+          {
+            "name": "kind",
+            "type": {
+              "defined": {
+                "name": "denylistKind"
+              }
+            }
+          },
+          {
+            "name": "key",
+            "type": "pubkey"
+          },
+          {
+            "name": "listed",
+            "type": "bool"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ],
+        "kind": "struct"
+      }
+    },
+    {
+      "name": "feeAccountsInitialized",
+      "type": {
+        "fields": [
+          {
+            "name": "tokenMint",
+            "type": "pubkey"
+          },
+          {
+            "name": "feeAuthority",
+            "type": "pubkey"
+          },
+          {
+            "name": "liquidityStakers",
+            "type": "pubkey"
+          },
+          {
+            "name": "treasury",
+            "type": "pubkey"
+          },
+          {
+            "name": "mevBounty",
+            "type": "pubkey"
+          },
+          {
+            "name": "feeCollection",
+            "type": "pubkey"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ],
+        "kind": "struct"
+      }
+    },
+    {
+      "name": "feeDistributed",
+      "type": {
+        "fields": [
+          {
+            "name": "tokenMint",
+            "type": "pubkey"
+          },
+          {
+            "name": "totalFee",
+            "type": "u64"
+          },
+          {
+            "name": "liquidityStakersFee",
+            "type": "u64"
+          },
+          {
+            "name": "treasuryFee",
+            "type": "u64"
+          },
+          {
+            "name": "mevBountyFee",
+            "type": "u64"
+          },
+          {
+            "name": "caller",
+            "type": "pubkey"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ],
+        "kind": "struct"
+      }
+    },
+    {
+      "name": "feeLedger",
+      "docs": [
+        "Running totals of protocol fees in one mint. `total_collected` less",
+        "`total_distributed` is what the fee collection account should hold."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "mint",
+            "type": "pubkey"
+          },
+          {
+            "name": "totalCollected",
+            "type": "u64"
+          },
+          {
+            "name": "totalDistributed",
+            "type": "u64"
+          },
+          {
+            "name": "lastSweepAt",
+            "docs": [
+              "Time of the last `sweep_fees`, zero before the first"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "pendingStakerRewards",
+            "docs": [
+              "Stakers' share settled while the mint had no reward pool, queued",
+              "into the pool by the first settlement that passes one"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "authorityBump",
+            "docs": [
+              "Bumps of the mint's fee authority and fee token account PDAs, so",
+              "`settle_many` can check them without searching for each bump"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "collectionBump",
+            "type": "u8"
+          },
+          {
+            "name": "stakersBump",
+            "type": "u8"
+          },
+          {
+            "name": "treasuryBump",
+            "type": "u8"
+          },
+          {
+            "name": "bountyBump",
+            "type": "u8"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "feeTier",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "minVolume",
+            "docs": [
+              "Rolling notional volume needed to reach this tier"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "discountBps",
+            "docs": [
+              "Discount on the protocol fee, in bps of the fee"
+            ],
+            "type": "u16"
+          }
+        ]
+      }
+    },
+    {
+      "name": "feeTiersUpdated",
+      "type": {
+        "fields": [
+          {
+            "name": "feeTiers",
+            "type": {
+              "vec": {
+                "defined": {
+                  "name": "feeTier"
+                }
+              }
+            }
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ],
+        "kind": "struct"
+      }
+    },
+    {
+      "name": "feeToken",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "tokenIn"
+          },
+          {
+            "name": "tokenOut"
+          }
+        ]
+      }
+    },
+    {
+      "name": "feesSwept",
+      "type": {
+        "fields": [
+          {
+            "name": "tokenMint",
+            "type": "pubkey"
+          },
+          {
+            "name": "totalFee",
+            "type": "u64"
+          },
+          {
+            "name": "liquidityStakersFee",
+            "type": "u64"
+          },
+          {
+            "name": "treasuryFee",
+            "type": "u64"
+          },
+          {
+            "name": "mevBountyFee",
+            "type": "u64"
+          },
+          {
+            "name": "keeperReward",
+            "type": "u64"
+          },
+          {
+            "name": "caller",
+            "type": "pubkey"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ],
+        "kind": "struct"
+      }
+    },
+    {
+      "name": "intentKind",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "standard"
+          },
+          {
+            "name": "dutchAuction",
+            "fields": [
+              {
+                "name": "startMinOut",
+                "type": "u64"
+              },
+              {
+                "name": "endMinOut",
+                "type": "u64"
+              },
+              {
+                "name": "startTime",
+                "type": "i64"
+              },
+              {
+                "name": "endTime",
+                "type": "i64"
+              }
+            ]
+          },
+          {
+            "name": "exactOut",
+            "fields": [
+              {
+                "name": "amountOut",
+                "type": "u64"
+              },
+              {
+                "name": "maxIn",
+                "type": "u64"
+              }
+            ]
+          },
+          {
+            "name": "basket",
+            "fields": [
+              {
+                "name": "legs",
+                "type": {
+                  "vec": {
+                    "defined": {
+                      "name": "basketLeg"
+                    }
+                  }
+                }
+              }
+            ]
+          },
+          {
+            "name": "conditional",
+            "fields": [
+              {
+                "name": "triggerPrice",
+                "type": "u64"
+              },
+              {
+                "name": "direction",
+                "type": {
+                  "defined": {
+                    "name": "triggerDirection"
+                  }
+                }
+              }
+            ]
+          },
+          {
+            "name": "recurring",
+            "fields": [
+              {
+                "name": "sliceAmountIn",
+                "type": "u64"
+              },
+              {
+                "name": "intervalSecs",
+                "type": "u64"
+              },
+              {
+                "name": "sliceMinOut",
+                "type": "u64"
+              }
+            ]
+          }
+        ]
+      }
+    },
+    {
+      "name": "intentsMatched",
+      "type": {
+        "fields": [
+          {
+            "name": "userA",
+            "type": "pubkey"
+          },
+          {
+            "name": "userB",
+            "type": "pubkey"
+          },
+          {
+            "name": "nonceA",
+            "type": "u64"
+          },
+          {
+            "name": "nonceB",
+            "type": "u64"
+          },
+          {
+            "name": "relayer",
+            "type": "pubkey"
+          },
+          {
+            "name": "tokenA",
+            "type": "pubkey"
+          },
+          {
+            "name": "tokenB",
+            "type": "pubkey"
+          },
+          {
+            "name": "matchedA",
+            "type": "u64"
+          },
+          {
+            "name": "matchedB",
+            "type": "u64"
+          },
+          {
+            "name": "relayerFillA",
+            "type": "u64"
+          },
+          {
+            "name": "relayerFillB",
+            "type": "u64"
+          },
+          {
+            "name": "protocolFeeA",
+            "type": "u64"
+          },
+          {
+            "name": "protocolFeeB",
+            "type": "u64"
+          },
+          {
+            "docs": [
+              "Volume-tier discounts applied to each side's protocol fee"
+            ],
+            "name": "feeDiscountBpsA",
+            "type": "u16"
+          },
+          {
+            "name": "feeDiscountBpsB",
+            "type": "u16"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ],
+        "kind": "struct"
+      }
     },
     {
-      "code": 6003,
-      "name": "invalidSignature",
-      "msg": "Signature verification failed"
+      "name": "mintConfig",
+      "docs": [
+        "Per-mint risk configuration managed by the protocol admin"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "mint",
+            "type": "pubkey"
+          },
+          {
+            "name": "oracle",
+            "docs": [
+              "Pyth price account for this mint; `Pubkey::default()` disables the guard"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "maxDeviationBps",
+            "docs": [
+              "Maximum deviation of a fill's effective price from the oracle price"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "maxStalenessSecs",
+            "docs": [
+              "Maximum age of the oracle price at reveal time"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "volumeLimit",
+            "docs": [
+              "Maximum volume of this mint, in base units, per volume epoch; zero",
+              "disables the limit"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "volumeEpochSecs",
+            "type": "u64"
+          },
+          {
+            "name": "epochStart",
+            "docs": [
+              "Start of the current volume epoch"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "epochVolume",
+            "docs": [
+              "Volume of this mint traded in the current epoch"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
     },
     {
-      "code": 6004,
-      "name": "hashMismatch",
-      "msg": "Hash mismatch between reveal and commit"
+      "name": "mintConfigUpdated",
+      "type": {
+        "fields": [
+          {
+            "name": "mint",
+            "type": "pubkey"
+          },
+          {
+            "name": "oracle",
+            "type": "pubkey"
+          },
+          {
+            "name": "maxDeviationBps",
+            "type": "u16"
+          },
+          {
+            "name": "maxStalenessSecs",
+            "type": "u64"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ],
+        "kind": "struct"
+      }
     },
     {
-      "code": 6005,
-      "name": "insufficientBalance",
-      "msg": "Insufficient token balance"
+      "name": "mintVolumeLimitUpdated",
+      "type": {
+        "fields": [
+          {
+            "name": "mint",
+            "type": "pubkey"
+          },
+          {
+            "name": "volumeLimit",
+            "type": "u64"
+          },
+          {
+            "name": "volumeEpochSecs",
+            "type": "u64"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ],
+        "kind": "struct"
+      }
     },
     {
-      "code": 6006,
-      "name": "mathOverflow",
-      "msg": "Mathematical overflow occurred"
+      "name": "positionReward",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "rewardDebt",
+            "docs": [
+              "`amount * acc_reward_per_share` already accounted for"
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "pending",
+            "docs": [
+              "Accrued and not yet claimed"
+            ],
+            "type": "u64"
+          }
+        ]
+      }
     },
     {
-      "code": 6007,
-      "name": "slippageExceeded",
-      "msg": "Slippage tolerance exceeded"
+      "name": "referralCapUpdated",
+      "type": {
+        "fields": [
+          {
+            "name": "maxReferralBps",
+            "type": "u16"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ],
+        "kind": "struct"
+      }
     },
     {
-      "code": 6008,
-      "name": "invalidTokenMint",
-      "msg": "Invalid token mint"
+      "name": "referrerStats",
+      "docs": [
+        "Cumulative referral earnings of one referrer in one mint"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "referrer",
+            "type": "pubkey"
+          },
+          {
+            "name": "mint",
+            "type": "pubkey"
+          },
+          {
+            "name": "totalEarned",
+            "type": "u64"
+          },
+          {
+            "name": "referredTrades",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
     },
     {
-      "code": 6009,
-      "name": "relayerFeeTooHigh",
-      "msg": "Relayer fee too high"
+      "name": "relayerFeeCapUpdated",
+      "type": {
+        "fields": [
+          {
+            "name": "maxRelayerFeeBps",
+            "type": "u16"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ],
+        "kind": "struct"
+      }
     },
     {
-      "code": 6010,
-      "name": "protocolFeeError",
-      "msg": "Protocol fee calculation failed"
+      "name": "relayerRegistration",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "relayer",
+            "type": "pubkey"
+          },
+          {
+            "name": "active",
+            "type": "bool"
+          },
+          {
+            "name": "registeredAt",
+            "type": "i64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
     },
     {
-      "code": 6011,
-      "name": "feeDistributionError",
-      "msg": "Fee distribution failed"
+      "name": "relayerStatusChanged",
+      "type": {
+        "fields": [
+          {
+            "name": "relayer",
+            "type": "pubkey"
+          },
+          {
+            "name": "active",
+            "type": "bool"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ],
+        "kind": "struct"
+      }
     },
     {
-      "code": 6012,
-      "name": "swapExecutionFailed",
-      "msg": "Swap execution failed"
+      "name": "rewardPool",
+      "docs": [
+        "Reward accumulator of one fee mint, paid out of that mint's liquidity",
+        "staker account. Settled rewards are streamed into the accumulator over",
+        "time rather than at once, so stake added just before a settlement and",
+        "removed right after it earns nothing from that settlement."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "mint",
+            "type": "pubkey"
+          },
+          {
+            "name": "accRewardPerShare",
+            "docs": [
+              "Rewards per staked token, scaled by `REWARD_PRECISION`"
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "queued",
+            "docs": [
+              "Settled rewards not yet streamed into the accumulator"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "streamEnd",
+            "docs": [
+              "Time by which `queued` is fully streamed"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "lastUpdate",
+            "docs": [
+              "Time the accumulator was last brought up to date"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "totalAccrued",
+            "type": "u64"
+          },
+          {
+            "name": "totalClaimed",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
     },
     {
-      "code": 6013,
-      "name": "invalidRelayer",
-      "msg": "Invalid relayer"
+      "name": "rewardsClaimed",
+      "type": {
+        "fields": [
+          {
+            "name": "owner",
+            "type": "pubkey"
+          },
+          {
+            "name": "feeMint",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ],
+        "kind": "struct"
+      }
     },
     {
-      "code": 6014,
-      "name": "amountTooSmall",
-      "msg": "Trade amount too small"
+      "name": "routeHop",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "mint",
+            "type": "pubkey"
+          },
+          {
+            "name": "minOut",
+            "docs": [
+              "Minimum amount of `mint` this hop must produce; zero for no floor"
+            ],
+            "type": "u64"
+          }
+        ]
+      }
     },
     {
-      "code": 6015,
-      "name": "amountTooLarge",
-      "msg": "Trade amount too large"
-    }
-  ],
-  "types": [
-    {
-      "name": "feeAccountsInitialized",
+      "name": "sliceExecuted",
       "type": {
-        "kind": "struct",
         "fields": [
           {
-            "name": "tokenMint",
+            "name": "user",
             "type": "pubkey"
           },
           {
-            "name": "feeAuthority",
+            "name": "relayer",
             "type": "pubkey"
           },
           {
-            "name": "liquidityStakers",
+            "name": "tokenIn",
             "type": "pubkey"
           },
           {
-            "name": "treasury",
+            "name": "tokenOut",
             "type": "pubkey"
           },
           {
-            "name": "mevBounty",
-            "type": "pubkey"
+            "name": "amountIn",
+            "type": "u64"
           },
           {
-            "name": "feeCollection",
-            "type": "pubkey"
+            "name": "amountOut",
+            "type": "u64"
+          },
+          {
+            "name": "protocolFee",
+            "type": "u64"
+          },
+          {
+            "docs": [
+              "Volume-tier discount applied to the protocol fee"
+            ],
+            "name": "feeDiscountBps",
+            "type": "u16"
+          },
+          {
+            "name": "relayerFee",
+            "type": "u64"
+          },
+          {
+            "name": "totalFilledIn",
+            "type": "u64"
+          },
+          {
+            "name": "completed",
+            "type": "bool"
+          },
+          {
+            "name": "nonce",
+            "type": "u64"
           },
           {
             "name": "timestamp",
             "type": "i64"
           }
+        ],
+        "kind": "struct"
+      }
+    },
+    {
+      "name": "stakePool",
+      "docs": [
+        "Global pool of staked tokens. Stakers earn the liquidity staker share of",
+        "protocol fees in every mint listed in `reward_mints`."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "stakeMint",
+            "type": "pubkey"
+          },
+          {
+            "name": "totalStaked",
+            "type": "u64"
+          },
+          {
+            "name": "rewardMints",
+            "docs": [
+              "Fee mints with a reward pool, in registration order"
+            ],
+            "type": {
+              "vec": "pubkey"
+            }
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
         ]
       }
     },
     {
-      "name": "feeDistributed",
+      "name": "stakePosition",
+      "docs": [
+        "Stake of one owner and its reward checkpoints, indexed like",
+        "`StakePool::reward_mints`"
+      ],
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "tokenMint",
+            "name": "owner",
             "type": "pubkey"
           },
           {
-            "name": "totalFee",
+            "name": "amount",
             "type": "u64"
           },
           {
-            "name": "liquidityStakersFee",
+            "name": "rewards",
+            "type": {
+              "vec": {
+                "defined": {
+                  "name": "positionReward"
+                }
+              }
+            }
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "staked",
+      "type": {
+        "fields": [
+          {
+            "name": "owner",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
             "type": "u64"
           },
           {
-            "name": "treasuryFee",
+            "name": "positionAmount",
             "type": "u64"
           },
           {
-            "name": "mevBountyFee",
+            "name": "totalStaked",
             "type": "u64"
           },
           {
-            "name": "caller",
-            "type": "pubkey"
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ],
+        "kind": "struct"
+      }
+    },
+    {
+      "name": "surplusShareUpdated",
+      "type": {
+        "fields": [
+          {
+            "name": "surplusBountyBps",
+            "type": "u16"
           },
           {
             "name": "timestamp",
             "type": "i64"
           }
-        ]
+        ],
+        "kind": "struct"
       }
     },
     {
@@ -838,6 +4768,24 @@ export type Unikron = {
             "name": "timestamp",
             "type": "i64"
           },
+          {
+            "name": "commitSlot",
+            "type": "u64"
+          },
+          {
+            "name": "filledIn",
+            "docs": [
+              "Cumulative `amount_in` filled across reveals"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "lastFillAt",
+            "docs": [
+              "Time of the most recent fill, used to space recurring slices"
+            ],
+            "type": "i64"
+          },
           {
             "name": "revealed",
             "type": "bool"
@@ -845,10 +4793,33 @@ export type Unikron = {
         ]
       }
     },
+    {
+      "name": "sweepParamsUpdated",
+      "type": {
+        "fields": [
+          {
+            "name": "keeperRewardBps",
+            "type": "u16"
+          },
+          {
+            "name": "minSweepIntervalSecs",
+            "type": "i64"
+          },
+          {
+            "name": "rewardStreamSecs",
+            "type": "i64"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ],
+        "kind": "struct"
+      }
+    },
     {
       "name": "tradeExecuted",
       "type": {
-        "kind": "struct",
         "fields": [
           {
             "name": "user",
@@ -878,10 +4849,55 @@ export type Unikron = {
             "name": "protocolFee",
             "type": "u64"
           },
+          {
+            "docs": [
+              "Volume-tier discount applied to the protocol fee"
+            ],
+            "name": "feeDiscountBps",
+            "type": "u16"
+          },
           {
             "name": "relayerFee",
             "type": "u64"
           },
+          {
+            "name": "relayerFeeToken",
+            "type": {
+              "defined": {
+                "name": "feeToken"
+              }
+            }
+          },
+          {
+            "docs": [
+              "Output above the pro-rata `quoted_out`"
+            ],
+            "name": "surplus",
+            "type": "u64"
+          },
+          {
+            "docs": [
+              "Portion of `surplus` paid to the MEV bounty pool"
+            ],
+            "name": "surplusToBounty",
+            "type": "u64"
+          },
+          {
+            "name": "referrer",
+            "type": "pubkey"
+          },
+          {
+            "name": "referralFee",
+            "type": "u64"
+          },
+          {
+            "name": "totalFilledIn",
+            "type": "u64"
+          },
+          {
+            "name": "unusedIn",
+            "type": "u64"
+          },
           {
             "name": "nonce",
             "type": "u64"
@@ -890,7 +4906,8 @@ export type Unikron = {
             "name": "timestamp",
             "type": "i64"
           }
-        ]
+        ],
+        "kind": "struct"
       }
     },
     {
@@ -918,6 +4935,17 @@ export type Unikron = {
             "name": "relayerFee",
             "type": "u64"
           },
+          {
+            "name": "relayerFeeToken",
+            "docs": [
+              "Token the relayer fee is paid in"
+            ],
+            "type": {
+              "defined": {
+                "name": "feeToken"
+              }
+            }
+          },
           {
             "name": "tokenIn",
             "type": "pubkey"
@@ -933,9 +4961,177 @@ export type Unikron = {
           {
             "name": "minOut",
             "type": "u64"
+          },
+          {
+            "name": "quotedOut",
+            "docs": [
+              "Output quoted to the user for `token_out`; on `reveal_trade` fills,",
+              "output above it is surplus shared with the MEV bounty pool. Zero",
+              "disables sharing."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "recipient",
+            "docs": [
+              "Owner of the account that receives the output"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "referrer",
+            "docs": [
+              "Frontend or partner credited with the flow; `Pubkey::default()` for none"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "referralBps",
+            "docs": [
+              "Referral fee in bps of the input, paid to `referrer` on top of it.",
+              "Only `reveal_trade` pays referrals; other fill paths reject intents",
+              "that carry one."
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "exclusivitySlots",
+            "docs": [
+              "Slots after commit during which only `relayer` may fill"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "kind",
+            "type": {
+              "defined": {
+                "name": "intentKind"
+              }
+            }
+          },
+          {
+            "name": "allowPartial",
+            "docs": [
+              "Allow several reveals to each fill part of `amount_in`"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "route",
+            "docs": [
+              "Intermediate mints between `token_in` and `token_out`, in order.",
+              "Only `reveal_trade` fills routed intents."
+            ],
+            "type": {
+              "vec": {
+                "defined": {
+                  "name": "routeHop"
+                }
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "triggerDirection",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "above"
+          },
+          {
+            "name": "below"
+          }
+        ]
+      }
+    },
+    {
+      "name": "unstaked",
+      "type": {
+        "fields": [
+          {
+            "name": "owner",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "positionAmount",
+            "type": "u64"
+          },
+          {
+            "name": "totalStaked",
+            "type": "u64"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ],
+        "kind": "struct"
+      }
+    },
+    {
+      "name": "userStats",
+      "docs": [
+        "Rolling trading activity of one user, bucketed by day"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "user",
+            "type": "pubkey"
+          },
+          {
+            "name": "dailyVolume",
+            "docs": [
+              "Notional volume per day, indexed by `day % STATS_WINDOW_DAYS`"
+            ],
+            "type": {
+              "array": [
+                "u64",
+                30
+              ]
+            }
+          },
+          {
+            "name": "lastDay",
+            "docs": [
+              "Day index (unix days) of the most recent update"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "tradeCount",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
           }
         ]
       }
+    },
+    {
+      "name": "userVolumeLimitUpdated",
+      "type": {
+        "fields": [
+          {
+            "name": "maxUserDailyNotional",
+            "type": "u64"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ],
+        "kind": "struct"
+      }
     }
   ]
 };
@@ -271,9 +271,11 @@ class ContractSwapService {
     );
   }
 
-  private findFeeCollectionAuthorityPDA(): [PublicKey, number] {
+  private findFeeCollectionAuthorityPDA(
+    tokenMint: PublicKey
+  ): [PublicKey, number] {
     return PublicKey.findProgramAddressSync(
-      [Buffer.from("fee_authority")],
+      [Buffer.from("fee_authority"), tokenMint.toBuffer()],
       this.programId
     );
   }
@@ -315,7 +317,7 @@ class ContractSwapService {
       relayerPublicKey
     );
 
-    const [feeCollectionAuthority] = this.findFeeCollectionAuthorityPDA();
+    const [feeCollectionAuthority] = this.findFeeCollectionAuthorityPDA(tokenInMint);
     const [feeCollectionAccount] =
       this.findFeeCollectionAccountPDA(tokenInMint);
 
@@ -1,4 +1,41 @@
 pub const FEE_BASIS_POINTS: u64 = 10;
 pub const LIQUIDITY_STAKER_PDA_SEED: &[u8] = b"liq_stakers";
 pub const TREASURY_PDA_SEED: &[u8] = b"treasury";
-pub const BOUNTY_PDA_SEED: &[u8] = b"mev_bounty";
\ No newline at end of file
+pub const BOUNTY_PDA_SEED: &[u8] = b"mev_bounty";
+pub const CONFIG_PDA_SEED: &[u8] = b"config";
+pub const RELAYER_PDA_SEED: &[u8] = b"relayer";
+pub const MAX_ROUTE_HOPS: usize = 3;
+pub const MAX_BASKET_LEGS: usize = 4;
+pub const BATCH_PDA_SEED: &[u8] = b"batch";
+pub const BATCH_VAULT_PDA_SEED: &[u8] = b"batch_vault";
+pub const BATCH_WINDOW_SLOTS: u64 = 150;
+pub const MAX_BATCH_ORDERS: usize = 16;
+pub const MINT_CONFIG_PDA_SEED: &[u8] = b"mint_config";
+pub const DEFAULT_MAX_RELAYER_FEE_BPS: u16 = 1000;
+pub const DEFAULT_SURPLUS_BOUNTY_BPS: u16 = 5000;
+pub const REFERRER_PDA_SEED: &[u8] = b"referrer";
+pub const DEFAULT_MAX_REFERRAL_BPS: u16 = 50;
+pub const USER_STATS_PDA_SEED: &[u8] = b"user_stats";
+pub const STATS_WINDOW_DAYS: usize = 30;
+pub const MAX_FEE_TIERS: usize = 4;
+/// Decimals of notional values used for volume tracking
+pub const NOTIONAL_DECIMALS: u8 = 6;
+pub const DENYLIST_PDA_SEED: &[u8] = b"denylist";
+pub const MAX_DENYLIST_ENTRIES: usize = 64;
+pub const FEE_AUTHORITY_PDA_SEED: &[u8] = b"fee_authority";
+pub const FEE_COLLECTION_PDA_SEED: &[u8] = b"fee_collection";
+pub const FEE_LEDGER_PDA_SEED: &[u8] = b"fee_ledger";
+pub const DEFAULT_KEEPER_REWARD_BPS: u16 = 10;
+pub const MAX_KEEPER_REWARD_BPS: u16 = 500;
+pub const DEFAULT_MIN_SWEEP_INTERVAL_SECS: i64 = 3600;
+pub const DEFAULT_REWARD_STREAM_SECS: i64 = 86_400;
+pub const STAKE_POOL_PDA_SEED: &[u8] = b"stake_pool";
+pub const STAKE_VAULT_PDA_SEED: &[u8] = b"stake_vault";
+pub const STAKE_POSITION_PDA_SEED: &[u8] = b"stake_position";
+pub const REWARD_POOL_PDA_SEED: &[u8] = b"reward_pool";
+pub const MAX_REWARD_MINTS: usize = 8;
+/// Fixed-point scale of reward-per-share accumulators
+pub const REWARD_PRECISION: u128 = 1_000_000_000_000;
+pub const BOUNTY_CLAIM_PDA_SEED: &[u8] = b"bounty_claim";
+pub const MAX_BOUNTY_EVIDENCE: usize = 4;
+pub const MAX_COMMITTEE_MEMBERS: usize = 5;
\ No newline at end of file
@@ -51,4 +51,130 @@ pub enum ErrorCode {
 
     #[msg("Trade amount too large")]
     AmountTooLarge,
-}
\ No newline at end of file
+
+    #[msg("Signer is not authorized for this action")]
+    Unauthorized,
+
+    #[msg("Relayer is not registered or inactive")]
+    RelayerNotRegistered,
+
+    #[msg("Preferred relayer exclusivity window still active")]
+    ExclusivityWindowActive,
+
+    #[msg("Invalid intent parameters")]
+    InvalidIntentParams,
+
+    #[msg("Partial fills are not allowed for this intent")]
+    PartialFillNotAllowed,
+
+    #[msg("Fill amount exceeds the unfilled remainder of the intent")]
+    FillExceedsRemaining,
+
+    #[msg("Route has too many hops")]
+    RouteTooLong,
+
+    #[msg("Intermediate hop output below its minimum")]
+    HopSlippageExceeded,
+
+    #[msg("Intent kind is not supported by this instruction")]
+    UnsupportedIntentKind,
+
+    #[msg("Intents are not compatible for matching")]
+    IntentsNotCompatible,
+
+    #[msg("Batch window has closed")]
+    BatchWindowClosed,
+
+    #[msg("Batch window is still open")]
+    BatchWindowOpen,
+
+    #[msg("Batch already settled")]
+    BatchAlreadySettled,
+
+    #[msg("Batch has no room for more orders")]
+    BatchFull,
+
+    #[msg("Already claimed")]
+    AlreadyClaimed,
+
+    #[msg("Oracle account is missing or invalid")]
+    InvalidOracle,
+
+    #[msg("Oracle price is stale")]
+    StaleOraclePrice,
+
+    #[msg("Execution price deviates too far from the oracle price")]
+    OraclePriceDeviation,
+
+    #[msg("Invalid configuration parameters")]
+    InvalidConfigParams,
+
+    #[msg("Oracle price has not crossed the trigger price")]
+    TriggerNotReached,
+
+    #[msg("Recurring intent interval has not elapsed")]
+    SliceIntervalNotElapsed,
+
+    #[msg("Output account is not owned by the intent recipient")]
+    InvalidRecipient,
+
+    #[msg("MEV bounty account is required to share surplus")]
+    BountyAccountRequired,
+
+    #[msg("Referral fee exceeds the configured maximum")]
+    ReferralFeeTooHigh,
+
+    #[msg("Referrer accounts are missing or invalid")]
+    InvalidReferrer,
+
+    #[msg("Trade exceeds a configured volume limit")]
+    VolumeLimitExceeded,
+
+    #[msg("Wallet or mint is denylisted")]
+    Denylisted,
+
+    #[msg("Denylist has no room for more entries")]
+    DenylistFull,
+
+    #[msg("Fees of this mint were swept too recently")]
+    SweepTooSoon,
+
+    #[msg("Fee settlement accounts are missing or not the mint's canonical accounts")]
+    InvalidSettlementAccounts,
+
+    #[msg("Stake pool has no room for more reward mints")]
+    RewardMintsFull,
+
+    #[msg("Reward pool accounts are missing or do not match the stake pool")]
+    InvalidRewardPool,
+
+    #[msg("Signer is not a bounty committee member")]
+    NotCommitteeMember,
+
+    #[msg("Committee member already voted on this claim")]
+    AlreadyVoted,
+
+    #[msg("Bounty claim is no longer pending")]
+    BountyClaimNotPending,
+
+    #[msg("Bounty claim has not been approved")]
+    BountyClaimNotApproved,
+
+    #[msg("Bounty claim evidence is empty or too long")]
+    InvalidBountyEvidence,
+
+    #[msg("Multi-hop routes can only be filled through reveal_trade")]
+    RouteNotSupported,
+
+    #[msg("Referral fees are only paid on reveal_trade fills")]
+    ReferralNotSupported,
+
+    #[msg("A daily notional limit requires an oracle price for the input mint")]
+    NotionalOracleRequired,
+
+    #[msg("Intent is not delegated enough of the input account to fill this slice")]
+    SliceDelegateMissing,
+
+    #[msg("Both sides of a match belong to the same user")]
+    SelfMatch,
+}
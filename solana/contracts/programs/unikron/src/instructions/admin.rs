@@ -0,0 +1,574 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+use crate::{state::*, errors::ErrorCode, constants::*, program::Unikron};
+
+// ==================== PROTOCOL INITIALIZATION ====================
+
+/// Creates the protocol config. Only the program's upgrade authority may
+/// call it, so whoever deploys the program decides the first admin.
+#[derive(Accounts)]
+pub struct InitializeProtocol<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Config::SIZE,
+        seeds = [CONFIG_PDA_SEED],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Admin that will manage protocol configuration and relayers
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, Unikron>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ ErrorCode::Unauthorized
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_initialize_protocol(ctx: Context<InitializeProtocol>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.admin = ctx.accounts.admin.key();
+    config.fee_bps = FEE_BASIS_POINTS as u16;
+    config.max_relayer_fee_bps = DEFAULT_MAX_RELAYER_FEE_BPS;
+    config.surplus_bounty_bps = DEFAULT_SURPLUS_BOUNTY_BPS;
+    config.max_referral_bps = DEFAULT_MAX_REFERRAL_BPS;
+    config.fee_tiers = Vec::new();
+    config.max_user_daily_notional = 0;
+    config.keeper_reward_bps = DEFAULT_KEEPER_REWARD_BPS;
+    config.min_sweep_interval_secs = DEFAULT_MIN_SWEEP_INTERVAL_SECS;
+    config.reward_stream_secs = DEFAULT_REWARD_STREAM_SECS;
+    config.bounty_committee = Vec::new();
+    config.bounty_threshold = 0;
+    config.is_paused = false;
+    config.bump = ctx.bumps.config;
+
+    msg!("Protocol initialized with admin: {}", config.admin);
+
+    Ok(())
+}
+
+/// Admin-only update of protocol-wide parameters in the config, shared by
+/// every `set_*` instruction
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_PDA_SEED],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn handle_set_relayer_fee_cap(ctx: Context<UpdateConfig>, max_relayer_fee_bps: u16) -> Result<()> {
+    require!(max_relayer_fee_bps <= 10000, ErrorCode::InvalidConfigParams);
+
+    ctx.accounts.config.max_relayer_fee_bps = max_relayer_fee_bps;
+
+    emit!(RelayerFeeCapUpdated {
+        max_relayer_fee_bps,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn handle_set_surplus_share(ctx: Context<UpdateConfig>, surplus_bounty_bps: u16) -> Result<()> {
+    require!(surplus_bounty_bps <= 10000, ErrorCode::InvalidConfigParams);
+
+    ctx.accounts.config.surplus_bounty_bps = surplus_bounty_bps;
+
+    emit!(SurplusShareUpdated {
+        surplus_bounty_bps,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn handle_set_referral_cap(ctx: Context<UpdateConfig>, max_referral_bps: u16) -> Result<()> {
+    require!(max_referral_bps <= 10000, ErrorCode::InvalidConfigParams);
+
+    ctx.accounts.config.max_referral_bps = max_referral_bps;
+
+    emit!(ReferralCapUpdated {
+        max_referral_bps,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn handle_set_fee_tiers(ctx: Context<UpdateConfig>, fee_tiers: Vec<FeeTier>) -> Result<()> {
+    require!(fee_tiers.len() <= MAX_FEE_TIERS, ErrorCode::InvalidConfigParams);
+    for (i, tier) in fee_tiers.iter().enumerate() {
+        require!(tier.discount_bps <= 10000, ErrorCode::InvalidConfigParams);
+        if i > 0 {
+            require!(
+                tier.min_volume > fee_tiers[i - 1].min_volume,
+                ErrorCode::InvalidConfigParams
+            );
+        }
+    }
+
+    emit!(FeeTiersUpdated {
+        fee_tiers: fee_tiers.clone(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    ctx.accounts.config.fee_tiers = fee_tiers;
+
+    Ok(())
+}
+
+pub fn handle_set_user_volume_limit(
+    ctx: Context<UpdateConfig>,
+    max_user_daily_notional: u64,
+) -> Result<()> {
+    ctx.accounts.config.max_user_daily_notional = max_user_daily_notional;
+
+    emit!(UserVolumeLimitUpdated {
+        max_user_daily_notional,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn handle_set_sweep_params(
+    ctx: Context<UpdateConfig>,
+    keeper_reward_bps: u16,
+    min_sweep_interval_secs: i64,
+    reward_stream_secs: i64,
+) -> Result<()> {
+    require!(keeper_reward_bps <= MAX_KEEPER_REWARD_BPS, ErrorCode::InvalidConfigParams);
+    require!(min_sweep_interval_secs >= 0, ErrorCode::InvalidConfigParams);
+    require!(reward_stream_secs >= 0, ErrorCode::InvalidConfigParams);
+
+    let config = &mut ctx.accounts.config;
+    config.keeper_reward_bps = keeper_reward_bps;
+    config.min_sweep_interval_secs = min_sweep_interval_secs;
+    config.reward_stream_secs = reward_stream_secs;
+
+    emit!(SweepParamsUpdated {
+        keeper_reward_bps,
+        min_sweep_interval_secs,
+        reward_stream_secs,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn handle_set_bounty_committee(
+    ctx: Context<UpdateConfig>,
+    members: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    require!(members.len() <= MAX_COMMITTEE_MEMBERS, ErrorCode::InvalidConfigParams);
+    require!(threshold as usize <= members.len(), ErrorCode::InvalidConfigParams);
+    require!(threshold > 0 || members.is_empty(), ErrorCode::InvalidConfigParams);
+
+    let mut unique = members.clone();
+    unique.sort();
+    unique.dedup();
+    require!(unique.len() == members.len(), ErrorCode::InvalidConfigParams);
+
+    emit!(BountyCommitteeUpdated {
+        members: members.clone(),
+        threshold,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    let config = &mut ctx.accounts.config;
+    config.bounty_committee = members;
+    config.bounty_threshold = threshold;
+
+    Ok(())
+}
+
+// ==================== RELAYER REGISTRY ====================
+
+#[derive(Accounts)]
+pub struct RegisterRelayer<'info> {
+    #[account(
+        seeds = [CONFIG_PDA_SEED],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + RelayerRegistration::SIZE,
+        seeds = [RELAYER_PDA_SEED, relayer.key().as_ref()],
+        bump
+    )]
+    pub relayer_registration: Account<'info, RelayerRegistration>,
+
+    /// CHECK: Only used as the key of the relayer being registered
+    pub relayer: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_register_relayer(ctx: Context<RegisterRelayer>) -> Result<()> {
+    let registration = &mut ctx.accounts.relayer_registration;
+    registration.relayer = ctx.accounts.relayer.key();
+    registration.active = true;
+    registration.registered_at = Clock::get()?.unix_timestamp;
+    registration.bump = ctx.bumps.relayer_registration;
+
+    emit!(RelayerStatusChanged {
+        relayer: registration.relayer,
+        active: true,
+        timestamp: registration.registered_at,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetRelayerStatus<'info> {
+    #[account(
+        seeds = [CONFIG_PDA_SEED],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [RELAYER_PDA_SEED, relayer_registration.relayer.as_ref()],
+        bump = relayer_registration.bump
+    )]
+    pub relayer_registration: Account<'info, RelayerRegistration>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn handle_set_relayer_status(ctx: Context<SetRelayerStatus>, active: bool) -> Result<()> {
+    let registration = &mut ctx.accounts.relayer_registration;
+    registration.active = active;
+
+    emit!(RelayerStatusChanged {
+        relayer: registration.relayer,
+        active,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// ==================== MINT CONFIGURATION ====================
+
+#[derive(Accounts)]
+pub struct InitializeMintConfig<'info> {
+    #[account(
+        seeds = [CONFIG_PDA_SEED],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + MintConfig::SIZE,
+        seeds = [MINT_CONFIG_PDA_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_initialize_mint_config(
+    ctx: Context<InitializeMintConfig>,
+    oracle: Pubkey,
+    max_deviation_bps: u16,
+    max_staleness_secs: u64,
+) -> Result<()> {
+    let mint_config = &mut ctx.accounts.mint_config;
+    mint_config.mint = ctx.accounts.mint.key();
+    mint_config.volume_limit = 0;
+    mint_config.volume_epoch_secs = 0;
+    mint_config.epoch_start = 0;
+    mint_config.epoch_volume = 0;
+    mint_config.bump = ctx.bumps.mint_config;
+
+    apply_mint_config(mint_config, oracle, max_deviation_bps, max_staleness_secs)
+}
+
+#[derive(Accounts)]
+pub struct UpdateMintConfig<'info> {
+    #[account(
+        seeds = [CONFIG_PDA_SEED],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [MINT_CONFIG_PDA_SEED, mint_config.mint.as_ref()],
+        bump = mint_config.bump
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn handle_update_mint_config(
+    ctx: Context<UpdateMintConfig>,
+    oracle: Pubkey,
+    max_deviation_bps: u16,
+    max_staleness_secs: u64,
+) -> Result<()> {
+    apply_mint_config(&mut ctx.accounts.mint_config, oracle, max_deviation_bps, max_staleness_secs)
+}
+
+#[derive(Accounts)]
+pub struct SetMintVolumeLimit<'info> {
+    #[account(
+        seeds = [CONFIG_PDA_SEED],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [MINT_CONFIG_PDA_SEED, mint_config.mint.as_ref()],
+        bump = mint_config.bump
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn handle_set_mint_volume_limit(
+    ctx: Context<SetMintVolumeLimit>,
+    volume_limit: u64,
+    volume_epoch_secs: u64,
+) -> Result<()> {
+    require!(
+        volume_limit == 0 || volume_epoch_secs > 0,
+        ErrorCode::InvalidConfigParams
+    );
+
+    let mint_config = &mut ctx.accounts.mint_config;
+    mint_config.volume_limit = volume_limit;
+    mint_config.volume_epoch_secs = volume_epoch_secs;
+    mint_config.epoch_start = 0;
+    mint_config.epoch_volume = 0;
+
+    emit!(MintVolumeLimitUpdated {
+        mint: mint_config.mint,
+        volume_limit,
+        volume_epoch_secs,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+fn apply_mint_config(
+    mint_config: &mut MintConfig,
+    oracle: Pubkey,
+    max_deviation_bps: u16,
+    max_staleness_secs: u64,
+) -> Result<()> {
+    require!(max_deviation_bps <= 10000, ErrorCode::InvalidConfigParams);
+
+    mint_config.oracle = oracle;
+    mint_config.max_deviation_bps = max_deviation_bps;
+    mint_config.max_staleness_secs = max_staleness_secs;
+
+    emit!(MintConfigUpdated {
+        mint: mint_config.mint,
+        oracle,
+        max_deviation_bps,
+        max_staleness_secs,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// ==================== DENYLIST ====================
+
+#[derive(Accounts)]
+pub struct InitializeDenylist<'info> {
+    #[account(
+        seeds = [CONFIG_PDA_SEED],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Denylist::SIZE,
+        seeds = [DENYLIST_PDA_SEED],
+        bump
+    )]
+    pub denylist: Box<Account<'info, Denylist>>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_initialize_denylist(ctx: Context<InitializeDenylist>) -> Result<()> {
+    let denylist = &mut ctx.accounts.denylist;
+    denylist.wallets = Vec::new();
+    denylist.mints = Vec::new();
+    denylist.bump = ctx.bumps.denylist;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateDenylist<'info> {
+    #[account(
+        seeds = [CONFIG_PDA_SEED],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [DENYLIST_PDA_SEED],
+        bump = denylist.bump
+    )]
+    pub denylist: Box<Account<'info, Denylist>>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn handle_add_to_denylist(ctx: Context<UpdateDenylist>, kind: DenylistKind, key: Pubkey) -> Result<()> {
+    if ctx.accounts.denylist.add(kind, key)? {
+        emit!(DenylistUpdated {
+            kind,
+            key,
+            listed: true,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+    }
+
+    Ok(())
+}
+
+pub fn handle_remove_from_denylist(ctx: Context<UpdateDenylist>, kind: DenylistKind, key: Pubkey) -> Result<()> {
+    if ctx.accounts.denylist.remove(kind, key) {
+        emit!(DenylistUpdated {
+            kind,
+            key,
+            listed: false,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+    }
+
+    Ok(())
+}
+
+// ==================== EVENTS ====================
+
+#[event]
+pub struct RelayerFeeCapUpdated {
+    pub max_relayer_fee_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SurplusShareUpdated {
+    pub surplus_bounty_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReferralCapUpdated {
+    pub max_referral_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeeTiersUpdated {
+    pub fee_tiers: Vec<FeeTier>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UserVolumeLimitUpdated {
+    pub max_user_daily_notional: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BountyCommitteeUpdated {
+    pub members: Vec<Pubkey>,
+    pub threshold: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SweepParamsUpdated {
+    pub keeper_reward_bps: u16,
+    pub min_sweep_interval_secs: i64,
+    pub reward_stream_secs: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RelayerStatusChanged {
+    pub relayer: Pubkey,
+    pub active: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MintConfigUpdated {
+    pub mint: Pubkey,
+    pub oracle: Pubkey,
+    pub max_deviation_bps: u16,
+    pub max_staleness_secs: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MintVolumeLimitUpdated {
+    pub mint: Pubkey,
+    pub volume_limit: u64,
+    pub volume_epoch_secs: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DenylistUpdated {
+    pub kind: DenylistKind,
+    pub key: Pubkey,
+    pub listed: bool,
+    pub timestamp: i64,
+}
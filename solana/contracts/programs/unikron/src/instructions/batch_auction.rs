@@ -0,0 +1,586 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer, Mint};
+use crate::{
+    state::*, errors::ErrorCode, constants::*,
+    oracle::{MintPrice, within_deviation},
+};
+use super::reveal_trade::{
+    verify_commitment, verify_ed25519_signature, quote_exact_in, tiered_fee_bps, record_fill_volume,
+};
+
+/// Batch window that `slot` falls into
+pub fn batch_epoch(slot: u64) -> u64 {
+    slot / BATCH_WINDOW_SLOTS
+}
+
+// ==================== OPEN BATCH ====================
+
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct OpenBatch<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Batch::SIZE,
+        seeds = [
+            BATCH_PDA_SEED,
+            base_mint.key().as_ref(),
+            quote_mint.key().as_ref(),
+            &epoch.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub batch: Box<Account<'info, Batch>>,
+
+    /// Escrow for base tokens sold into the batch
+    #[account(
+        init,
+        payer = payer,
+        token::mint = base_mint,
+        token::authority = batch,
+        seeds = [BATCH_VAULT_PDA_SEED, batch.key().as_ref(), base_mint.key().as_ref()],
+        bump
+    )]
+    pub base_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Escrow for quote tokens sold into the batch
+    #[account(
+        init,
+        payer = payer,
+        token::mint = quote_mint,
+        token::authority = batch,
+        seeds = [BATCH_VAULT_PDA_SEED, batch.key().as_ref(), quote_mint.key().as_ref()],
+        bump
+    )]
+    pub quote_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Pair mints in canonical order, base key lower than quote key
+    #[account(constraint = base_mint.key() < quote_mint.key() @ ErrorCode::InvalidTokenMint)]
+    pub base_mint: Box<Account<'info, Mint>>,
+    pub quote_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn handle_open_batch(ctx: Context<OpenBatch>, epoch: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(epoch >= batch_epoch(clock.slot), ErrorCode::BatchWindowClosed);
+
+    let batch = &mut ctx.accounts.batch;
+    batch.base_mint = ctx.accounts.base_mint.key();
+    batch.quote_mint = ctx.accounts.quote_mint.key();
+    batch.epoch = epoch;
+    batch.settled = false;
+    batch.orders = Vec::new();
+    batch.bump = ctx.bumps.batch;
+
+    msg!("Batch opened for epoch {}: {} / {}", epoch, batch.base_mint, batch.quote_mint);
+
+    Ok(())
+}
+
+// ==================== REVEAL INTO BATCH ====================
+
+#[derive(Accounts)]
+pub struct RevealToBatch<'info> {
+    #[account(
+        mut,
+        has_one = user,
+        seeds = [b"intent", user.key().as_ref(), &swap_intent.nonce.to_le_bytes()],
+        bump
+    )]
+    pub swap_intent: Account<'info, SwapIntent>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// CHECK: We're verifying instruction manually for ed25519 signature
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    #[account(seeds = [CONFIG_PDA_SEED], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(seeds = [DENYLIST_PDA_SEED], bump = denylist.bump)]
+    pub denylist: Box<Account<'info, Denylist>>,
+
+    /// Rolling volume of the user, which sets their fee tier
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserStats::SIZE,
+        seeds = [USER_STATS_PDA_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Box<Account<'info, UserStats>>,
+
+    #[account(
+        mut,
+        seeds = [
+            BATCH_PDA_SEED,
+            batch.base_mint.as_ref(),
+            batch.quote_mint.as_ref(),
+            &batch.epoch.to_le_bytes(),
+        ],
+        bump = batch.bump
+    )]
+    pub batch: Box<Account<'info, Batch>>,
+
+    #[account(
+        mut,
+        seeds = [BATCH_VAULT_PDA_SEED, batch.key().as_ref(), token_in_mint.key().as_ref()],
+        bump
+    )]
+    pub vault_in: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = user_token_in_account.mint == token_in_mint.key(),
+        constraint = user_token_in_account.owner == user.key()
+    )]
+    pub user_token_in_account: Box<Account<'info, TokenAccount>>,
+
+    pub token_in_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [MINT_CONFIG_PDA_SEED, token_in_mint.key().as_ref()],
+        bump = token_in_config.bump
+    )]
+    pub token_in_config: Box<Account<'info, MintConfig>>,
+
+    /// CHECK: Matched against `token_in_config.oracle` and parsed in-program
+    pub token_in_oracle: Option<AccountInfo<'info>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_reveal_to_batch(
+    ctx: Context<RevealToBatch>,
+    intent: TradeIntentData,
+    expected_hash: [u8; 32],
+    signature: [u8; 64],
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let batch = &ctx.accounts.batch;
+
+    // ==================== VALIDATION PHASE ====================
+
+    verify_commitment(
+        &ctx.accounts.swap_intent,
+        &intent,
+        &expected_hash,
+        &ctx.accounts.user.key(),
+        clock.unix_timestamp,
+    )?;
+    ctx.accounts.denylist.check_intent(&intent)?;
+
+    require!(!batch.settled, ErrorCode::BatchAlreadySettled);
+    require!(batch.epoch == batch_epoch(clock.slot), ErrorCode::BatchWindowClosed);
+    require!(batch.orders.len() < MAX_BATCH_ORDERS, ErrorCode::BatchFull);
+
+    // Batch orders are whole, route-free exact-in orders with no relayer or
+    // referrer. Their fill is pro rata, so they must accept partial fills.
+    require!(ctx.accounts.swap_intent.filled_in == 0, ErrorCode::UnsupportedIntentKind);
+    require!(intent.allow_partial, ErrorCode::PartialFillNotAllowed);
+    require!(intent.route.is_empty(), ErrorCode::RouteNotSupported);
+    require!(!intent.has_referral(), ErrorCode::ReferralNotSupported);
+    require!(intent.relayer_fee == 0, ErrorCode::UnsupportedIntentKind);
+    require!(
+        matches!(intent.kind, IntentKind::Standard | IntentKind::DutchAuction { .. }),
+        ErrorCode::UnsupportedIntentKind
+    );
+
+    let sells_base = if intent.token_in == batch.base_mint && intent.token_out == batch.quote_mint {
+        true
+    } else if intent.token_in == batch.quote_mint && intent.token_out == batch.base_mint {
+        false
+    } else {
+        return Err(ErrorCode::InvalidTokenMint.into());
+    };
+    require_keys_eq!(intent.token_in, ctx.accounts.token_in_mint.key(), ErrorCode::HashMismatch);
+
+    require!(
+        ctx.accounts.user_token_in_account.amount >= intent.amount_in,
+        ErrorCode::InsufficientBalance
+    );
+
+    // ==================== ED25519 SIGNATURE VERIFICATION ====================
+
+    verify_ed25519_signature(
+        &ctx.accounts.instructions_sysvar,
+        1,
+        &signature,
+        &ctx.accounts.user.key().to_bytes(),
+        &expected_hash,
+    )?;
+
+    // ==================== ESCROW ====================
+
+    let day = unix_day(clock.unix_timestamp);
+    ctx.accounts.user_stats.initialize_if_new(ctx.accounts.user.key(), ctx.bumps.user_stats);
+    let (fee_bps, fee_discount_bps) = tiered_fee_bps(&ctx.accounts.config, &ctx.accounts.user_stats, day)?;
+
+    let (protocol_fee, amount_after_fee) = quote_exact_in(intent.amount_in, fee_bps)?;
+    let min_out = intent.required_min_out(clock.unix_timestamp)?;
+
+    // The fee is escrowed with the order and only collected on the part
+    // that fills, so a refunded order gets it back
+    let cpi = Transfer {
+        from: ctx.accounts.user_token_in_account.to_account_info(),
+        to: ctx.accounts.vault_in.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+    token::transfer(
+        CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi),
+        intent.amount_in,
+    ).map_err(|_| ErrorCode::SwapExecutionFailed)?;
+
+    // ==================== STATE UPDATE ====================
+
+    let swap_intent = &mut ctx.accounts.swap_intent;
+    swap_intent.filled_in = intent.amount_in;
+    swap_intent.revealed = true;
+
+    // The output is only known at the clearing price and a claim must not
+    // fail, so batch orders count against the input mint's limit alone
+    let price_in = MintPrice::load(
+        &ctx.accounts.token_in_config,
+        ctx.accounts.token_in_oracle.as_ref(),
+        ctx.accounts.token_in_mint.decimals,
+        clock.unix_timestamp,
+    )?;
+    record_fill_volume(
+        &ctx.accounts.config,
+        &mut ctx.accounts.user_stats,
+        &price_in,
+        &mut ctx.accounts.token_in_config,
+        intent.amount_in,
+        None,
+        clock.unix_timestamp,
+    )?;
+
+    let batch = &mut ctx.accounts.batch;
+    batch.orders.push(BatchOrder {
+        owner: intent.user,
+        recipient: intent.recipient,
+        sells_base,
+        amount_in: amount_after_fee,
+        protocol_fee,
+        min_out,
+        claimed: false,
+    });
+
+    emit!(BatchOrderRevealed {
+        batch: batch.key(),
+        user: intent.user,
+        order_index: (batch.orders.len() - 1) as u8,
+        sells_base,
+        amount_in: amount_after_fee,
+        min_out,
+        protocol_fee,
+        fee_discount_bps,
+        nonce: intent.nonce,
+    });
+
+    Ok(())
+}
+
+// ==================== SETTLE BATCH ====================
+
+#[derive(Accounts)]
+pub struct SettleBatch<'info> {
+    #[account(
+        mut,
+        seeds = [
+            BATCH_PDA_SEED,
+            batch.base_mint.as_ref(),
+            batch.quote_mint.as_ref(),
+            &batch.epoch.to_le_bytes(),
+        ],
+        bump = batch.bump
+    )]
+    pub batch: Box<Account<'info, Batch>>,
+
+    #[account(address = batch.base_mint @ ErrorCode::InvalidTokenMint)]
+    pub base_mint: Box<Account<'info, Mint>>,
+
+    #[account(address = batch.quote_mint @ ErrorCode::InvalidTokenMint)]
+    pub quote_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        seeds = [MINT_CONFIG_PDA_SEED, base_mint.key().as_ref()],
+        bump = base_config.bump
+    )]
+    pub base_config: Box<Account<'info, MintConfig>>,
+
+    #[account(
+        seeds = [MINT_CONFIG_PDA_SEED, quote_mint.key().as_ref()],
+        bump = quote_config.bump
+    )]
+    pub quote_config: Box<Account<'info, MintConfig>>,
+
+    /// CHECK: Matched against `base_config.oracle` and parsed in-program
+    pub base_oracle: Option<AccountInfo<'info>>,
+
+    /// CHECK: Matched against `quote_config.oracle` and parsed in-program
+    pub quote_oracle: Option<AccountInfo<'info>>,
+
+    /// Anyone may settle a batch once its window has closed
+    pub caller: Signer<'info>,
+}
+
+pub fn handle_settle_batch(ctx: Context<SettleBatch>) -> Result<()> {
+    let clock = Clock::get()?;
+    let batch = &mut ctx.accounts.batch;
+
+    require!(!batch.settled, ErrorCode::BatchAlreadySettled);
+    require!(batch_epoch(clock.slot) > batch.epoch, ErrorCode::BatchWindowOpen);
+
+    batch.clear()?;
+
+    // A clearing price that strays from the oracle refunds every order
+    // rather than leaving the batch unsettled
+    let mut oracle_rejected = false;
+    if batch.price_num > 0 {
+        let base_price = MintPrice::load(
+            &ctx.accounts.base_config,
+            ctx.accounts.base_oracle.as_ref(),
+            ctx.accounts.base_mint.decimals,
+            clock.unix_timestamp,
+        )?;
+        let quote_price = MintPrice::load(
+            &ctx.accounts.quote_config,
+            ctx.accounts.quote_oracle.as_ref(),
+            ctx.accounts.quote_mint.decimals,
+            clock.unix_timestamp,
+        )?;
+
+        if !within_deviation(&base_price, &quote_price, batch.price_den, batch.price_num)? {
+            batch.refund_all();
+            oracle_rejected = true;
+        }
+    }
+
+    emit!(BatchSettled {
+        batch: batch.key(),
+        base_mint: batch.base_mint,
+        quote_mint: batch.quote_mint,
+        epoch: batch.epoch,
+        price_num: batch.price_num,
+        price_den: batch.price_den,
+        base_filled: batch.base_filled,
+        quote_filled: batch.quote_filled,
+        order_count: batch.orders.len() as u8,
+        oracle_rejected,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Batch settled at {} / {} with {} base filled",
+        batch.price_num,
+        batch.price_den,
+        batch.base_filled
+    );
+
+    Ok(())
+}
+
+// ==================== CLAIM FILL ====================
+
+#[derive(Accounts)]
+pub struct ClaimBatchFill<'info> {
+    #[account(
+        mut,
+        seeds = [
+            BATCH_PDA_SEED,
+            batch.base_mint.as_ref(),
+            batch.quote_mint.as_ref(),
+            &batch.epoch.to_le_bytes(),
+        ],
+        bump = batch.bump
+    )]
+    pub batch: Box<Account<'info, Batch>>,
+
+    #[account(
+        mut,
+        seeds = [BATCH_VAULT_PDA_SEED, batch.key().as_ref(), batch.base_mint.as_ref()],
+        bump
+    )]
+    pub base_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [BATCH_VAULT_PDA_SEED, batch.key().as_ref(), batch.quote_mint.as_ref()],
+        bump
+    )]
+    pub quote_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Base account of the owner when refunding a base sale, or of the
+    /// recipient when receiving bought base
+    #[account(
+        mut,
+        constraint = base_account.mint == batch.base_mint
+    )]
+    pub base_account: Box<Account<'info, TokenAccount>>,
+
+    /// Quote account of the owner when refunding a quote sale, or of the
+    /// recipient when receiving sold-base proceeds
+    #[account(
+        mut,
+        constraint = quote_account.mint == batch.quote_mint
+    )]
+    pub quote_account: Box<Account<'info, TokenAccount>>,
+
+    /// Fee collection account of the order's input mint, receiving the
+    /// protocol fee on the filled input
+    #[account(
+        mut,
+        seeds = [FEE_COLLECTION_PDA_SEED, fee_collection_account.mint.as_ref()],
+        bump
+    )]
+    pub fee_collection_account: Box<Account<'info, TokenAccount>>,
+
+    /// Fee totals of the order's input mint
+    #[account(
+        mut,
+        seeds = [FEE_LEDGER_PDA_SEED, fee_collection_account.mint.as_ref()],
+        bump = fee_ledger.bump
+    )]
+    pub fee_ledger: Box<Account<'info, FeeLedger>>,
+
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handle_claim_batch_fill(ctx: Context<ClaimBatchFill>, order_index: u8) -> Result<()> {
+    let batch = &ctx.accounts.batch;
+    require!(batch.settled, ErrorCode::BatchWindowOpen);
+
+    let order = batch.orders
+        .get(order_index as usize)
+        .ok_or(ErrorCode::InvalidIntentParams)?
+        .clone();
+    require_keys_eq!(order.owner, ctx.accounts.owner.key(), ErrorCode::Unauthorized);
+    require!(!order.claimed, ErrorCode::AlreadyClaimed);
+
+    // Refunds go back to the owner, fills to the recipient
+    let (refund_account, output_account, vault_in) = if order.sells_base {
+        (&ctx.accounts.base_account, &ctx.accounts.quote_account, &ctx.accounts.base_vault)
+    } else {
+        (&ctx.accounts.quote_account, &ctx.accounts.base_account, &ctx.accounts.quote_vault)
+    };
+    require_keys_eq!(refund_account.owner, order.owner, ErrorCode::Unauthorized);
+    require_keys_eq!(output_account.owner, order.recipient, ErrorCode::InvalidRecipient);
+    require_keys_eq!(
+        ctx.accounts.fee_collection_account.mint,
+        refund_account.mint,
+        ErrorCode::InvalidTokenMint
+    );
+
+    let (base_out, quote_out, protocol_fee) = batch.claimable(&order)?;
+
+    // ==================== PAYOUT ====================
+
+    let epoch_bytes = batch.epoch.to_le_bytes();
+    let batch_seeds = &[
+        BATCH_PDA_SEED,
+        batch.base_mint.as_ref(),
+        batch.quote_mint.as_ref(),
+        epoch_bytes.as_ref(),
+        &[batch.bump],
+    ];
+    let signer_seeds = &[&batch_seeds[..]];
+
+    for (from, to, amount) in [
+        (&ctx.accounts.base_vault, &ctx.accounts.base_account, base_out),
+        (&ctx.accounts.quote_vault, &ctx.accounts.quote_account, quote_out),
+        (vault_in, &ctx.accounts.fee_collection_account, protocol_fee),
+    ] {
+        if amount == 0 {
+            continue;
+        }
+
+        let cpi = Transfer {
+            from: from.to_account_info(),
+            to: to.to_account_info(),
+            authority: ctx.accounts.batch.to_account_info(),
+        };
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi,
+                signer_seeds,
+            ),
+            amount,
+        )?;
+    }
+
+    // ==================== STATE UPDATE ====================
+
+    ctx.accounts.fee_ledger.record_collected(protocol_fee)?;
+
+    let batch = &mut ctx.accounts.batch;
+    batch.orders[order_index as usize].claimed = true;
+
+    emit!(BatchFillClaimed {
+        batch: batch.key(),
+        owner: order.owner,
+        order_index,
+        base_out,
+        quote_out,
+        protocol_fee,
+    });
+
+    Ok(())
+}
+
+// ==================== EVENTS ====================
+
+#[event]
+pub struct BatchOrderRevealed {
+    pub batch: Pubkey,
+    pub user: Pubkey,
+    pub order_index: u8,
+    pub sells_base: bool,
+    pub amount_in: u64,
+    pub min_out: u64,
+    pub protocol_fee: u64,
+    /// Volume-tier discount applied to the protocol fee
+    pub fee_discount_bps: u16,
+    pub nonce: u64,
+}
+
+#[event]
+pub struct BatchSettled {
+    pub batch: Pubkey,
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub epoch: u64,
+    pub price_num: u64,
+    pub price_den: u64,
+    pub base_filled: u64,
+    pub quote_filled: u64,
+    pub order_count: u8,
+    /// Clearing price was discarded for straying from the oracle
+    pub oracle_rejected: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BatchFillClaimed {
+    pub batch: Pubkey,
+    pub owner: Pubkey,
+    pub order_index: u8,
+    pub base_out: u64,
+    pub quote_out: u64,
+    /// Escrowed protocol fee collected on the filled input
+    pub protocol_fee: u64,
+}
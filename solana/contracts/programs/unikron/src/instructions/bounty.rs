@@ -0,0 +1,250 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer, Mint};
+use crate::{state::*, errors::ErrorCode, constants::*};
+
+// ==================== SUBMIT ====================
+
+#[derive(Accounts)]
+#[instruction(claim_id: u64)]
+pub struct SubmitBountyClaim<'info> {
+    #[account(
+        init,
+        payer = reporter,
+        space = 8 + BountyClaim::SIZE,
+        seeds = [BOUNTY_CLAIM_PDA_SEED, reporter.key().as_ref(), &claim_id.to_le_bytes()],
+        bump
+    )]
+    pub bounty_claim: Box<Account<'info, BountyClaim>>,
+
+    /// Mint of the bounty pool the claim is paid from
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub reporter: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_submit_bounty_claim(
+    ctx: Context<SubmitBountyClaim>,
+    claim_id: u64,
+    amount: u64,
+    evidence: Vec<BountyEvidence>,
+) -> Result<()> {
+    require!(amount > 0, ErrorCode::AmountTooSmall);
+    require!(
+        !evidence.is_empty() && evidence.len() <= MAX_BOUNTY_EVIDENCE,
+        ErrorCode::InvalidBountyEvidence
+    );
+
+    let clock = Clock::get()?;
+
+    let claim = &mut ctx.accounts.bounty_claim;
+    claim.reporter = ctx.accounts.reporter.key();
+    claim.claim_id = claim_id;
+    claim.mint = ctx.accounts.mint.key();
+    claim.amount = amount;
+    claim.evidence = evidence;
+    claim.approvals = Vec::new();
+    claim.rejections = Vec::new();
+    claim.status = BountyClaimStatus::Pending;
+    claim.created_at = clock.unix_timestamp;
+    claim.bump = ctx.bumps.bounty_claim;
+
+    emit!(BountyClaimSubmitted {
+        claim: claim.key(),
+        reporter: claim.reporter,
+        claim_id,
+        mint: claim.mint,
+        amount,
+        evidence_count: claim.evidence.len() as u8,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// ==================== VOTE ====================
+
+#[derive(Accounts)]
+pub struct VoteBountyClaim<'info> {
+    #[account(seeds = [CONFIG_PDA_SEED], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        seeds = [
+            BOUNTY_CLAIM_PDA_SEED,
+            bounty_claim.reporter.as_ref(),
+            &bounty_claim.claim_id.to_le_bytes(),
+        ],
+        bump = bounty_claim.bump
+    )]
+    pub bounty_claim: Box<Account<'info, BountyClaim>>,
+
+    #[account(
+        constraint = config.bounty_committee.contains(&member.key()) @ ErrorCode::NotCommitteeMember
+    )]
+    pub member: Signer<'info>,
+}
+
+pub fn handle_vote_bounty_claim(ctx: Context<VoteBountyClaim>, approve: bool) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let claim = &mut ctx.accounts.bounty_claim;
+
+    claim.record_vote(
+        ctx.accounts.member.key(),
+        approve,
+        &config.bounty_committee,
+        config.bounty_threshold,
+    )?;
+
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    emit!(BountyClaimVoted {
+        claim: claim.key(),
+        member: ctx.accounts.member.key(),
+        approve,
+        approvals: claim.approvals.len() as u8,
+        rejections: claim.rejections.len() as u8,
+        timestamp,
+    });
+
+    if claim.status != BountyClaimStatus::Pending {
+        emit!(BountyClaimResolved {
+            claim: claim.key(),
+            status: claim.status,
+            timestamp,
+        });
+    }
+
+    Ok(())
+}
+
+// ==================== PAYOUT ====================
+
+/// Pays an approved claim from the bounty pool of its mint; anyone may
+/// trigger it once the committee has approved
+#[derive(Accounts)]
+pub struct PayBountyClaim<'info> {
+    #[account(
+        mut,
+        seeds = [
+            BOUNTY_CLAIM_PDA_SEED,
+            bounty_claim.reporter.as_ref(),
+            &bounty_claim.claim_id.to_le_bytes(),
+        ],
+        bump = bounty_claim.bump,
+        has_one = mint
+    )]
+    pub bounty_claim: Box<Account<'info, BountyClaim>>,
+
+    #[account(
+        mut,
+        seeds = [BOUNTY_PDA_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub bounty_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Fee collection authority PDA of `mint`, owner of the bounty pool
+    #[account(
+        seeds = [FEE_AUTHORITY_PDA_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub fee_collection_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        constraint = reporter_token_account.mint == mint.key(),
+        constraint = reporter_token_account.owner == bounty_claim.reporter @ ErrorCode::InvalidRecipient
+    )]
+    pub reporter_token_account: Box<Account<'info, TokenAccount>>,
+
+    pub mint: Box<Account<'info, Mint>>,
+
+    pub caller: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handle_pay_bounty_claim(ctx: Context<PayBountyClaim>) -> Result<()> {
+    let claim = &ctx.accounts.bounty_claim;
+    require!(claim.status == BountyClaimStatus::Approved, ErrorCode::BountyClaimNotApproved);
+    require!(
+        ctx.accounts.bounty_account.amount >= claim.amount,
+        ErrorCode::InsufficientBalance
+    );
+
+    let amount = claim.amount;
+    let mint_key = ctx.accounts.mint.key();
+    let authority_seeds = &[
+        FEE_AUTHORITY_PDA_SEED,
+        mint_key.as_ref(),
+        &[ctx.bumps.fee_collection_authority],
+    ];
+    let signer_seeds = &[&authority_seeds[..]];
+
+    let cpi = Transfer {
+        from: ctx.accounts.bounty_account.to_account_info(),
+        to: ctx.accounts.reporter_token_account.to_account_info(),
+        authority: ctx.accounts.fee_collection_authority.to_account_info(),
+    };
+    token::transfer(
+        CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi, signer_seeds),
+        amount,
+    )?;
+
+    let claim = &mut ctx.accounts.bounty_claim;
+    claim.status = BountyClaimStatus::Paid;
+
+    emit!(BountyClaimPaid {
+        claim: claim.key(),
+        reporter: claim.reporter,
+        mint: mint_key,
+        amount,
+        caller: ctx.accounts.caller.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// ==================== EVENTS ====================
+
+#[event]
+pub struct BountyClaimSubmitted {
+    pub claim: Pubkey,
+    pub reporter: Pubkey,
+    pub claim_id: u64,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub evidence_count: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BountyClaimVoted {
+    pub claim: Pubkey,
+    pub member: Pubkey,
+    pub approve: bool,
+    pub approvals: u8,
+    pub rejections: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BountyClaimResolved {
+    pub claim: Pubkey,
+    pub status: BountyClaimStatus,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BountyClaimPaid {
+    pub claim: Pubkey,
+    pub reporter: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub caller: Pubkey,
+    pub timestamp: i64,
+}
@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
-use super::super::state::SwapIntent;
+use super::super::state::{SwapIntent, Denylist};
+use crate::{constants::DENYLIST_PDA_SEED, errors::ErrorCode};
 
 #[derive(Accounts)]
 #[instruction(nonce: u64)]
@@ -13,9 +14,15 @@ pub struct CommitTrade<'info> {
     )]
     pub swap_intent: Account<'info, SwapIntent>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = !denylist.is_wallet_listed(&user.key()) @ ErrorCode::Denylisted
+    )]
     pub user: Signer<'info>,
 
+    #[account(seeds = [DENYLIST_PDA_SEED], bump = denylist.bump)]
+    pub denylist: Box<Account<'info, Denylist>>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -30,7 +37,11 @@ pub fn handle_commit(
     intent.intent_hash = intent_hash;
     intent.nonce = nonce;
     intent.expiry = expiry;
-    intent.timestamp = Clock::get()?.unix_timestamp;
+    let clock = Clock::get()?;
+    intent.timestamp = clock.unix_timestamp;
+    intent.commit_slot = clock.slot;
+    intent.filled_in = 0;
+    intent.last_fill_at = 0;
     intent.revealed = false;
     Ok(())
 }
\ No newline at end of file
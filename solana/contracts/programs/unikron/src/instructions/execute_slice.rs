@@ -0,0 +1,361 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer, Mint};
+use crate::{
+    state::*, errors::ErrorCode, constants::*,
+    math::mul_div,
+    oracle::{MintPrice, check_price_deviation},
+};
+use super::reveal_trade::{
+    verify_commitment, validate_relayer, validate_relayer_fee, verify_ed25519_signature,
+    quote_exact_in, quote_hop, tiered_fee_bps, record_fill_volume,
+};
+
+/// Relayer-driven execution of one slice of a recurring intent. The user
+/// does not sign: before the first slice they approve the intent PDA as SPL
+/// delegate of `user_token_in_account` for the intent's `amount_in`, plus
+/// the relayer fee when it is paid in `token_in`. That account holds one
+/// delegate at a time, so each recurring intent needs its own input account.
+#[derive(Accounts)]
+pub struct ExecuteSlice<'info> {
+    #[account(
+        mut,
+        has_one = user,
+        seeds = [b"intent", user.key().as_ref(), &swap_intent.nonce.to_le_bytes()],
+        bump
+    )]
+    pub swap_intent: Account<'info, SwapIntent>,
+
+    /// CHECK: Owner of the intent, who authorised it by signing the intent
+    /// hash (checked against the ed25519 instruction); receives the intent's
+    /// rent once the last slice fills
+    #[account(mut)]
+    pub user: AccountInfo<'info>,
+
+    /// CHECK: We're verifying instruction manually for ed25519 signature
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    #[account(seeds = [CONFIG_PDA_SEED], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(seeds = [DENYLIST_PDA_SEED], bump = denylist.bump)]
+    pub denylist: Box<Account<'info, Denylist>>,
+
+    /// Rolling volume of the user, which sets their fee tier
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = 8 + UserStats::SIZE,
+        seeds = [USER_STATS_PDA_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Box<Account<'info, UserStats>>,
+
+    #[account(
+        mut,
+        constraint = user_token_in_account.mint == token_in_mint.key(),
+        constraint = user_token_in_account.owner == user.key()
+    )]
+    pub user_token_in_account: Box<Account<'info, TokenAccount>>,
+
+    /// Output account, owned by the intent's recipient
+    #[account(
+        mut,
+        constraint = user_token_out_account.mint == token_out_mint.key()
+    )]
+    pub user_token_out_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = relayer_token_in_account.mint == token_in_mint.key(),
+        constraint = relayer_token_in_account.owner == relayer.key()
+    )]
+    pub relayer_token_in_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = relayer_token_out_account.mint == token_out_mint.key(),
+        constraint = relayer_token_out_account.owner == relayer.key()
+    )]
+    pub relayer_token_out_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+
+    /// Registry entry of the relayer, required when filling an open intent
+    #[account(
+        seeds = [RELAYER_PDA_SEED, relayer.key().as_ref()],
+        bump = relayer_registration.bump
+    )]
+    pub relayer_registration: Option<Account<'info, RelayerRegistration>>,
+
+    pub token_in_mint: Box<Account<'info, Mint>>,
+    pub token_out_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [MINT_CONFIG_PDA_SEED, token_in_mint.key().as_ref()],
+        bump = token_in_config.bump
+    )]
+    pub token_in_config: Box<Account<'info, MintConfig>>,
+
+    #[account(
+        mut,
+        seeds = [MINT_CONFIG_PDA_SEED, token_out_mint.key().as_ref()],
+        bump = token_out_config.bump
+    )]
+    pub token_out_config: Box<Account<'info, MintConfig>>,
+
+    /// CHECK: Matched against `token_in_config.oracle` and parsed in-program
+    pub token_in_oracle: Option<AccountInfo<'info>>,
+
+    /// CHECK: Matched against `token_out_config.oracle` and parsed in-program
+    pub token_out_oracle: Option<AccountInfo<'info>>,
+
+    #[account(
+        mut,
+        seeds = [FEE_COLLECTION_PDA_SEED, token_in_mint.key().as_ref()],
+        bump,
+        constraint = fee_collection_account.owner == fee_collection_authority.key()
+    )]
+    pub fee_collection_account: Box<Account<'info, TokenAccount>>,
+
+    /// Fee totals of `token_in_mint`
+    #[account(
+        mut,
+        seeds = [FEE_LEDGER_PDA_SEED, token_in_mint.key().as_ref()],
+        bump = fee_ledger.bump
+    )]
+    pub fee_ledger: Box<Account<'info, FeeLedger>>,
+
+    /// CHECK: Fee collection authority PDA of `token_in_mint`
+    #[account(
+        seeds = [FEE_AUTHORITY_PDA_SEED, token_in_mint.key().as_ref()],
+        bump
+    )]
+    pub fee_collection_authority: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_execute_slice(
+    ctx: Context<ExecuteSlice>,
+    intent: TradeIntentData,
+    expected_hash: [u8; 32],
+    signature: [u8; 64],
+) -> Result<()> {
+    let stored = &ctx.accounts.swap_intent;
+    let clock = Clock::get()?;
+
+    // ==================== VALIDATION PHASE ====================
+
+    verify_commitment(
+        stored,
+        &intent,
+        &expected_hash,
+        &ctx.accounts.user.key(),
+        clock.unix_timestamp,
+    )?;
+
+    require!(
+        matches!(intent.kind, IntentKind::Recurring { .. }),
+        ErrorCode::UnsupportedIntentKind
+    );
+
+    // Slices do not pay referrals, so refuse terms that would be dropped
+    require!(!intent.has_referral(), ErrorCode::ReferralNotSupported);
+
+    ctx.accounts.denylist.check_intent(&intent)?;
+    validate_relayer_fee(&intent, &ctx.accounts.config)?;
+
+    validate_relayer(
+        &intent,
+        &ctx.accounts.relayer.key(),
+        ctx.accounts.relayer_registration.as_ref(),
+        stored.commit_slot,
+        clock.slot,
+    )?;
+
+    require_keys_eq!(intent.token_in, ctx.accounts.token_in_mint.key(), ErrorCode::HashMismatch);
+    require_keys_eq!(intent.token_out, ctx.accounts.token_out_mint.key(), ErrorCode::HashMismatch);
+    require_keys_eq!(
+        ctx.accounts.user_token_out_account.owner,
+        intent.recipient,
+        ErrorCode::InvalidRecipient
+    );
+
+    // At most one slice per interval
+    require!(
+        intent.slice_due(stored.filled_in, stored.last_fill_at, clock.unix_timestamp),
+        ErrorCode::SliceIntervalNotElapsed
+    );
+
+    verify_ed25519_signature(
+        &ctx.accounts.instructions_sysvar,
+        1,
+        &signature,
+        &ctx.accounts.user.key().to_bytes(),
+        &expected_hash,
+    )?;
+
+    // ==================== SLICE QUOTE ====================
+
+    let (slice_in, required_out) = intent.next_slice(stored.filled_in)?;
+    let filled_after = stored.filled_in + slice_in;
+
+    let day = unix_day(clock.unix_timestamp);
+    ctx.accounts.user_stats.initialize_if_new(ctx.accounts.user.key(), ctx.bumps.user_stats);
+    let (fee_bps, fee_discount_bps) = tiered_fee_bps(&ctx.accounts.config, &ctx.accounts.user_stats, day)?;
+
+    let (protocol_fee, swap_amount_after_fee) = quote_exact_in(slice_in, fee_bps)?;
+    let amount_out = quote_hop(swap_amount_after_fee)?;
+    require!(amount_out >= required_out, ErrorCode::SlippageExceeded);
+
+    let price_in = MintPrice::load(
+        &ctx.accounts.token_in_config,
+        ctx.accounts.token_in_oracle.as_ref(),
+        ctx.accounts.token_in_mint.decimals,
+        clock.unix_timestamp,
+    )?;
+    let price_out = MintPrice::load(
+        &ctx.accounts.token_out_config,
+        ctx.accounts.token_out_oracle.as_ref(),
+        ctx.accounts.token_out_mint.decimals,
+        clock.unix_timestamp,
+    )?;
+    check_price_deviation(&price_in, &price_out, swap_amount_after_fee, amount_out)?;
+
+    // A token-out relayer fee is withheld from the output
+    let relayer_fee = mul_div(intent.relayer_fee, slice_in, intent.amount_in)?;
+    let (relayer_fee_in, relayer_fee_out) = intent.split_relayer_fee(relayer_fee);
+    let user_out = amount_out
+        .checked_sub(relayer_fee_out)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let slice_debit = slice_in
+        .checked_add(relayer_fee_in)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let user_in = &ctx.accounts.user_token_in_account;
+    require!(user_in.amount >= slice_debit, ErrorCode::InsufficientBalance);
+    require!(
+        user_in.delegate == COption::Some(ctx.accounts.swap_intent.key())
+            && user_in.delegated_amount >= slice_debit,
+        ErrorCode::SliceDelegateMissing
+    );
+    require!(
+        ctx.accounts.relayer_token_out_account.amount >= user_out,
+        ErrorCode::InsufficientBalance
+    );
+
+    // ==================== SLICE EXECUTION ====================
+
+    let user_key = ctx.accounts.user.key();
+    let nonce_bytes = intent.nonce.to_le_bytes();
+    let intent_seeds = &[
+        b"intent".as_ref(),
+        user_key.as_ref(),
+        nonce_bytes.as_ref(),
+        &[ctx.bumps.swap_intent],
+    ];
+    let signer_seeds = &[&intent_seeds[..]];
+
+    // Input moves under the intent PDA's delegate authority
+    for (to, amount) in [
+        (ctx.accounts.relayer_token_in_account.to_account_info(), swap_amount_after_fee),
+        (ctx.accounts.fee_collection_account.to_account_info(), protocol_fee),
+        (ctx.accounts.relayer_token_in_account.to_account_info(), relayer_fee_in),
+    ] {
+        if amount == 0 {
+            continue;
+        }
+
+        let cpi = Transfer {
+            from: ctx.accounts.user_token_in_account.to_account_info(),
+            to,
+            authority: ctx.accounts.swap_intent.to_account_info(),
+        };
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi,
+                signer_seeds,
+            ),
+            amount,
+        ).map_err(|_| ErrorCode::SwapExecutionFailed)?;
+    }
+
+    let relayer_to_user_cpi = Transfer {
+        from: ctx.accounts.relayer_token_out_account.to_account_info(),
+        to: ctx.accounts.user_token_out_account.to_account_info(),
+        authority: ctx.accounts.relayer.to_account_info(),
+    };
+    token::transfer(
+        CpiContext::new(ctx.accounts.token_program.to_account_info(), relayer_to_user_cpi),
+        user_out,
+    ).map_err(|_| ErrorCode::SwapExecutionFailed)?;
+
+    // ==================== STATE UPDATE ====================
+
+    let completed = filled_after == intent.amount_in;
+
+    let stored = &mut ctx.accounts.swap_intent;
+    stored.filled_in = filled_after;
+    stored.last_fill_at = clock.unix_timestamp;
+    stored.revealed = completed;
+
+    record_fill_volume(
+        &ctx.accounts.config,
+        &mut ctx.accounts.user_stats,
+        &price_in,
+        &mut ctx.accounts.token_in_config,
+        slice_in,
+        Some((&mut ctx.accounts.token_out_config, amount_out)),
+        clock.unix_timestamp,
+    )?;
+    ctx.accounts.fee_ledger.record_collected(protocol_fee)?;
+
+    emit!(SliceExecuted {
+        user: intent.user,
+        relayer: ctx.accounts.relayer.key(),
+        token_in: intent.token_in,
+        token_out: intent.token_out,
+        amount_in: slice_in,
+        amount_out,
+        protocol_fee,
+        fee_discount_bps,
+        relayer_fee,
+        total_filled_in: filled_after,
+        completed,
+        nonce: intent.nonce,
+        timestamp: clock.unix_timestamp,
+    });
+
+    // Return the intent's rent to the user once the last slice fills
+    if completed {
+        ctx.accounts.swap_intent.close(ctx.accounts.user.to_account_info())?;
+    }
+
+    Ok(())
+}
+
+// ==================== EVENTS ====================
+
+#[event]
+pub struct SliceExecuted {
+    pub user: Pubkey,
+    pub relayer: Pubkey,
+    pub token_in: Pubkey,
+    pub token_out: Pubkey,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub protocol_fee: u64,
+    /// Volume-tier discount applied to the protocol fee
+    pub fee_discount_bps: u16,
+    pub relayer_fee: u64,
+    pub total_filled_in: u64,
+    pub completed: bool,
+    pub nonce: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,583 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer, Mint};
+use crate::{
+    state::*, errors::ErrorCode, constants::*,
+    oracle::{MintPrice, check_price_deviation},
+};
+use super::reveal_trade::{
+    verify_commitment, validate_relayer, validate_relayer_fee, verify_ed25519_signature,
+    quote_exact_in, quote_hop, tiered_fee_bps, record_fill_volume,
+};
+
+/// Fixed-point scale for the peer-to-peer clearing price
+const PRICE_SCALE: u128 = 1_000_000_000_000;
+
+#[derive(Accounts)]
+pub struct MatchIntents<'info> {
+    #[account(
+        mut,
+        constraint = swap_intent_a.user == user_a.key() @ ErrorCode::InvalidSignature,
+        seeds = [b"intent", user_a.key().as_ref(), &swap_intent_a.nonce.to_le_bytes()],
+        bump
+    )]
+    pub swap_intent_a: Box<Account<'info, SwapIntent>>,
+
+    #[account(
+        mut,
+        constraint = swap_intent_b.user == user_b.key() @ ErrorCode::InvalidSignature,
+        constraint = swap_intent_b.key() != swap_intent_a.key() @ ErrorCode::IntentsNotCompatible,
+        seeds = [b"intent", user_b.key().as_ref(), &swap_intent_b.nonce.to_le_bytes()],
+        bump
+    )]
+    pub swap_intent_b: Box<Account<'info, SwapIntent>>,
+
+    /// User selling token A for token B
+    #[account(mut)]
+    pub user_a: Signer<'info>,
+
+    /// User selling token B for token A; a different wallet from `user_a`,
+    /// since both legs would otherwise write the same stats account
+    #[account(
+        mut,
+        constraint = user_b.key() != user_a.key() @ ErrorCode::SelfMatch
+    )]
+    pub user_b: Signer<'info>,
+
+    /// CHECK: We're verifying instruction manually for ed25519 signature
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    #[account(seeds = [CONFIG_PDA_SEED], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(seeds = [DENYLIST_PDA_SEED], bump = denylist.bump)]
+    pub denylist: Box<Account<'info, Denylist>>,
+
+    /// Rolling volume of user A, which sets their fee tier
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = 8 + UserStats::SIZE,
+        seeds = [USER_STATS_PDA_SEED, user_a.key().as_ref()],
+        bump
+    )]
+    pub user_a_stats: Box<Account<'info, UserStats>>,
+
+    /// Rolling volume of user B, which sets their fee tier
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = 8 + UserStats::SIZE,
+        seeds = [USER_STATS_PDA_SEED, user_b.key().as_ref()],
+        bump
+    )]
+    pub user_b_stats: Box<Account<'info, UserStats>>,
+
+    #[account(
+        mut,
+        constraint = user_a_token_in_account.mint == token_a_mint.key(),
+        constraint = user_a_token_in_account.owner == user_a.key()
+    )]
+    pub user_a_token_in_account: Box<Account<'info, TokenAccount>>,
+
+    /// Output account, owned by the recipient of intent A
+    #[account(
+        mut,
+        constraint = user_a_token_out_account.mint == token_b_mint.key()
+    )]
+    pub user_a_token_out_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = user_b_token_in_account.mint == token_b_mint.key(),
+        constraint = user_b_token_in_account.owner == user_b.key()
+    )]
+    pub user_b_token_in_account: Box<Account<'info, TokenAccount>>,
+
+    /// Output account, owned by the recipient of intent B
+    #[account(
+        mut,
+        constraint = user_b_token_out_account.mint == token_a_mint.key()
+    )]
+    pub user_b_token_out_account: Box<Account<'info, TokenAccount>>,
+
+    /// Relayer token A account, used for leftovers and relayer fees
+    #[account(
+        mut,
+        constraint = relayer_token_a_account.mint == token_a_mint.key(),
+        constraint = relayer_token_a_account.owner == relayer.key()
+    )]
+    pub relayer_token_a_account: Box<Account<'info, TokenAccount>>,
+
+    /// Relayer token B account, used for leftovers and relayer fees
+    #[account(
+        mut,
+        constraint = relayer_token_b_account.mint == token_b_mint.key(),
+        constraint = relayer_token_b_account.owner == relayer.key()
+    )]
+    pub relayer_token_b_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+
+    /// Registry entry of the relayer, required when matching open intents
+    #[account(
+        seeds = [RELAYER_PDA_SEED, relayer.key().as_ref()],
+        bump = relayer_registration.bump
+    )]
+    pub relayer_registration: Option<Account<'info, RelayerRegistration>>,
+
+    pub token_a_mint: Box<Account<'info, Mint>>,
+    pub token_b_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [MINT_CONFIG_PDA_SEED, token_a_mint.key().as_ref()],
+        bump = token_a_config.bump
+    )]
+    pub token_a_config: Box<Account<'info, MintConfig>>,
+
+    #[account(
+        mut,
+        seeds = [MINT_CONFIG_PDA_SEED, token_b_mint.key().as_ref()],
+        bump = token_b_config.bump
+    )]
+    pub token_b_config: Box<Account<'info, MintConfig>>,
+
+    /// CHECK: Matched against `token_a_config.oracle` and parsed in-program
+    pub token_a_oracle: Option<AccountInfo<'info>>,
+
+    /// CHECK: Matched against `token_b_config.oracle` and parsed in-program
+    pub token_b_oracle: Option<AccountInfo<'info>>,
+
+    #[account(
+        mut,
+        seeds = [FEE_COLLECTION_PDA_SEED, token_a_mint.key().as_ref()],
+        bump,
+        constraint = fee_collection_a_account.owner == fee_collection_a_authority.key()
+    )]
+    pub fee_collection_a_account: Box<Account<'info, TokenAccount>>,
+
+    /// Fee totals of `token_a_mint`
+    #[account(
+        mut,
+        seeds = [FEE_LEDGER_PDA_SEED, token_a_mint.key().as_ref()],
+        bump = fee_ledger_a.bump
+    )]
+    pub fee_ledger_a: Box<Account<'info, FeeLedger>>,
+
+    #[account(
+        mut,
+        seeds = [FEE_COLLECTION_PDA_SEED, token_b_mint.key().as_ref()],
+        bump,
+        constraint = fee_collection_b_account.owner == fee_collection_b_authority.key()
+    )]
+    pub fee_collection_b_account: Box<Account<'info, TokenAccount>>,
+
+    /// Fee totals of `token_b_mint`
+    #[account(
+        mut,
+        seeds = [FEE_LEDGER_PDA_SEED, token_b_mint.key().as_ref()],
+        bump = fee_ledger_b.bump
+    )]
+    pub fee_ledger_b: Box<Account<'info, FeeLedger>>,
+
+    /// CHECK: Fee collection authority PDA of `token_a_mint`
+    #[account(
+        seeds = [FEE_AUTHORITY_PDA_SEED, token_a_mint.key().as_ref()],
+        bump
+    )]
+    pub fee_collection_a_authority: AccountInfo<'info>,
+
+    /// CHECK: Fee collection authority PDA of `token_b_mint`
+    #[account(
+        seeds = [FEE_AUTHORITY_PDA_SEED, token_b_mint.key().as_ref()],
+        bump
+    )]
+    pub fee_collection_b_authority: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Amounts moved by a peer-to-peer match
+struct MatchSettlement {
+    /// Token A moved from user A to user B
+    matched_a: u64,
+    /// Token B moved from user B to user A
+    matched_b: u64,
+    /// Token A of user A left unmatched and filled by the relayer
+    leftover_a: u64,
+    /// Token B of user B left unmatched and filled by the relayer
+    leftover_b: u64,
+    /// Token B the relayer pays user A for `leftover_a`
+    relayer_out_b: u64,
+    /// Token A the relayer pays user B for `leftover_b`
+    relayer_out_a: u64,
+}
+
+/// One side's output after withholding its relayer fee
+struct RecipientPayout {
+    /// Output the relayer delivers to the recipient
+    relayer_to_recipient: u64,
+    /// Output the counterparty delivers to the recipient
+    peer_to_recipient: u64,
+    /// Counterparty output diverted to the relayer as fee
+    peer_to_relayer: u64,
+}
+
+pub fn handle_match(
+    ctx: Context<MatchIntents>,
+    intent_a: TradeIntentData,
+    expected_hash_a: [u8; 32],
+    signature_a: [u8; 64],
+    intent_b: TradeIntentData,
+    expected_hash_b: [u8; 32],
+    signature_b: [u8; 64],
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let relayer = ctx.accounts.relayer.key();
+
+    // ==================== VALIDATION PHASE ====================
+
+    for (stored, intent, expected_hash, user) in [
+        (&ctx.accounts.swap_intent_a, &intent_a, &expected_hash_a, ctx.accounts.user_a.key()),
+        (&ctx.accounts.swap_intent_b, &intent_b, &expected_hash_b, ctx.accounts.user_b.key()),
+    ] {
+        verify_commitment(stored, intent, expected_hash, &user, clock.unix_timestamp)?;
+        ctx.accounts.denylist.check_intent(intent)?;
+        validate_relayer_fee(intent, &ctx.accounts.config)?;
+        validate_relayer(
+            intent,
+            &relayer,
+            ctx.accounts.relayer_registration.as_ref(),
+            stored.commit_slot,
+            clock.slot,
+        )?;
+
+        // Only whole, single-hop exact-in intents without a referral can be matched
+        require!(stored.filled_in == 0, ErrorCode::UnsupportedIntentKind);
+        require!(intent.route.is_empty(), ErrorCode::RouteNotSupported);
+        require!(!intent.has_referral(), ErrorCode::ReferralNotSupported);
+        require!(
+            matches!(intent.kind, IntentKind::Standard | IntentKind::DutchAuction { .. }),
+            ErrorCode::UnsupportedIntentKind
+        );
+    }
+
+    // Intents must trade opposite sides of the same pair
+    require_keys_eq!(intent_a.token_in, ctx.accounts.token_a_mint.key(), ErrorCode::HashMismatch);
+    require_keys_eq!(intent_a.token_out, ctx.accounts.token_b_mint.key(), ErrorCode::HashMismatch);
+    require_keys_eq!(intent_b.token_in, ctx.accounts.token_b_mint.key(), ErrorCode::HashMismatch);
+    require_keys_eq!(intent_b.token_out, ctx.accounts.token_a_mint.key(), ErrorCode::HashMismatch);
+    require_keys_eq!(
+        ctx.accounts.user_a_token_out_account.owner,
+        intent_a.recipient,
+        ErrorCode::InvalidRecipient
+    );
+    require_keys_eq!(
+        ctx.accounts.user_b_token_out_account.owner,
+        intent_b.recipient,
+        ErrorCode::InvalidRecipient
+    );
+
+    // ==================== ED25519 SIGNATURE VERIFICATION ====================
+
+    // Signatures for A and B are expected in the two preceding instructions
+    verify_ed25519_signature(
+        &ctx.accounts.instructions_sysvar,
+        2,
+        &signature_a,
+        &ctx.accounts.user_a.key().to_bytes(),
+        &expected_hash_a,
+    )?;
+    verify_ed25519_signature(
+        &ctx.accounts.instructions_sysvar,
+        1,
+        &signature_b,
+        &ctx.accounts.user_b.key().to_bytes(),
+        &expected_hash_b,
+    )?;
+
+    // ==================== FEE TIER ====================
+
+    let day = unix_day(clock.unix_timestamp);
+    ctx.accounts.user_a_stats.initialize_if_new(ctx.accounts.user_a.key(), ctx.bumps.user_a_stats);
+    ctx.accounts.user_b_stats.initialize_if_new(ctx.accounts.user_b.key(), ctx.bumps.user_b_stats);
+    let (fee_bps_a, fee_discount_bps_a) = tiered_fee_bps(&ctx.accounts.config, &ctx.accounts.user_a_stats, day)?;
+    let (fee_bps_b, fee_discount_bps_b) = tiered_fee_bps(&ctx.accounts.config, &ctx.accounts.user_b_stats, day)?;
+
+    // ==================== CLEARING ====================
+
+    let (protocol_fee_a, net_a) = quote_exact_in(intent_a.amount_in, fee_bps_a)?;
+    let (protocol_fee_b, net_b) = quote_exact_in(intent_b.amount_in, fee_bps_b)?;
+    let min_out_a = intent_a.required_min_out(clock.unix_timestamp)?;
+    let min_out_b = intent_b.required_min_out(clock.unix_timestamp)?;
+
+    let settlement = clear_match(net_a, min_out_a, net_b, min_out_b)?;
+
+    // Both users must receive at least their required output
+    let total_out_a = settlement.matched_b
+        .checked_add(settlement.relayer_out_b)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let total_out_b = settlement.matched_a
+        .checked_add(settlement.relayer_out_a)
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(total_out_a >= min_out_a, ErrorCode::SlippageExceeded);
+    require!(total_out_b >= min_out_b, ErrorCode::SlippageExceeded);
+
+    // ==================== ORACLE GUARD ====================
+
+    // Each side's effective price at the clearing price must track the oracle
+    let price_a = MintPrice::load(
+        &ctx.accounts.token_a_config,
+        ctx.accounts.token_a_oracle.as_ref(),
+        ctx.accounts.token_a_mint.decimals,
+        clock.unix_timestamp,
+    )?;
+    let price_b = MintPrice::load(
+        &ctx.accounts.token_b_config,
+        ctx.accounts.token_b_oracle.as_ref(),
+        ctx.accounts.token_b_mint.decimals,
+        clock.unix_timestamp,
+    )?;
+    check_price_deviation(&price_a, &price_b, net_a, total_out_a)?;
+    check_price_deviation(&price_b, &price_a, net_b, total_out_b)?;
+
+    // Token-in relayer fees are paid on top of the input; token-out fees
+    // are withheld from each side's output
+    let (relayer_fee_in_a, relayer_fee_out_a) = intent_a.split_relayer_fee(intent_a.relayer_fee);
+    let (relayer_fee_in_b, relayer_fee_out_b) = intent_b.split_relayer_fee(intent_b.relayer_fee);
+    let payout_a = withhold_relayer_fee(relayer_fee_out_a, settlement.relayer_out_b, settlement.matched_b)?;
+    let payout_b = withhold_relayer_fee(relayer_fee_out_b, settlement.relayer_out_a, settlement.matched_a)?;
+
+    require!(
+        ctx.accounts.user_a_token_in_account.amount >= intent_a.amount_in
+            .checked_add(relayer_fee_in_a)
+            .ok_or(ErrorCode::MathOverflow)?,
+        ErrorCode::InsufficientBalance
+    );
+    require!(
+        ctx.accounts.user_b_token_in_account.amount >= intent_b.amount_in
+            .checked_add(relayer_fee_in_b)
+            .ok_or(ErrorCode::MathOverflow)?,
+        ErrorCode::InsufficientBalance
+    );
+
+    // ==================== STATE UPDATE ====================
+
+    let swap_intent_a = &mut ctx.accounts.swap_intent_a;
+    swap_intent_a.filled_in = intent_a.amount_in;
+    swap_intent_a.revealed = true;
+
+    let swap_intent_b = &mut ctx.accounts.swap_intent_b;
+    swap_intent_b.filled_in = intent_b.amount_in;
+    swap_intent_b.revealed = true;
+
+    record_fill_volume(
+        &ctx.accounts.config,
+        &mut ctx.accounts.user_a_stats,
+        &price_a,
+        &mut ctx.accounts.token_a_config,
+        intent_a.amount_in,
+        Some((&mut ctx.accounts.token_b_config, total_out_a)),
+        clock.unix_timestamp,
+    )?;
+    record_fill_volume(
+        &ctx.accounts.config,
+        &mut ctx.accounts.user_b_stats,
+        &price_b,
+        &mut ctx.accounts.token_b_config,
+        intent_b.amount_in,
+        Some((&mut ctx.accounts.token_a_config, total_out_b)),
+        clock.unix_timestamp,
+    )?;
+
+    ctx.accounts.fee_ledger_a.record_collected(protocol_fee_a)?;
+    ctx.accounts.fee_ledger_b.record_collected(protocol_fee_b)?;
+
+    // ==================== ATOMIC SETTLEMENT ====================
+
+    let token_program = ctx.accounts.token_program.to_account_info();
+    let user_a = ctx.accounts.user_a.to_account_info();
+    let user_b = ctx.accounts.user_b.to_account_info();
+    let relayer_info = ctx.accounts.relayer.to_account_info();
+
+    let transfers = [
+        // Protocol fees on each side
+        (&ctx.accounts.user_a_token_in_account, &ctx.accounts.fee_collection_a_account, &user_a, protocol_fee_a),
+        (&ctx.accounts.user_b_token_in_account, &ctx.accounts.fee_collection_b_account, &user_b, protocol_fee_b),
+        // Peer-to-peer legs
+        (&ctx.accounts.user_a_token_in_account, &ctx.accounts.user_b_token_out_account, &user_a, payout_b.peer_to_recipient),
+        (&ctx.accounts.user_b_token_in_account, &ctx.accounts.user_a_token_out_account, &user_b, payout_a.peer_to_recipient),
+        // Leftovers filled from relayer inventory
+        (&ctx.accounts.user_a_token_in_account, &ctx.accounts.relayer_token_a_account, &user_a, settlement.leftover_a),
+        (&ctx.accounts.relayer_token_b_account, &ctx.accounts.user_a_token_out_account, &relayer_info, payout_a.relayer_to_recipient),
+        (&ctx.accounts.user_b_token_in_account, &ctx.accounts.relayer_token_b_account, &user_b, settlement.leftover_b),
+        (&ctx.accounts.relayer_token_a_account, &ctx.accounts.user_b_token_out_account, &relayer_info, payout_b.relayer_to_recipient),
+        // Relayer fees not covered by the relayer's own fill come out of the peer legs
+        (&ctx.accounts.user_b_token_in_account, &ctx.accounts.relayer_token_b_account, &user_b, payout_a.peer_to_relayer),
+        (&ctx.accounts.user_a_token_in_account, &ctx.accounts.relayer_token_a_account, &user_a, payout_b.peer_to_relayer),
+        // Token-in relayer fees
+        (&ctx.accounts.user_a_token_in_account, &ctx.accounts.relayer_token_a_account, &user_a, relayer_fee_in_a),
+        (&ctx.accounts.user_b_token_in_account, &ctx.accounts.relayer_token_b_account, &user_b, relayer_fee_in_b),
+    ];
+
+    for (from, to, authority, amount) in transfers {
+        if amount == 0 {
+            continue;
+        }
+
+        let cpi = Transfer {
+            from: from.to_account_info(),
+            to: to.to_account_info(),
+            authority: authority.clone(),
+        };
+
+        token::transfer(CpiContext::new(token_program.clone(), cpi), amount)
+            .map_err(|_| ErrorCode::SwapExecutionFailed)?;
+    }
+
+    // ==================== SUCCESS EVENT ====================
+
+    emit!(IntentsMatched {
+        user_a: intent_a.user,
+        user_b: intent_b.user,
+        nonce_a: intent_a.nonce,
+        nonce_b: intent_b.nonce,
+        relayer,
+        token_a: intent_a.token_in,
+        token_b: intent_b.token_in,
+        matched_a: settlement.matched_a,
+        matched_b: settlement.matched_b,
+        relayer_fill_a: settlement.relayer_out_a,
+        relayer_fill_b: settlement.relayer_out_b,
+        protocol_fee_a,
+        protocol_fee_b,
+        fee_discount_bps_a,
+        fee_discount_bps_b,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Intents matched peer-to-peer: {} A <-> {} B", settlement.matched_a, settlement.matched_b);
+
+    Ok(())
+}
+
+// ==================== HELPER FUNCTIONS ====================
+
+/// Clear two opposing intents at the midpoint of their limit prices.
+///
+/// User A sells `net_a` of token A for at least `min_out_a` of token B, and
+/// user B sells `net_b` of token B for at least `min_out_b` of token A. The
+/// side that is not fully absorbed by the other is filled by the relayer.
+fn clear_match(net_a: u64, min_out_a: u64, net_b: u64, min_out_b: u64) -> Result<MatchSettlement> {
+    require!(net_a > 0 && net_b > 0 && min_out_b > 0, ErrorCode::AmountTooSmall);
+
+    // Limit prices in token B per token A: A asks at least `price_a`,
+    // B bids at most `price_b`
+    let price_a = (min_out_a as u128)
+        .checked_mul(PRICE_SCALE)
+        .ok_or(ErrorCode::MathOverflow)?
+        / net_a as u128;
+    let price_b = (net_b as u128)
+        .checked_mul(PRICE_SCALE)
+        .ok_or(ErrorCode::MathOverflow)?
+        / min_out_b as u128;
+    require!(price_a <= price_b, ErrorCode::IntentsNotCompatible);
+
+    let price = (price_a + price_b) / 2;
+    require!(price > 0, ErrorCode::IntentsNotCompatible);
+
+    // Token A that B's whole input buys at the clearing price
+    let affordable_a = (net_b as u128)
+        .checked_mul(PRICE_SCALE)
+        .ok_or(ErrorCode::MathOverflow)?
+        / price;
+    let matched_a = affordable_a.min(net_a as u128) as u64;
+    let matched_b = ((matched_a as u128 * price) / PRICE_SCALE).min(net_b as u128) as u64;
+
+    let leftover_a = net_a - matched_a;
+    let leftover_b = net_b - matched_b;
+
+    Ok(MatchSettlement {
+        matched_a,
+        matched_b,
+        leftover_a,
+        leftover_b,
+        relayer_out_b: quote_hop(leftover_a)?,
+        relayer_out_a: quote_hop(leftover_b)?,
+    })
+}
+
+/// Withhold `relayer_fee` from one side's output, first from what the
+/// relayer delivers and then from the counterparty leg
+fn withhold_relayer_fee(relayer_fee: u64, relayer_out: u64, peer_out: u64) -> Result<RecipientPayout> {
+    let from_relayer = relayer_fee.min(relayer_out);
+    let peer_to_relayer = relayer_fee - from_relayer;
+
+    Ok(RecipientPayout {
+        relayer_to_recipient: relayer_out - from_relayer,
+        peer_to_recipient: peer_out
+            .checked_sub(peer_to_relayer)
+            .ok_or(ErrorCode::MathOverflow)?,
+        peer_to_relayer,
+    })
+}
+
+// ==================== EVENTS ====================
+
+#[event]
+pub struct IntentsMatched {
+    pub user_a: Pubkey,
+    pub user_b: Pubkey,
+    pub nonce_a: u64,
+    pub nonce_b: u64,
+    pub relayer: Pubkey,
+    pub token_a: Pubkey,
+    pub token_b: Pubkey,
+    pub matched_a: u64,
+    pub matched_b: u64,
+    pub relayer_fill_a: u64,
+    pub relayer_fill_b: u64,
+    pub protocol_fee_a: u64,
+    pub protocol_fee_b: u64,
+    /// Volume-tier discounts applied to each side's protocol fee
+    pub fee_discount_bps_a: u16,
+    pub fee_discount_bps_b: u16,
+    pub timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn match_clears_at_midpoint_and_relayer_fills_rest() {
+        // A asks 1 B per A, B bids 2 B per A: clears at 1.5
+        let settlement = clear_match(1_000, 1_000, 2_000, 1_000).unwrap();
+
+        assert_eq!((settlement.matched_a, settlement.matched_b), (1_000, 1_500));
+        assert_eq!((settlement.leftover_a, settlement.leftover_b), (0, 500));
+        assert_eq!((settlement.relayer_out_b, settlement.relayer_out_a), (0, 500));
+    }
+
+    #[test]
+    fn match_absorbs_smaller_side() {
+        // B only has enough to buy 400 A at the 1.5 midpoint
+        let settlement = clear_match(1_000, 1_000, 600, 300).unwrap();
+
+        assert_eq!((settlement.matched_a, settlement.matched_b), (400, 600));
+        assert_eq!((settlement.leftover_a, settlement.leftover_b), (600, 0));
+        assert_eq!(settlement.relayer_out_b, 600);
+    }
+
+    #[test]
+    fn relayer_fee_comes_from_relayer_leg_first() {
+        let payout = withhold_relayer_fee(30, 20, 1_000).unwrap();
+
+        assert_eq!(payout.relayer_to_recipient, 0);
+        assert_eq!(payout.peer_to_relayer, 10);
+        assert_eq!(payout.peer_to_recipient, 990);
+    }
+}
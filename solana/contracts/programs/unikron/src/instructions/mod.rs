@@ -1,9 +1,23 @@
 // programs/unikron/src/instructions/mod.rs
 
+pub mod admin;
+pub mod batch_auction;
+pub mod bounty;
 pub mod commit_trade;
+pub mod execute_slice;
+pub mod match_intents;
+pub mod referral;
 pub mod reveal_trade;
 pub mod settle_trade;
+pub mod staking;
 
+pub use admin::*;
+pub use batch_auction::*;
+pub use bounty::*;
 pub use commit_trade::*;
+pub use execute_slice::*;
+pub use match_intents::*;
+pub use referral::*;
 pub use reveal_trade::*;
-pub use settle_trade::*;
\ No newline at end of file
+pub use settle_trade::*;
+pub use staking::*;
\ No newline at end of file
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+use crate::{state::*, constants::*};
+
+#[derive(Accounts)]
+pub struct InitializeReferrerStats<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + ReferrerStats::SIZE,
+        seeds = [REFERRER_PDA_SEED, referrer.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub referrer_stats: Account<'info, ReferrerStats>,
+
+    /// CHECK: Only used as the key of the referrer being tracked
+    pub referrer: AccountInfo<'info>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_initialize_referrer_stats(ctx: Context<InitializeReferrerStats>) -> Result<()> {
+    let stats = &mut ctx.accounts.referrer_stats;
+    stats.referrer = ctx.accounts.referrer.key();
+    stats.mint = ctx.accounts.mint.key();
+    stats.total_earned = 0;
+    stats.referred_trades = 0;
+    stats.bump = ctx.bumps.referrer_stats;
+
+    msg!("Referrer stats initialized for {} in mint {}", stats.referrer, stats.mint);
+
+    Ok(())
+}
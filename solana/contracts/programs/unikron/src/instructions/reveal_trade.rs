@@ -1,6 +1,10 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer, Mint};
-use crate::{state::*, errors::ErrorCode, constants::*};
+use crate::{
+    state::*, errors::ErrorCode, constants::*,
+    math::{mul_div, mul_div_ceil},
+    oracle::{MintPrice, check_price_deviation, unit_price, notional_value},
+};
 use sha2::{Digest, Sha256};
 use solana_program::{
     ed25519_program,
@@ -24,6 +28,23 @@ pub struct RevealTrade<'info> {
     #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
     pub instructions_sysvar: AccountInfo<'info>,
 
+    #[account(seeds = [CONFIG_PDA_SEED], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(seeds = [DENYLIST_PDA_SEED], bump = denylist.bump)]
+    pub denylist: Box<Account<'info, Denylist>>,
+
+    /// Rolling volume of the user, which sets their fee tier; created by the
+    /// relayer on the user's first fill
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = 8 + UserStats::SIZE,
+        seeds = [USER_STATS_PDA_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Box<Account<'info, UserStats>>,
+
     // Token accounts for the actual swap
     #[account(
         mut,
@@ -32,10 +53,10 @@ pub struct RevealTrade<'info> {
     )]
     pub user_token_in_account: Account<'info, TokenAccount>,
 
+    /// Output account, owned by the intent's recipient
     #[account(
         mut,
-        constraint = user_token_out_account.mint == token_out_mint.key(),
-        constraint = user_token_out_account.owner == user.key()
+        constraint = user_token_out_account.mint == token_out_mint.key()
     )]
     pub user_token_out_account: Account<'info, TokenAccount>,
 
@@ -56,21 +77,82 @@ pub struct RevealTrade<'info> {
     #[account(mut)]
     pub relayer: Signer<'info>,
 
+    /// Registry entry of the relayer, required when filling an open intent
+    #[account(
+        seeds = [RELAYER_PDA_SEED, relayer.key().as_ref()],
+        bump = relayer_registration.bump
+    )]
+    pub relayer_registration: Option<Account<'info, RelayerRegistration>>,
+
     // Token mints
     pub token_in_mint: Account<'info, Mint>,
     pub token_out_mint: Account<'info, Mint>,
 
+    #[account(
+        mut,
+        seeds = [MINT_CONFIG_PDA_SEED, token_in_mint.key().as_ref()],
+        bump = token_in_config.bump
+    )]
+    pub token_in_config: Box<Account<'info, MintConfig>>,
+
+    #[account(
+        mut,
+        seeds = [MINT_CONFIG_PDA_SEED, token_out_mint.key().as_ref()],
+        bump = token_out_config.bump
+    )]
+    pub token_out_config: Box<Account<'info, MintConfig>>,
+
+    /// CHECK: Matched against `token_in_config.oracle` and parsed in-program
+    pub token_in_oracle: Option<AccountInfo<'info>>,
+
+    /// CHECK: Matched against `token_out_config.oracle` and parsed in-program
+    pub token_out_oracle: Option<AccountInfo<'info>>,
+
     // Fee collection account owned by fee authority PDA
     #[account(
         mut,
-        constraint = fee_collection_account.mint == token_in_mint.key(),
+        seeds = [FEE_COLLECTION_PDA_SEED, token_in_mint.key().as_ref()],
+        bump,
         constraint = fee_collection_account.owner == fee_collection_authority.key()
     )]
     pub fee_collection_account: Account<'info, TokenAccount>,
 
-    /// CHECK: PDA authority for fee collection
+    /// Fee totals of `token_in_mint`
     #[account(
-        seeds = [b"fee_authority"],
+        mut,
+        seeds = [FEE_LEDGER_PDA_SEED, token_in_mint.key().as_ref()],
+        bump = fee_ledger.bump
+    )]
+    pub fee_ledger: Box<Account<'info, FeeLedger>>,
+
+    /// MEV bounty pool for `token_out`, required when a fill produces surplus
+    /// to share
+    #[account(
+        mut,
+        seeds = [BOUNTY_PDA_SEED, token_out_mint.key().as_ref()],
+        bump
+    )]
+    pub bounty_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Referrer's `token_in` account, required when the intent pays a referral
+    #[account(
+        mut,
+        constraint = referrer_token_account.mint == token_in_mint.key()
+    )]
+    pub referrer_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Referrer's earnings record for `token_in`, required alongside
+    /// `referrer_token_account`
+    #[account(
+        mut,
+        seeds = [REFERRER_PDA_SEED, referrer_stats.referrer.as_ref(), token_in_mint.key().as_ref()],
+        bump = referrer_stats.bump
+    )]
+    pub referrer_stats: Option<Box<Account<'info, ReferrerStats>>>,
+
+    /// CHECK: Fee collection authority PDA of `token_in_mint`
+    #[account(
+        seeds = [FEE_AUTHORITY_PDA_SEED, token_in_mint.key().as_ref()],
         bump
     )]
     pub fee_collection_authority: AccountInfo<'info>,
@@ -79,134 +161,305 @@ pub struct RevealTrade<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn handle_reveal(
-    ctx: Context<RevealTrade>,
+pub fn handle_reveal<'info>(
+    ctx: Context<'_, '_, '_, 'info, RevealTrade<'info>>,
     intent: TradeIntentData,
     expected_hash: [u8; 32],
     signature: [u8; 64],
+    fill_amount: u64,
 ) -> Result<()> {
-    let stored = &mut ctx.accounts.swap_intent;
+    let stored = &ctx.accounts.swap_intent;
     let clock = Clock::get()?;
     
     // ==================== VALIDATION PHASE ====================
     
-    // Check if intent is already revealed
-    require!(!stored.revealed, ErrorCode::AlreadyRevealed);
+    // Check intent lifecycle, parameters and hash commitment
+    verify_commitment(
+        stored,
+        &intent,
+        &expected_hash,
+        &ctx.accounts.user.key(),
+        clock.unix_timestamp,
+    )?;
     
-    // Check expiry
+    // Recurring intents are filled slice by slice through `execute_slice`
     require!(
-        stored.expiry > clock.unix_timestamp as u64, 
-        ErrorCode::IntentExpired
+        !matches!(intent.kind, IntentKind::Recurring { .. }),
+        ErrorCode::UnsupportedIntentKind
     );
     
-    // Check nonce matches
-    require!(stored.nonce == intent.nonce, ErrorCode::NonceMismatch);
-    
-    // Verify user matches
-    require_keys_eq!(intent.user, ctx.accounts.user.key(), ErrorCode::InvalidSignature);
+    ctx.accounts.denylist.check_intent(&intent)?;
+    validate_relayer_fee(&intent, &ctx.accounts.config)?;
+    validate_referral(&intent, &ctx.accounts.config)?;
     
-    // Verify relayer matches
-    require_keys_eq!(intent.relayer, ctx.accounts.relayer.key(), ErrorCode::InvalidSignature);
+    // Verify relayer is allowed to fill this intent
+    validate_relayer(
+        &intent,
+        &ctx.accounts.relayer.key(),
+        ctx.accounts.relayer_registration.as_ref(),
+        stored.commit_slot,
+        clock.slot,
+    )?;
     
     // Verify token accounts match intent
     require_keys_eq!(intent.token_in, ctx.accounts.token_in_mint.key(), ErrorCode::HashMismatch);
     require_keys_eq!(intent.token_out, ctx.accounts.token_out_mint.key(), ErrorCode::HashMismatch);
+    require_keys_eq!(
+        ctx.accounts.user_token_out_account.owner,
+        intent.recipient,
+        ErrorCode::InvalidRecipient
+    );
     
-    // Validate amounts
-    require!(intent.amount_in > 0, ErrorCode::AmountTooSmall);
-    require!(intent.min_out > 0, ErrorCode::AmountTooSmall);
-    require!(intent.relayer_fee < intent.amount_in / 10, ErrorCode::RelayerFeeTooHigh);
-    
-    // ==================== HASH VERIFICATION ====================
-    
-    // Reconstruct hash of TradeIntentData
-    let mut hasher = Sha256::new();
-    hasher.update(intent.try_to_vec()?);
-    let computed_hash = hasher.finalize();
-    
-    // Verify hash matches commitment
+    // Intermediate route mints are passed in order as remaining accounts
     require!(
-        computed_hash[..] == expected_hash[..], 
-        ErrorCode::HashMismatch
+        ctx.remaining_accounts.len() >= intent.route.len(),
+        ErrorCode::InvalidTokenMint
     );
+    for (hop, account) in intent.route.iter().zip(ctx.remaining_accounts.iter()) {
+        require_keys_eq!(hop.mint, account.key(), ErrorCode::InvalidTokenMint);
+        require_keys_eq!(*account.owner, token::ID, ErrorCode::InvalidTokenMint);
+        Mint::try_deserialize(&mut &account.try_borrow_data()?[..])
+            .map_err(|_| ErrorCode::InvalidTokenMint)?;
+    }
     
-    // Verify stored hash matches
+    // Validate fill amount against the unfilled remainder
+    require!(fill_amount > 0, ErrorCode::AmountTooSmall);
     require!(
-        stored.intent_hash == expected_hash,
-        ErrorCode::HashMismatch
+        intent.allow_partial || fill_amount == intent.amount_in,
+        ErrorCode::PartialFillNotAllowed
     );
+    let filled_after = stored.filled_in
+        .checked_add(fill_amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(filled_after <= intent.amount_in, ErrorCode::FillExceedsRemaining);
     
     // ==================== ED25519 SIGNATURE VERIFICATION ====================
     
     verify_ed25519_signature(
         &ctx.accounts.instructions_sysvar,
+        1,
         &signature,
         &ctx.accounts.user.key().to_bytes(),
         &expected_hash,
     )?;
     
-    // ==================== AMOUNT VALIDATION ====================
+    // ==================== FEE TIER ====================
     
-    // Check user has sufficient balance
-    require!(
-        ctx.accounts.user_token_in_account.amount >= intent.amount_in,
-        ErrorCode::InsufficientBalance
-    );
+    let day = unix_day(clock.unix_timestamp);
+    ctx.accounts.user_stats.initialize_if_new(ctx.accounts.user.key(), ctx.bumps.user_stats);
+    let (fee_bps, fee_discount_bps) = tiered_fee_bps(&ctx.accounts.config, &ctx.accounts.user_stats, day)?;
     
-    // ==================== CALCULATE PROTOCOL FEE ====================
+    // ==================== SWAP QUOTE ====================
     
-    let protocol_fee = intent.amount_in
-        .checked_mul(FEE_BASIS_POINTS)
-        .ok_or(ErrorCode::MathOverflow)?
-        .checked_div(10000)
-        .ok_or(ErrorCode::MathOverflow)?;
+    let relayer_fee = mul_div(intent.relayer_fee, fill_amount, intent.amount_in)?;
+    let (relayer_fee_in, relayer_fee_out) = intent.split_relayer_fee(relayer_fee);
     
-    // ==================== MOCK SWAP CALCULATION ====================
+    // `(leg_in, leg_out)` of basket legs after the first, delivered via
+    // remaining accounts
+    let mut extra_leg_fills: Vec<(u64, u64)> = Vec::new();
     
-    // Mock Jupiter route calculation
-    let swap_amount_after_fee = intent.amount_in
-        .checked_sub(protocol_fee)
+    // `priced_in` is the post-fee input behind `actual_out_amount`, used by the oracle guard
+    let (swap_in, protocol_fee, actual_out_amount, priced_in) = match &intent.kind {
+        IntentKind::ExactOut { amount_out, max_in } => {
+            let (swap_in, protocol_fee) = quote_exact_out(*amount_out, fee_bps)?;
+            
+            // Exact-out slippage is bounded on the input side
+            require!(swap_in <= *max_in, ErrorCode::SlippageExceeded);
+            (swap_in, protocol_fee, *amount_out, swap_in - protocol_fee)
+        }
+        IntentKind::Basket { legs } => {
+            let (protocol_fee, swap_amount_after_fee) = quote_exact_in(fill_amount, fee_bps)?;
+            let leg_fills = quote_basket(
+                swap_amount_after_fee,
+                legs,
+                fill_amount,
+                intent.amount_in,
+            )?;
+            extra_leg_fills = leg_fills[1..].to_vec();
+            let (first_leg_in, first_leg_out) = leg_fills[0];
+            (fill_amount, protocol_fee, first_leg_out, first_leg_in)
+        }
+        _ => {
+            let (protocol_fee, swap_amount_after_fee) = quote_exact_in(fill_amount, fee_bps)?;
+            let actual_out_amount = quote_route(
+                swap_amount_after_fee,
+                &intent.route,
+                fill_amount,
+                intent.amount_in,
+            )?;
+            
+            // Validate slippage against the output required at this moment,
+            // scaled pro-rata to the size of this fill
+            let required_out = mul_div_ceil(
+                intent.required_min_out(clock.unix_timestamp)?,
+                fill_amount,
+                intent.amount_in,
+            )?;
+            require!(
+                actual_out_amount >= required_out,
+                ErrorCode::SlippageExceeded
+            );
+            (fill_amount, protocol_fee, actual_out_amount, swap_amount_after_fee)
+        }
+    };
+    
+    // ==================== ORACLE GUARD ====================
+    
+    // Any oracle configured for either mint must be passed and fresh
+    let price_in = MintPrice::load(
+        &ctx.accounts.token_in_config,
+        ctx.accounts.token_in_oracle.as_ref(),
+        ctx.accounts.token_in_mint.decimals,
+        clock.unix_timestamp,
+    )?;
+    let price_out = MintPrice::load(
+        &ctx.accounts.token_out_config,
+        ctx.accounts.token_out_oracle.as_ref(),
+        ctx.accounts.token_out_mint.decimals,
+        clock.unix_timestamp,
+    )?;
+    
+    check_price_deviation(&price_in, &price_out, priced_in, actual_out_amount)?;
+    
+    if let IntentKind::Conditional { trigger_price, direction } = &intent.kind {
+        check_trigger_price(&price_in, &price_out, *trigger_price, direction)?;
+    }
+    
+    // Input the user committed to but that this fill does not need stays with them
+    let unused_in = fill_amount
+        .checked_sub(swap_in)
         .ok_or(ErrorCode::MathOverflow)?;
     
-    // Simulate 1:1 swap for simplicity (in production, use Jupiter CPI)
-    let actual_out_amount = swap_amount_after_fee;
+    // ==================== SURPLUS SHARING ====================
     
-    // Validate slippage
+    // Output above the quote for this fill is split with the MEV bounty pool
+    let surplus = if intent.quoted_out > 0 {
+        let quoted_for_fill = mul_div(intent.quoted_out, fill_amount, intent.amount_in)?;
+        actual_out_amount.saturating_sub(quoted_for_fill)
+    } else {
+        0
+    };
+    let surplus_to_bounty = mul_div(surplus, ctx.accounts.config.surplus_bounty_bps as u64, 10000)?;
+    
+    // ==================== REFERRAL ====================
+    
+    let referral_fee = mul_div(swap_in, intent.referral_bps as u64, 10000)?;
+    if referral_fee > 0 {
+        let referrer_account = ctx.accounts.referrer_token_account
+            .as_ref()
+            .ok_or(ErrorCode::InvalidReferrer)?;
+        let referrer_stats = ctx.accounts.referrer_stats
+            .as_ref()
+            .ok_or(ErrorCode::InvalidReferrer)?;
+        require_keys_eq!(referrer_account.owner, intent.referrer, ErrorCode::InvalidReferrer);
+        require_keys_eq!(referrer_stats.referrer, intent.referrer, ErrorCode::InvalidReferrer);
+    }
+    
+    // ==================== AMOUNT VALIDATION ====================
+    
+    // Check user has sufficient balance, including token-in relayer and
+    // referral fees
     require!(
-        actual_out_amount >= intent.min_out,
-        ErrorCode::SlippageExceeded
+        ctx.accounts.user_token_in_account.amount >= swap_in
+            .checked_add(relayer_fee_in)
+            .and_then(|total| total.checked_add(referral_fee))
+            .ok_or(ErrorCode::MathOverflow)?,
+        ErrorCode::InsufficientBalance
     );
     
-    // Check relayer has sufficient balance for providing output tokens
+    // Check relayer has sufficient balance for providing output tokens;
+    // a token-out relayer fee and the bounty's surplus share are withheld
+    // from the output
+    let delivered_out = actual_out_amount
+        .checked_sub(relayer_fee_out)
+        .and_then(|out| out.checked_sub(surplus_to_bounty))
+        .ok_or(ErrorCode::MathOverflow)?;
     require!(
-        ctx.accounts.relayer_token_out_account.amount >= actual_out_amount,
+        ctx.accounts.relayer_token_out_account.amount >= delivered_out + surplus_to_bounty,
         ErrorCode::InsufficientBalance
     );
     
+    // ==================== RISK LIMITS ====================
+    
+    record_fill_volume(
+        &ctx.accounts.config,
+        &mut ctx.accounts.user_stats,
+        &price_in,
+        &mut ctx.accounts.token_in_config,
+        swap_in,
+        Some((&mut ctx.accounts.token_out_config, actual_out_amount)),
+        clock.unix_timestamp,
+    )?;
+    
+    if let IntentKind::Basket { legs } = &intent.kind {
+        check_basket_legs(
+            ctx.remaining_accounts,
+            &legs[1..],
+            &extra_leg_fills,
+            &price_in,
+            ctx.program_id,
+            clock.unix_timestamp,
+        )?;
+    }
+    
     // ==================== STATE UPDATE ====================
     
-    stored.revealed = true;
+    // Intent only counts as revealed once fully filled
+    let stored = &mut ctx.accounts.swap_intent;
+    stored.filled_in = filled_after;
+    stored.last_fill_at = clock.unix_timestamp;
+    stored.revealed = filled_after == intent.amount_in;
+    
+    ctx.accounts.fee_ledger.record_collected(protocol_fee)?;
+    
+    if referral_fee > 0 {
+        if let Some(referrer_stats) = ctx.accounts.referrer_stats.as_mut() {
+            referrer_stats.total_earned = referrer_stats.total_earned
+                .checked_add(referral_fee)
+                .ok_or(ErrorCode::MathOverflow)?;
+            referrer_stats.referred_trades += 1;
+        }
+    }
     
     // ==================== ATOMIC SWAP EXECUTION ====================
     
     execute_atomic_swap(
         &ctx,  // Pass context
         &intent,
-        actual_out_amount,
+        swap_in,
         protocol_fee,
+        relayer_fee_in,
+        referral_fee,
+        delivered_out,
     )?;
     
+    if surplus_to_bounty > 0 {
+        pay_surplus_bounty(&ctx, surplus_to_bounty)?;
+    }
+    
+    if let IntentKind::Basket { legs } = &intent.kind {
+        deliver_basket_legs(&ctx, &intent.recipient, &legs[1..], &extra_leg_fills)?;
+    }
+    
     // ==================== SUCCESS EVENT ====================
     
     emit!(TradeExecuted {
         user: intent.user,
-        relayer: intent.relayer,
+        relayer: ctx.accounts.relayer.key(),
         token_in: intent.token_in,
         token_out: intent.token_out,
-        amount_in: intent.amount_in,
+        amount_in: swap_in,
         amount_out: actual_out_amount,
         protocol_fee,
-        relayer_fee: intent.relayer_fee,
+        fee_discount_bps,
+        relayer_fee,
+        relayer_fee_token: intent.relayer_fee_token,
+        surplus,
+        surplus_to_bounty,
+        referrer: intent.referrer,
+        referral_fee,
+        total_filled_in: filled_after,
+        unused_in,
         nonce: intent.nonce,
         timestamp: clock.unix_timestamp,
     });
@@ -218,8 +471,353 @@ pub fn handle_reveal(
 
 // ==================== HELPER FUNCTIONS ====================
 
-fn verify_ed25519_signature(
+/// Checks shared by every instruction that reveals a committed intent:
+/// lifecycle, intent parameters and the hash commitment
+pub(crate) fn verify_commitment(
+    stored: &SwapIntent,
+    intent: &TradeIntentData,
+    expected_hash: &[u8; 32],
+    user: &Pubkey,
+    now: i64,
+) -> Result<()> {
+    // Check if intent is already revealed
+    require!(!stored.revealed, ErrorCode::AlreadyRevealed);
+    
+    // Check expiry
+    require!(
+        stored.expiry > now as u64, 
+        ErrorCode::IntentExpired
+    );
+    
+    // Check nonce matches
+    require!(stored.nonce == intent.nonce, ErrorCode::NonceMismatch);
+    
+    // Verify user matches
+    require_keys_eq!(intent.user, *user, ErrorCode::InvalidSignature);
+    
+    // Validate amounts
+    require!(intent.amount_in > 0, ErrorCode::AmountTooSmall);
+    require!(intent.min_out > 0, ErrorCode::AmountTooSmall);
+    require!(
+        intent.quoted_out == 0 || intent.quoted_out >= intent.min_out,
+        ErrorCode::InvalidIntentParams
+    );
+    intent.validate_kind()?;
+    
+    // ==================== HASH VERIFICATION ====================
+    
+    // Reconstruct hash of TradeIntentData
+    let mut hasher = Sha256::new();
+    hasher.update(intent.try_to_vec()?);
+    let computed_hash = hasher.finalize();
+    
+    // Verify hash matches commitment
+    require!(
+        computed_hash[..] == expected_hash[..], 
+        ErrorCode::HashMismatch
+    );
+    
+    // Verify stored hash matches
+    require!(
+        stored.intent_hash == *expected_hash,
+        ErrorCode::HashMismatch
+    );
+    
+    Ok(())
+}
+
+/// Cap the relayer fee at the configured bps of the amount it is paid from:
+/// `amount_in` for token-in fees, the `min_out` floor for token-out fees
+pub(crate) fn validate_relayer_fee(intent: &TradeIntentData, config: &Config) -> Result<()> {
+    let base = match intent.relayer_fee_token {
+        FeeToken::TokenIn => intent.amount_in,
+        FeeToken::TokenOut => intent.min_out,
+    };
+    require!(
+        intent.relayer_fee <= mul_div(base, config.max_relayer_fee_bps as u64, 10000)?,
+        ErrorCode::RelayerFeeTooHigh
+    );
+    Ok(())
+}
+
+/// Bound the referral fee by the configured maximum; an intent without a
+/// referrer cannot carry one
+fn validate_referral(intent: &TradeIntentData, config: &Config) -> Result<()> {
+    if intent.referrer == Pubkey::default() {
+        require!(intent.referral_bps == 0, ErrorCode::InvalidIntentParams);
+    }
+    require!(
+        intent.referral_bps <= config.max_referral_bps,
+        ErrorCode::ReferralFeeTooHigh
+    );
+    Ok(())
+}
+
+/// Protocol fee in bps for a user: `config.fee_bps` less the discount their
+/// volume over the window ending on `day` earns, before the current trade.
+/// Returns `(fee_bps, discount_bps)`.
+pub(crate) fn tiered_fee_bps(config: &Config, user_stats: &UserStats, day: i64) -> Result<(u64, u16)> {
+    let discount_bps = config.fee_discount_bps(user_stats.volume_at(day));
+    let fee_bps = mul_div(config.fee_bps as u64, 10000 - discount_bps as u64, 10000)?;
+    Ok((fee_bps, discount_bps))
+}
+
+/// Enforce the user's daily notional limit and the per-mint epoch limits
+/// for a fill, then record it. Every fill path goes through here.
+///
+/// Volume counts the input at its oracle value. A fill of a mint without an
+/// oracle has no value comparable across mints, so it earns no tier volume
+/// and the daily limit requires an oracle price for the input mint.
+pub(crate) fn record_fill_volume(
+    config: &Config,
+    user_stats: &mut UserStats,
+    price_in: &MintPrice,
+    token_in_config: &mut MintConfig,
+    amount_in: u64,
+    token_out: Option<(&mut MintConfig, u64)>,
+    now: i64,
+) -> Result<()> {
+    let day = unix_day(now);
+    let notional = match price_in.price.as_ref() {
+        Some(price) => notional_value(amount_in, Some(price), price_in.decimals)?,
+        None => 0,
+    };
+
+    let max_daily = config.max_user_daily_notional;
+    if max_daily > 0 {
+        require!(price_in.price.is_some(), ErrorCode::NotionalOracleRequired);
+        let daily = user_stats.volume_on(day).saturating_add(notional);
+        require!(daily <= max_daily, ErrorCode::VolumeLimitExceeded);
+    }
+
+    // Per-mint epoch limits bound both what flows in and what flows out
+    token_in_config.record_volume(amount_in, now)?;
+    if let Some((token_out_config, amount_out)) = token_out {
+        token_out_config.record_volume(amount_out, now)?;
+    }
+
+    user_stats.record(day, notional)
+}
+
+/// Take a protocol fee of `fee_bps` from an exact-in fill, returning
+/// `(protocol_fee, swap_amount_after_fee)`
+pub(crate) fn quote_exact_in(amount_in: u64, fee_bps: u64) -> Result<(u64, u64)> {
+    // ==================== CALCULATE PROTOCOL FEE ====================
+    
+    let protocol_fee = amount_in
+        .checked_mul(fee_bps)
+        .ok_or(ErrorCode::MathOverflow)?
+        .checked_div(10000)
+        .ok_or(ErrorCode::MathOverflow)?;
+    
+    let swap_amount_after_fee = amount_in
+        .checked_sub(protocol_fee)
+        .ok_or(ErrorCode::MathOverflow)?;
+    
+    Ok((protocol_fee, swap_amount_after_fee))
+}
+
+/// Require the oracle pair price to have crossed a conditional intent's
+/// trigger. Both mints must have a fresh oracle configured.
+fn check_trigger_price(
+    input: &MintPrice,
+    output: &MintPrice,
+    trigger_price: u64,
+    direction: &TriggerDirection,
+) -> Result<()> {
+    let (Some(oracle_in), Some(oracle_out)) = (input.price, output.price) else {
+        return Err(ErrorCode::InvalidOracle.into());
+    };
+    
+    let price = unit_price(&oracle_in, input.decimals, &oracle_out, output.decimals)?;
+    
+    require!(
+        direction.is_crossed(price, trigger_price),
+        ErrorCode::TriggerNotReached
+    );
+    
+    Ok(())
+}
+
+/// Split `amount` across basket legs by weight and quote each leg as
+/// `(leg_in, leg_out)`, enforcing each leg's floor pro-rata to the fill
+/// size. The last leg absorbs rounding.
+fn quote_basket(
+    amount: u64,
+    legs: &[BasketLeg],
+    fill_amount: u64,
+    total_amount_in: u64,
+) -> Result<Vec<(u64, u64)>> {
+    let mut outputs = Vec::with_capacity(legs.len());
+    let mut allocated: u64 = 0;
+    
+    for (i, leg) in legs.iter().enumerate() {
+        let leg_in = if i == legs.len() - 1 {
+            amount.checked_sub(allocated).ok_or(ErrorCode::MathOverflow)?
+        } else {
+            mul_div(amount, leg.weight_bps as u64, 10000)?
+        };
+        allocated = allocated.checked_add(leg_in).ok_or(ErrorCode::MathOverflow)?;
+        
+        let leg_out = quote_hop(leg_in)?;
+        let leg_floor = mul_div_ceil(leg.min_out, fill_amount, total_amount_in)?;
+        require!(leg_out >= leg_floor, ErrorCode::SlippageExceeded);
+        outputs.push((leg_in, leg_out));
+    }
+    
+    Ok(outputs)
+}
+
+/// Run `amount` through each intermediate hop and the final leg into
+/// `token_out`, enforcing each hop's floor pro-rata to the fill size
+fn quote_route(
+    amount: u64,
+    route: &[RouteHop],
+    fill_amount: u64,
+    total_amount_in: u64,
+) -> Result<u64> {
+    let mut hop_amount = amount;
+    
+    for hop in route {
+        hop_amount = quote_hop(hop_amount)?;
+        
+        if hop.min_out > 0 {
+            let hop_floor = mul_div_ceil(hop.min_out, fill_amount, total_amount_in)?;
+            require!(hop_amount >= hop_floor, ErrorCode::HopSlippageExceeded);
+        }
+    }
+    
+    quote_hop(hop_amount)
+}
+
+/// Quote a single hop of a route
+pub(crate) fn quote_hop(amount_in: u64) -> Result<u64> {
+    // Simulate 1:1 swap for simplicity (in production, use Jupiter CPI)
+    Ok(amount_in)
+}
+
+/// Quote an exact-out fill, returning `(amount_in, protocol_fee)` where the
+/// protocol fee of `fee_bps` is grossed up on the input side
+fn quote_exact_out(amount_out: u64, fee_bps: u64) -> Result<(u64, u64)> {
+    // Simulate 1:1 swap for simplicity (in production, use Jupiter CPI)
+    let swap_amount_after_fee = amount_out;
+    
+    let amount_in = mul_div_ceil(
+        swap_amount_after_fee,
+        10000,
+        10000 - fee_bps,
+    )?;
+    let protocol_fee = amount_in
+        .checked_sub(swap_amount_after_fee)
+        .ok_or(ErrorCode::MathOverflow)?;
+    
+    Ok((amount_in, protocol_fee))
+}
+
+pub(crate) fn validate_relayer(
+    intent: &TradeIntentData,
+    relayer: &Pubkey,
+    registration: Option<&Account<RelayerRegistration>>,
+    commit_slot: u64,
+    slot: u64,
+) -> Result<()> {
+    // The named relayer can always fill its own intent
+    if !intent.is_open() && intent.relayer == *relayer {
+        return Ok(());
+    }
+
+    if !intent.is_open_at(commit_slot, slot) {
+        require!(intent.exclusivity_slots == 0, ErrorCode::ExclusivityWindowActive);
+        return Err(ErrorCode::InvalidSignature.into());
+    }
+
+    // Open intent or elapsed exclusivity window: any active registered relayer may fill
+    let registration = registration.ok_or(ErrorCode::RelayerNotRegistered)?;
+    require_keys_eq!(registration.relayer, *relayer, ErrorCode::RelayerNotRegistered);
+    require!(registration.active, ErrorCode::RelayerNotRegistered);
+
+    Ok(())
+}
+
+/// Size of the ed25519 instruction header: signature count and padding
+const ED25519_HEADER_SIZE: usize = 2;
+/// Size of one `Ed25519SignatureOffsets` entry
+const ED25519_OFFSETS_SIZE: usize = 14;
+
+/// Offsets of one signature in an ed25519 program instruction, as laid out
+/// by the precompile
+struct Ed25519SignatureOffsets {
+    signature_offset: u16,
+    signature_instruction_index: u16,
+    public_key_offset: u16,
+    public_key_instruction_index: u16,
+    message_data_offset: u16,
+    message_data_size: u16,
+    message_instruction_index: u16,
+}
+
+impl Ed25519SignatureOffsets {
+    fn parse(data: &[u8]) -> Self {
+        let field = |i: usize| u16::from_le_bytes([data[i * 2], data[i * 2 + 1]]);
+        Self {
+            signature_offset: field(0),
+            signature_instruction_index: field(1),
+            public_key_offset: field(2),
+            public_key_instruction_index: field(3),
+            message_data_offset: field(4),
+            message_data_size: field(5),
+            message_instruction_index: field(6),
+        }
+    }
+}
+
+/// Check that ed25519 program instruction data verifies exactly one
+/// signature of `message` by `pubkey`, with all of its data inside the
+/// instruction itself
+fn verify_ed25519_instruction_data(
+    data: &[u8],
+    signature: &[u8; 64],
+    pubkey: &[u8; 32],
+    message: &[u8],
+) -> Result<()> {
+    require!(
+        data.len() >= ED25519_HEADER_SIZE + ED25519_OFFSETS_SIZE,
+        ErrorCode::InvalidSignature
+    );
+    require!(data[0] == 1, ErrorCode::InvalidSignature);
+    
+    let offsets = Ed25519SignatureOffsets::parse(
+        &data[ED25519_HEADER_SIZE..ED25519_HEADER_SIZE + ED25519_OFFSETS_SIZE],
+    );
+    
+    // Data referenced from other instructions is not covered by this check
+    require!(
+        offsets.signature_instruction_index == u16::MAX
+            && offsets.public_key_instruction_index == u16::MAX
+            && offsets.message_instruction_index == u16::MAX,
+        ErrorCode::InvalidSignature
+    );
+    
+    let slice = |offset: u16, len: usize| -> Result<&[u8]> {
+        let start = offset as usize;
+        data.get(start..start + len).ok_or(ErrorCode::InvalidSignature.into())
+    };
+    
+    require!(slice(offsets.signature_offset, 64)? == signature, ErrorCode::InvalidSignature);
+    require!(slice(offsets.public_key_offset, 32)? == pubkey, ErrorCode::InvalidSignature);
+    require!(
+        slice(offsets.message_data_offset, offsets.message_data_size as usize)? == message,
+        ErrorCode::InvalidSignature
+    );
+    
+    Ok(())
+}
+
+/// Verify the ed25519 program instruction `ix_offset` positions before the
+/// current instruction signs `message_hash` with `user_pubkey`
+pub(crate) fn verify_ed25519_signature(
     instructions_sysvar: &AccountInfo,
+    ix_offset: u16,
     signature: &[u8; 64],
     user_pubkey: &[u8; 32],
     message_hash: &[u8; 32],
@@ -228,39 +826,20 @@ fn verify_ed25519_signature(
     let current_index = anchor_lang::solana_program::sysvar::instructions::load_current_index_checked(instructions_sysvar)?;
     
     // We expect an ed25519 instruction to be present before this instruction
-    if current_index == 0 {
+    if current_index < ix_offset {
         return Err(ErrorCode::InvalidSignature.into());
     }
     
-    // Load the previous instruction (should be ed25519 verification)
-    let ed25519_ix_index = current_index.saturating_sub(1);
+    // Load the preceding instruction (should be ed25519 verification)
+    let ed25519_ix_index = current_index - ix_offset;
     let ed25519_ix = load_instruction_at_checked(ed25519_ix_index as usize, instructions_sysvar)?;
     
     // Verify it's an ed25519 instruction
     require_keys_eq!(ed25519_ix.program_id, ed25519_program::id(), ErrorCode::InvalidSignature);
     
-    // Parse ed25519 instruction data
-    let ix_data = &ed25519_ix.data;
-    require!(ix_data.len() >= 1 + 64 + 32 + 2, ErrorCode::InvalidSignature);
-    
-    let num_signatures = ix_data[0];
-    require!(num_signatures == 1, ErrorCode::InvalidSignature);
-    
-    let ix_signature = &ix_data[1..65];
-    let ix_pubkey = &ix_data[65..97];
-    let message_len = u16::from_le_bytes([ix_data[97], ix_data[98]]) as usize;
-    
-    require!(ix_data.len() >= 99 + message_len, ErrorCode::InvalidSignature);
-    let ix_message = &ix_data[99..99 + message_len];
-    
-    // Verify signature matches
-    require!(ix_signature == signature, ErrorCode::InvalidSignature);
-    
-    // Verify pubkey matches
-    require!(ix_pubkey == user_pubkey, ErrorCode::InvalidSignature);
-    
-    // Verify message matches our hash
-    require!(ix_message == message_hash, ErrorCode::InvalidSignature);
+    // The precompile has verified the signature its offsets point at; make
+    // sure those are the user's signature, key and intent hash
+    verify_ed25519_instruction_data(&ed25519_ix.data, signature, user_pubkey, message_hash)?;
     
     msg!("ED25519 signature verified successfully for user: {:?}", user_pubkey);
     
@@ -270,13 +849,16 @@ fn verify_ed25519_signature(
 fn execute_atomic_swap(
     ctx: &Context<RevealTrade>,
     intent: &TradeIntentData,
-    actual_out_amount: u64,
+    swap_in: u64,
     protocol_fee: u64,
+    relayer_fee_in: u64,
+    referral_fee: u64,
+    delivered_out: u64,
 ) -> Result<()> {
     // ==================== ATOMIC TRANSACTION PATTERN ====================
     
     // 1. Transfer input tokens from user to relayer (minus protocol fee)
-    let user_to_relayer_amount = intent.amount_in
+    let user_to_relayer_amount = swap_in
         .checked_sub(protocol_fee)
         .ok_or(ErrorCode::MathOverflow)?;
     
@@ -305,7 +887,7 @@ fn execute_atomic_swap(
         ).map_err(|_| ErrorCode::SwapExecutionFailed)?;
     }
     
-    // 3. Transfer output tokens from relayer to user
+    // 3. Transfer output tokens, net of the relayer fee, from relayer to recipient
     let relayer_to_user_cpi = Transfer {
         from: ctx.accounts.relayer_token_out_account.to_account_info(),
         to: ctx.accounts.user_token_out_account.to_account_info(),
@@ -314,35 +896,190 @@ fn execute_atomic_swap(
     
     token::transfer(
         CpiContext::new(ctx.accounts.token_program.to_account_info(), relayer_to_user_cpi),
-        actual_out_amount,
+        delivered_out,
     ).map_err(|_| ErrorCode::SwapExecutionFailed)?;
     
-    // 4. Transfer relayer fee if specified
-    if intent.relayer_fee > 0 {
+    // 4. Transfer a token-in relayer fee from user to relayer
+    if relayer_fee_in > 0 {
         let relayer_fee_cpi = Transfer {
-            from: ctx.accounts.user_token_out_account.to_account_info(),
-            to: ctx.accounts.relayer_token_out_account.to_account_info(),
+            from: ctx.accounts.user_token_in_account.to_account_info(),
+            to: ctx.accounts.relayer_token_in_account.to_account_info(),
             authority: ctx.accounts.user.to_account_info(),
         };
         
         token::transfer(
             CpiContext::new(ctx.accounts.token_program.to_account_info(), relayer_fee_cpi),
-            intent.relayer_fee,
+            relayer_fee_in,
+        ).map_err(|_| ErrorCode::SwapExecutionFailed)?;
+    }
+    
+    // 5. Transfer the referral fee from user to referrer
+    if referral_fee > 0 {
+        let referrer_account = ctx.accounts.referrer_token_account
+            .as_ref()
+            .ok_or(ErrorCode::InvalidReferrer)?;
+        let referral_cpi = Transfer {
+            from: ctx.accounts.user_token_in_account.to_account_info(),
+            to: referrer_account.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+        
+        token::transfer(
+            CpiContext::new(ctx.accounts.token_program.to_account_info(), referral_cpi),
+            referral_fee,
         ).map_err(|_| ErrorCode::SwapExecutionFailed)?;
     }
     
     // Log successful atomic swap
     msg!("Atomic swap completed successfully:");
-    msg!("  Input: {} tokens of {}", intent.amount_in, intent.token_in);
-    msg!("  Output: {} tokens of {}", actual_out_amount, intent.token_out);
+    msg!("  Input: {} tokens of {}", swap_in, intent.token_in);
+    msg!("  Output: {} tokens of {}", delivered_out, intent.token_out);
     msg!("  Protocol fee: {}", protocol_fee);
-    msg!("  Relayer fee: {}", intent.relayer_fee);
+    msg!("  Relayer fee in: {}", relayer_fee_in);
+    msg!("  Referral fee: {}", referral_fee);
+    
+    Ok(())
+}
+
+/// Pay the bounty's share of surplus from the relayer's output inventory
+fn pay_surplus_bounty(ctx: &Context<RevealTrade>, amount: u64) -> Result<()> {
+    let bounty_account = ctx.accounts.bounty_account
+        .as_ref()
+        .ok_or(ErrorCode::BountyAccountRequired)?;
+    
+    let bounty_cpi = Transfer {
+        from: ctx.accounts.relayer_token_out_account.to_account_info(),
+        to: bounty_account.to_account_info(),
+        authority: ctx.accounts.relayer.to_account_info(),
+    };
+    
+    token::transfer(
+        CpiContext::new(ctx.accounts.token_program.to_account_info(), bounty_cpi),
+        amount,
+    ).map_err(|_| ErrorCode::SwapExecutionFailed)?;
+    
+    Ok(())
+}
+
+/// Accounts of each basket leg after the first in `reveal_trade`'s
+/// remaining accounts, in leg order: recipient token account, relayer token
+/// account, leg mint, leg mint config and leg oracle (any account when the
+/// leg mint has none configured)
+pub const BASKET_LEG_ACCOUNTS: usize = 5;
+
+/// Hold basket legs after the first to the checks the first gets through
+/// the fixed accounts: the oracle guard against the input price and the leg
+/// mint's epoch volume limit
+fn check_basket_legs(
+    remaining_accounts: &[AccountInfo],
+    legs: &[BasketLeg],
+    fills: &[(u64, u64)],
+    price_in: &MintPrice,
+    program_id: &Pubkey,
+    now: i64,
+) -> Result<()> {
+    require!(
+        remaining_accounts.len() >= legs.len() * BASKET_LEG_ACCOUNTS,
+        ErrorCode::InvalidTokenMint
+    );
+    
+    for ((leg, (leg_in, leg_out)), accounts) in legs
+        .iter()
+        .zip(fills.iter())
+        .zip(remaining_accounts.chunks(BASKET_LEG_ACCOUNTS))
+    {
+        let [_, _, mint, mint_config, oracle] = accounts else {
+            return Err(ErrorCode::InvalidTokenMint.into());
+        };
+        
+        require_keys_eq!(mint.key(), leg.token_out, ErrorCode::InvalidTokenMint);
+        require_keys_eq!(*mint.owner, token::ID, ErrorCode::InvalidTokenMint);
+        let decimals = Mint::try_deserialize(&mut &mint.try_borrow_data()?[..])
+            .map_err(|_| ErrorCode::InvalidTokenMint)?
+            .decimals;
+        
+        require_keys_eq!(*mint_config.owner, *program_id, ErrorCode::InvalidTokenMint);
+        require!(mint_config.is_writable, ErrorCode::InvalidTokenMint);
+        let mut config = MintConfig::try_deserialize(&mut &mint_config.try_borrow_data()?[..])
+            .map_err(|_| ErrorCode::InvalidTokenMint)?;
+        let expected = Pubkey::create_program_address(
+            &[MINT_CONFIG_PDA_SEED, leg.token_out.as_ref(), &[config.bump]],
+            program_id,
+        ).map_err(|_| ErrorCode::InvalidTokenMint)?;
+        require_keys_eq!(mint_config.key(), expected, ErrorCode::InvalidTokenMint);
+        
+        let price_out = MintPrice::load(&config, Some(oracle), decimals, now)?;
+        check_price_deviation(price_in, &price_out, *leg_in, *leg_out)?;
+        
+        config.record_volume(*leg_out, now)?;
+        config.try_serialize(&mut &mut mint_config.try_borrow_mut_data()?[..])?;
+    }
+    
+    Ok(())
+}
+
+/// Deliver basket legs after the first from the recipient and relayer
+/// accounts of each leg's `BASKET_LEG_ACCOUNTS` remaining accounts
+fn deliver_basket_legs<'info>(
+    ctx: &Context<'_, '_, '_, 'info, RevealTrade<'info>>,
+    recipient: &Pubkey,
+    legs: &[BasketLeg],
+    fills: &[(u64, u64)],
+) -> Result<()> {
+    require!(
+        ctx.remaining_accounts.len() >= legs.len() * BASKET_LEG_ACCOUNTS,
+        ErrorCode::InvalidTokenMint
+    );
+    
+    for (i, (leg, (_, amount_out))) in legs.iter().zip(fills.iter()).enumerate() {
+        let user_out = &ctx.remaining_accounts[i * BASKET_LEG_ACCOUNTS];
+        let relayer_out = &ctx.remaining_accounts[i * BASKET_LEG_ACCOUNTS + 1];
+        
+        validate_token_account(user_out, &leg.token_out, recipient)?;
+        validate_token_account(relayer_out, &leg.token_out, &ctx.accounts.relayer.key())?;
+        
+        let leg_cpi = Transfer {
+            from: relayer_out.clone(),
+            to: user_out.clone(),
+            authority: ctx.accounts.relayer.to_account_info(),
+        };
+        
+        token::transfer(
+            CpiContext::new(ctx.accounts.token_program.to_account_info(), leg_cpi),
+            *amount_out,
+        ).map_err(|_| ErrorCode::SwapExecutionFailed)?;
+        
+        emit!(BasketLegFilled {
+            user: ctx.accounts.user.key(),
+            token_out: leg.token_out,
+            amount_out: *amount_out,
+            nonce: ctx.accounts.swap_intent.nonce,
+        });
+    }
     
     Ok(())
 }
 
+/// Check an unchecked token account's mint and owner
+pub(crate) fn validate_token_account(account: &AccountInfo, mint: &Pubkey, owner: &Pubkey) -> Result<()> {
+    require_keys_eq!(*account.owner, token::ID, ErrorCode::InvalidTokenMint);
+    let token_account = TokenAccount::try_deserialize(&mut &account.try_borrow_data()?[..])
+        .map_err(|_| ErrorCode::InvalidTokenMint)?;
+    require_keys_eq!(token_account.mint, *mint, ErrorCode::InvalidTokenMint);
+    require_keys_eq!(token_account.owner, *owner, ErrorCode::InvalidTokenMint);
+    Ok(())
+}
+
 // ==================== EVENTS ====================
 
+#[event]
+pub struct BasketLegFilled {
+    pub user: Pubkey,
+    pub token_out: Pubkey,
+    pub amount_out: u64,
+    pub nonce: u64,
+}
+
 #[event]
 pub struct TradeExecuted {
     pub user: Pubkey,
@@ -352,7 +1089,153 @@ pub struct TradeExecuted {
     pub amount_in: u64,
     pub amount_out: u64,
     pub protocol_fee: u64,
+    /// Volume-tier discount applied to the protocol fee
+    pub fee_discount_bps: u16,
     pub relayer_fee: u64,
+    pub relayer_fee_token: FeeToken,
+    /// Output above the pro-rata `quoted_out`
+    pub surplus: u64,
+    /// Portion of `surplus` paid to the MEV bounty pool
+    pub surplus_to_bounty: u64,
+    pub referrer: Pubkey,
+    pub referral_fee: u64,
+    pub total_filled_in: u64,
+    pub unused_in: u64,
     pub nonce: u64,
     pub timestamp: i64,
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{config, mint_config, mint_price, user_stats};
+
+    /// Ed25519 instruction data as built by the SDK: header, offsets, then
+    /// public key, signature and message
+    fn ed25519_data(pubkey: &[u8; 32], signature: &[u8; 64], message: &[u8]) -> Vec<u8> {
+        let public_key_offset = (ED25519_HEADER_SIZE + ED25519_OFFSETS_SIZE) as u16;
+        let signature_offset = public_key_offset + 32;
+        let message_data_offset = signature_offset + 64;
+
+        let mut data = vec![1, 0];
+        for field in [
+            signature_offset,
+            u16::MAX,
+            public_key_offset,
+            u16::MAX,
+            message_data_offset,
+            message.len() as u16,
+            u16::MAX,
+        ] {
+            data.extend_from_slice(&field.to_le_bytes());
+        }
+        data.extend_from_slice(pubkey);
+        data.extend_from_slice(signature);
+        data.extend_from_slice(message);
+        data
+    }
+
+    #[test]
+    fn ed25519_data_matches_signed_intent() {
+        let (pubkey, signature, hash) = ([1u8; 32], [2u8; 64], [3u8; 32]);
+        let data = ed25519_data(&pubkey, &signature, &hash);
+
+        assert!(verify_ed25519_instruction_data(&data, &signature, &pubkey, &hash).is_ok());
+        assert!(verify_ed25519_instruction_data(&data, &signature, &[9u8; 32], &hash).is_err());
+        assert!(verify_ed25519_instruction_data(&data, &[9u8; 64], &pubkey, &hash).is_err());
+        assert!(verify_ed25519_instruction_data(&data, &signature, &pubkey, &[9u8; 32]).is_err());
+    }
+
+    #[test]
+    fn ed25519_data_must_be_self_contained() {
+        let (pubkey, signature, hash) = ([1u8; 32], [2u8; 64], [3u8; 32]);
+
+        // Message taken from another instruction
+        let mut data = ed25519_data(&pubkey, &signature, &hash);
+        data[14..16].copy_from_slice(&0u16.to_le_bytes());
+        assert!(verify_ed25519_instruction_data(&data, &signature, &pubkey, &hash).is_err());
+
+        // Offsets pointing past the end of the data
+        let mut data = ed25519_data(&pubkey, &signature, &hash);
+        data[10..12].copy_from_slice(&200u16.to_le_bytes());
+        assert!(verify_ed25519_instruction_data(&data, &signature, &pubkey, &hash).is_err());
+
+        // More than one signature
+        let mut data = ed25519_data(&pubkey, &signature, &hash);
+        data[0] = 2;
+        assert!(verify_ed25519_instruction_data(&data, &signature, &pubkey, &hash).is_err());
+    }
+
+    #[test]
+    fn exact_out_grosses_up_fee_on_input() {
+        assert_eq!(quote_exact_out(1_000, 30).unwrap(), (1_004, 4));
+        assert_eq!(quote_exact_out(1_000, 0).unwrap(), (1_000, 0));
+
+        // The grossed-up input always nets at least the requested output
+        for amount_out in [1, 333, 9_970, 1_000_000] {
+            let (amount_in, _) = quote_exact_out(amount_out, 30).unwrap();
+            let (_, net) = quote_exact_in(amount_in, 30).unwrap();
+            assert!(net >= amount_out);
+        }
+    }
+
+    /// One dollar per token of 6 decimals
+    fn dollar() -> MintPrice {
+        mint_price(100_000_000, 6, 0)
+    }
+
+    #[test]
+    fn fill_volume_enforces_daily_notional() {
+        let config = config(1_500_000);
+        let (mut stats, mut token_in, mut token_out) = (user_stats(), mint_config(0), mint_config(0));
+
+        record_fill_volume(&config, &mut stats, &dollar(), &mut token_in, 1_000_000, Some((&mut token_out, 1)), 0)
+            .unwrap();
+        assert_eq!(stats.volume_on(0), 1_000_000);
+        assert!(
+            record_fill_volume(&config, &mut stats, &dollar(), &mut token_in, 1_000_000, None, 60).is_err()
+        );
+
+        // The limit resets on the next day
+        record_fill_volume(&config, &mut stats, &dollar(), &mut token_in, 1_000_000, None, 86400).unwrap();
+        assert_eq!(stats.trade_count, 2);
+    }
+
+    #[test]
+    fn fill_volume_needs_oracle_under_daily_notional() {
+        let unpriced = MintPrice { price: None, decimals: 6, max_deviation_bps: 0 };
+        let (mut stats, mut token_in) = (user_stats(), mint_config(0));
+
+        assert!(
+            record_fill_volume(&config(1_500_000), &mut stats, &unpriced, &mut token_in, 1, None, 0).is_err()
+        );
+
+        // Unpriced fills count as trades but earn no tier volume
+        record_fill_volume(&config(0), &mut stats, &unpriced, &mut token_in, 1_000_000_000, None, 0).unwrap();
+        assert_eq!((stats.volume_on(0), stats.trade_count), (0, 1));
+    }
+
+    #[test]
+    fn tier_discount_applies_to_configured_fee() {
+        let mut config = config(0);
+        config.fee_bps = 30;
+        config.fee_tiers = vec![FeeTier { min_volume: 1_000, discount_bps: 5000 }];
+        let mut stats = user_stats();
+
+        assert_eq!(tiered_fee_bps(&config, &stats, 0).unwrap(), (30, 0));
+        stats.record(0, 1_000).unwrap();
+        assert_eq!(tiered_fee_bps(&config, &stats, 0).unwrap(), (15, 5000));
+    }
+
+    #[test]
+    fn fill_volume_enforces_both_mint_limits() {
+        let config = config(0);
+        let (mut stats, mut token_in, mut token_out) = (user_stats(), mint_config(100), mint_config(50));
+
+        record_fill_volume(&config, &mut stats, &dollar(), &mut token_in, 100, Some((&mut token_out, 50)), 0)
+            .unwrap();
+        assert!(
+            record_fill_volume(&config, &mut stats, &dollar(), &mut token_in, 0, Some((&mut token_out, 1)), 0)
+                .is_err()
+        );
+    }
+}
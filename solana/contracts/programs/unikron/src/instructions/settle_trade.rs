@@ -1,12 +1,22 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer, Mint};
-use crate::{constants::*, errors::ErrorCode};
+use crate::{constants::*, errors::ErrorCode, math::mul_div, state::{Config, FeeLedger, StakePool, RewardPool}};
 
+/// Distributes a chosen amount of a mint's fees. Restricted to the admin;
+/// anyone else distributes fees through `sweep_fees`, which is rate limited
+/// and always takes the whole balance.
 #[derive(Accounts)]
 pub struct SettleFee<'info> {
-    /// CHECK: PDA authority for fee collection that holds the collected fees
     #[account(
-        seeds = [b"fee_authority"],
+        seeds = [CONFIG_PDA_SEED],
+        bump = config.bump,
+        constraint = config.admin == caller.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Fee collection authority PDA of `token_mint`, owner of the collected fees
+    #[account(
+        seeds = [FEE_AUTHORITY_PDA_SEED, token_mint.key().as_ref()],
         bump
     )]
     pub fee_collection_authority: AccountInfo<'info>,
@@ -14,10 +24,33 @@ pub struct SettleFee<'info> {
     /// The token account that holds collected protocol fees
     #[account(
         mut,
+        seeds = [FEE_COLLECTION_PDA_SEED, token_mint.key().as_ref()],
+        bump,
         constraint = source_fee_account.owner == fee_collection_authority.key()
     )]
     pub source_fee_account: Account<'info, TokenAccount>,
 
+    /// Fee totals of `token_mint`
+    #[account(
+        mut,
+        seeds = [FEE_LEDGER_PDA_SEED, token_mint.key().as_ref()],
+        bump = fee_ledger.bump
+    )]
+    pub fee_ledger: Account<'info, FeeLedger>,
+
+    #[account(seeds = [STAKE_POOL_PDA_SEED], bump = stake_pool.bump)]
+    pub stake_pool: Option<Box<Account<'info, StakePool>>>,
+
+    /// Staker reward accumulator of `token_mint`, created by
+    /// `initialize_reward_pool`. Omitted while the mint has none, holding
+    /// the stakers' share in the fee ledger instead.
+    #[account(
+        mut,
+        seeds = [REWARD_POOL_PDA_SEED, token_mint.key().as_ref()],
+        bump = reward_pool.bump
+    )]
+    pub reward_pool: Option<Box<Account<'info, RewardPool>>>,
+
     /// Token account for liquidity stakers (50% of fees)
     #[account(
         mut,
@@ -48,7 +81,7 @@ pub struct SettleFee<'info> {
     /// The token mint for the fees being distributed
     pub token_mint: Account<'info, Mint>,
 
-    /// Relayer or authorized caller who triggers fee distribution
+    /// Protocol admin
     #[account(mut)]
     pub caller: Signer<'info>,
 
@@ -69,30 +102,16 @@ pub fn handler(ctx: Context<SettleFee>, fee_amount: u64) -> Result<()> {
     // ==================== FEE CALCULATION ====================
     
     // Calculate distribution amounts
-    let treasury_fee = fee_amount
-        .checked_mul(30)
-        .ok_or(ErrorCode::MathOverflow)?
-        .checked_div(100)
-        .ok_or(ErrorCode::MathOverflow)?;
-    
-    let stakers_fee = fee_amount
-        .checked_mul(50)
-        .ok_or(ErrorCode::MathOverflow)?
-        .checked_div(100)
-        .ok_or(ErrorCode::MathOverflow)?;
-    
-    let bounty_fee = fee_amount
-        .checked_sub(treasury_fee)
-        .ok_or(ErrorCode::MathOverflow)?
-        .checked_sub(stakers_fee)
-        .ok_or(ErrorCode::MathOverflow)?;
+    let (stakers_fee, treasury_fee, bounty_fee) = split_fee(fee_amount)?;
     
     // ==================== FEE DISTRIBUTION ====================
     
     // Get PDA seeds and bumps for signing
     let fee_authority_bump = ctx.bumps.fee_collection_authority;
+    let token_mint_key = ctx.accounts.token_mint.key();
     let authority_seeds = &[
-        b"fee_authority".as_ref(),
+        FEE_AUTHORITY_PDA_SEED,
+        token_mint_key.as_ref(),
         &[fee_authority_bump],
     ];
     let signer_seeds = &[&authority_seeds[..]];
@@ -145,6 +164,16 @@ pub fn handler(ctx: Context<SettleFee>, fee_amount: u64) -> Result<()> {
         bounty_fee,
     )?;
     
+    ctx.accounts.fee_ledger.record_distributed(fee_amount)?;
+    accrue_staker_rewards(
+        &mut ctx.accounts.fee_ledger,
+        ctx.accounts.reward_pool.as_deref_mut().map(|pool| &mut **pool),
+        ctx.accounts.stake_pool.as_deref().map(|pool| &**pool),
+        stakers_fee,
+        clock.unix_timestamp,
+        ctx.accounts.config.reward_stream_secs,
+    )?;
+    
     // ==================== EVENT EMISSION ====================
     
     emit!(FeeDistributed {
@@ -170,23 +199,402 @@ pub fn handler(ctx: Context<SettleFee>, fee_amount: u64) -> Result<()> {
     Ok(())
 }
 
+/// Split a fee amount into `(stakers_fee, treasury_fee, bounty_fee)` as
+/// 50% / 30% / 20%, with rounding dust going to the bounty pool
+fn split_fee(fee_amount: u64) -> Result<(u64, u64, u64)> {
+    let treasury_fee = fee_amount
+        .checked_mul(30)
+        .ok_or(ErrorCode::MathOverflow)?
+        .checked_div(100)
+        .ok_or(ErrorCode::MathOverflow)?;
+    
+    let stakers_fee = fee_amount
+        .checked_mul(50)
+        .ok_or(ErrorCode::MathOverflow)?
+        .checked_div(100)
+        .ok_or(ErrorCode::MathOverflow)?;
+    
+    let bounty_fee = fee_amount
+        .checked_sub(treasury_fee)
+        .ok_or(ErrorCode::MathOverflow)?
+        .checked_sub(stakers_fee)
+        .ok_or(ErrorCode::MathOverflow)?;
+    
+    Ok((stakers_fee, treasury_fee, bounty_fee))
+}
+
+/// Queue `stakers_fee`, with anything the ledger held back, into the mint's
+/// reward pool, or hold it in the ledger while the mint has no reward pool
+fn accrue_staker_rewards(
+    ledger: &mut FeeLedger,
+    reward_pool: Option<&mut RewardPool>,
+    stake_pool: Option<&StakePool>,
+    stakers_fee: u64,
+    now: i64,
+    stream_secs: i64,
+) -> Result<()> {
+    let amount = ledger.pending_staker_rewards
+        .checked_add(stakers_fee)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    match (reward_pool, stake_pool) {
+        (Some(reward_pool), Some(stake_pool)) => {
+            reward_pool.accrue(amount, stake_pool.total_staked, now, stream_secs)?;
+            ledger.pending_staker_rewards = 0;
+        }
+        (Some(_), None) => return err!(ErrorCode::InvalidRewardPool),
+        (None, _) => ledger.pending_staker_rewards = amount,
+    }
+
+    Ok(())
+}
+
+/// Split a swept balance into `(keeper_reward, stakers_fee, treasury_fee,
+/// bounty_fee)`, paying the keeper first and splitting the rest like `split_fee`
+fn split_sweep(balance: u64, keeper_reward_bps: u16) -> Result<(u64, u64, u64, u64)> {
+    let keeper_reward = mul_div(balance, keeper_reward_bps as u64, 10000)?;
+    let (stakers_fee, treasury_fee, bounty_fee) = split_fee(balance - keeper_reward)?;
+    Ok((keeper_reward, stakers_fee, treasury_fee, bounty_fee))
+}
+
+// ==================== FEE SWEEP ====================
+
+/// Permissionless crank that distributes the whole balance of a mint's fee
+/// collection account, paying the caller `keeper_reward_bps` of it
+#[derive(Accounts)]
+pub struct SweepFees<'info> {
+    #[account(seeds = [CONFIG_PDA_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Fee collection authority PDA of `token_mint`, owner of the collected fees
+    #[account(
+        seeds = [FEE_AUTHORITY_PDA_SEED, token_mint.key().as_ref()],
+        bump
+    )]
+    pub fee_collection_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [FEE_COLLECTION_PDA_SEED, token_mint.key().as_ref()],
+        bump,
+        constraint = source_fee_account.owner == fee_collection_authority.key()
+    )]
+    pub source_fee_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [FEE_LEDGER_PDA_SEED, token_mint.key().as_ref()],
+        bump = fee_ledger.bump
+    )]
+    pub fee_ledger: Account<'info, FeeLedger>,
+
+    #[account(seeds = [STAKE_POOL_PDA_SEED], bump = stake_pool.bump)]
+    pub stake_pool: Option<Box<Account<'info, StakePool>>>,
+
+    /// Staker reward accumulator of `token_mint`, created by
+    /// `initialize_reward_pool`. Omitted while the mint has none, holding
+    /// the stakers' share in the fee ledger instead.
+    #[account(
+        mut,
+        seeds = [REWARD_POOL_PDA_SEED, token_mint.key().as_ref()],
+        bump = reward_pool.bump
+    )]
+    pub reward_pool: Option<Box<Account<'info, RewardPool>>>,
+
+    #[account(
+        mut,
+        seeds = [LIQUIDITY_STAKER_PDA_SEED, token_mint.key().as_ref()],
+        bump
+    )]
+    pub liquidity_staker_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_PDA_SEED, token_mint.key().as_ref()],
+        bump
+    )]
+    pub treasury_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [BOUNTY_PDA_SEED, token_mint.key().as_ref()],
+        bump
+    )]
+    pub bounty_account: Account<'info, TokenAccount>,
+
+    /// Caller's account receiving the keeper reward
+    #[account(
+        mut,
+        constraint = caller_token_account.mint == token_mint.key(),
+        constraint = caller_token_account.owner == caller.key()
+    )]
+    pub caller_token_account: Account<'info, TokenAccount>,
+
+    pub token_mint: Account<'info, Mint>,
+
+    pub caller: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn sweep_fees(ctx: Context<SweepFees>) -> Result<()> {
+    let clock = Clock::get()?;
+    let config = &ctx.accounts.config;
+    let ledger = &ctx.accounts.fee_ledger;
+    
+    // ==================== INPUT VALIDATION ====================
+    
+    require!(
+        ledger.sweep_due(clock.unix_timestamp, config.min_sweep_interval_secs),
+        ErrorCode::SweepTooSoon
+    );
+    
+    let balance = ctx.accounts.source_fee_account.amount;
+    require!(balance > 0, ErrorCode::AmountTooSmall);
+    
+    // ==================== FEE CALCULATION ====================
+    
+    let (keeper_reward, stakers_fee, treasury_fee, bounty_fee) = split_sweep(balance, config.keeper_reward_bps)?;
+    
+    // ==================== FEE DISTRIBUTION ====================
+    
+    let token_mint_key = ctx.accounts.token_mint.key();
+    let authority_seeds = &[
+        FEE_AUTHORITY_PDA_SEED,
+        token_mint_key.as_ref(),
+        &[ctx.bumps.fee_collection_authority],
+    ];
+    let signer_seeds = &[&authority_seeds[..]];
+    
+    for (to, amount) in [
+        (ctx.accounts.liquidity_staker_account.to_account_info(), stakers_fee),
+        (ctx.accounts.treasury_account.to_account_info(), treasury_fee),
+        (ctx.accounts.bounty_account.to_account_info(), bounty_fee),
+        (ctx.accounts.caller_token_account.to_account_info(), keeper_reward),
+    ] {
+        transfer_from_fee_account(
+            &ctx.accounts.token_program,
+            &ctx.accounts.source_fee_account.to_account_info(),
+            &ctx.accounts.fee_collection_authority,
+            signer_seeds,
+            to,
+            amount,
+        )?;
+    }
+    
+    let ledger = &mut ctx.accounts.fee_ledger;
+    ledger.record_distributed(balance)?;
+    ledger.last_sweep_at = clock.unix_timestamp;
+    accrue_staker_rewards(
+        ledger,
+        ctx.accounts.reward_pool.as_deref_mut().map(|pool| &mut **pool),
+        ctx.accounts.stake_pool.as_deref().map(|pool| &**pool),
+        stakers_fee,
+        clock.unix_timestamp,
+        config.reward_stream_secs,
+    )?;
+    
+    // ==================== EVENT EMISSION ====================
+    
+    emit!(FeesSwept {
+        token_mint: token_mint_key,
+        total_fee: balance,
+        liquidity_stakers_fee: stakers_fee,
+        treasury_fee,
+        mev_bounty_fee: bounty_fee,
+        keeper_reward,
+        caller: ctx.accounts.caller.key(),
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+// ==================== MULTI-MINT SETTLEMENT ====================
+
+/// Accounts of one mint in `settle_many`'s remaining accounts, in order:
+/// fee collection authority, fee collection account, fee ledger, reward
+/// pool (the program id while the mint has none), liquidity stakers
+/// account, treasury account, bounty account and the token mint
+pub const SETTLE_GROUP_LEN: usize = 8;
+
+/// Settles several mints at once. Each mint contributes one group of
+/// `SETTLE_GROUP_LEN` remaining accounts, validated like `SettleFee`.
+/// Restricted to the admin like `SettleFee`.
+#[derive(Accounts)]
+pub struct SettleMany<'info> {
+    #[account(
+        seeds = [CONFIG_PDA_SEED],
+        bump = config.bump,
+        constraint = config.admin == caller.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Needed when any group's mint has a reward pool
+    #[account(seeds = [STAKE_POOL_PDA_SEED], bump = stake_pool.bump)]
+    pub stake_pool: Option<Box<Account<'info, StakePool>>>,
+
+    /// Protocol admin
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn settle_many<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SettleMany<'info>>,
+    fee_amounts: Vec<u64>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    require!(
+        !fee_amounts.is_empty()
+            && ctx.remaining_accounts.len() == fee_amounts.len() * SETTLE_GROUP_LEN,
+        ErrorCode::InvalidSettlementAccounts
+    );
+    
+    for (group, &fee_amount) in ctx.remaining_accounts.chunks(SETTLE_GROUP_LEN).zip(fee_amounts.iter()) {
+        let [authority, source, ledger, reward_pool, stakers, treasury, bounty, mint] = group else {
+            return Err(ErrorCode::InvalidSettlementAccounts.into());
+        };
+        let mint_key = mint.key();
+        
+        // ==================== ACCOUNT VALIDATION ====================
+        
+        Account::<Mint>::try_from(mint).map_err(|_| ErrorCode::InvalidSettlementAccounts)?;
+        
+        // The ledger holds the bumps of the mint's other fee PDAs
+        let mut fee_ledger = Account::<FeeLedger>::try_from(ledger)
+            .map_err(|_| ErrorCode::InvalidSettlementAccounts)?;
+        require_keys_eq!(fee_ledger.mint, mint_key, ErrorCode::InvalidSettlementAccounts);
+        require_fee_pda(ledger, FEE_LEDGER_PDA_SEED, &mint_key, fee_ledger.bump, ctx.program_id)?;
+        require_fee_pda(authority, FEE_AUTHORITY_PDA_SEED, &mint_key, fee_ledger.authority_bump, ctx.program_id)?;
+        require_fee_pda(source, FEE_COLLECTION_PDA_SEED, &mint_key, fee_ledger.collection_bump, ctx.program_id)?;
+        require_fee_pda(stakers, LIQUIDITY_STAKER_PDA_SEED, &mint_key, fee_ledger.stakers_bump, ctx.program_id)?;
+        require_fee_pda(treasury, TREASURY_PDA_SEED, &mint_key, fee_ledger.treasury_bump, ctx.program_id)?;
+        require_fee_pda(bounty, BOUNTY_PDA_SEED, &mint_key, fee_ledger.bounty_bump, ctx.program_id)?;
+        
+        let mut pool = if reward_pool.key() == *ctx.program_id {
+            None
+        } else {
+            let pool = Account::<RewardPool>::try_from(reward_pool)
+                .map_err(|_| ErrorCode::InvalidSettlementAccounts)?;
+            require_fee_pda(reward_pool, REWARD_POOL_PDA_SEED, &mint_key, pool.bump, ctx.program_id)?;
+            Some(pool)
+        };
+        
+        let source_account = Account::<TokenAccount>::try_from(source)
+            .map_err(|_| ErrorCode::InvalidSettlementAccounts)?;
+        require_keys_eq!(source_account.mint, mint_key, ErrorCode::InvalidSettlementAccounts);
+        require_keys_eq!(source_account.owner, authority.key(), ErrorCode::InvalidSettlementAccounts);
+        
+        // ==================== INPUT VALIDATION ====================
+        
+        require!(fee_amount > 0, ErrorCode::AmountTooSmall);
+        require!(source_account.amount >= fee_amount, ErrorCode::InsufficientBalance);
+        
+        // ==================== FEE DISTRIBUTION ====================
+        
+        let (stakers_fee, treasury_fee, bounty_fee) = split_fee(fee_amount)?;
+        
+        let authority_seeds = &[
+            FEE_AUTHORITY_PDA_SEED,
+            mint_key.as_ref(),
+            &[fee_ledger.authority_bump],
+        ];
+        let signer_seeds = &[&authority_seeds[..]];
+        
+        for (to, amount) in [(stakers, stakers_fee), (treasury, treasury_fee), (bounty, bounty_fee)] {
+            transfer_from_fee_account(
+                &ctx.accounts.token_program,
+                source,
+                authority,
+                signer_seeds,
+                to.clone(),
+                amount,
+            )?;
+        }
+        
+        fee_ledger.record_distributed(fee_amount)?;
+        accrue_staker_rewards(
+            &mut fee_ledger,
+            pool.as_deref_mut(),
+            ctx.accounts.stake_pool.as_deref().map(|pool| &**pool),
+            stakers_fee,
+            clock.unix_timestamp,
+            ctx.accounts.config.reward_stream_secs,
+        )?;
+        fee_ledger.exit(ctx.program_id)?;
+        if let Some(pool) = pool {
+            pool.exit(ctx.program_id)?;
+        }
+        
+        emit!(FeeDistributed {
+            token_mint: mint_key,
+            total_fee: fee_amount,
+            liquidity_stakers_fee: stakers_fee,
+            treasury_fee,
+            mev_bounty_fee: bounty_fee,
+            caller: ctx.accounts.caller.key(),
+            timestamp: clock.unix_timestamp,
+        });
+    }
+    
+    msg!("Fee distribution completed for {} mints", fee_amounts.len());
+    
+    Ok(())
+}
+
+/// Check that `account` is this program's `[seed, mint]` PDA with the
+/// stored `bump`
+fn require_fee_pda(account: &AccountInfo, seed: &[u8], mint: &Pubkey, bump: u8, program_id: &Pubkey) -> Result<()> {
+    let expected = Pubkey::create_program_address(&[seed, mint.as_ref(), &[bump]], program_id)
+        .map_err(|_| ErrorCode::InvalidSettlementAccounts)?;
+    require_keys_eq!(account.key(), expected, ErrorCode::InvalidSettlementAccounts);
+    Ok(())
+}
+
+/// Transfer `amount` out of a fee collection account under its authority PDA
+fn transfer_from_fee_account<'info>(
+    token_program: &Program<'info, Token>,
+    source: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    to: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    
+    let cpi = Transfer {
+        from: source.clone(),
+        to,
+        authority: authority.clone(),
+    };
+    token::transfer(
+        CpiContext::new_with_signer(token_program.to_account_info(), cpi, signer_seeds),
+        amount,
+    )
+}
+
 // ==================== INITIALIZATION FUNCTIONS ====================
 
+/// Creates the fee accounts of one mint. Idempotent, so it can be called
+/// again for a mint whose accounts already exist. Staker rewards are
+/// attached separately by `initialize_reward_pool`.
 #[derive(Accounts)]
-pub struct InitializeFeeAccounts<'info> {
-    /// CHECK: PDA authority for fee collection
+pub struct InitializeMintFees<'info> {
+    /// CHECK: Fee collection authority PDA of `token_mint`; signs only, never created
     #[account(
-        init,
-        payer = payer,
-        space = 0,
-        seeds = [b"fee_authority"],
+        seeds = [FEE_AUTHORITY_PDA_SEED, token_mint.key().as_ref()],
         bump
     )]
     pub fee_collection_authority: AccountInfo<'info>,
 
     /// Initialize liquidity stakers fee account
     #[account(
-        init,
+        init_if_needed,
         payer = payer,
         token::mint = token_mint,
         token::authority = fee_collection_authority,
@@ -197,7 +605,7 @@ pub struct InitializeFeeAccounts<'info> {
 
     /// Initialize treasury fee account
     #[account(
-        init,
+        init_if_needed,
         payer = payer,
         token::mint = token_mint,
         token::authority = fee_collection_authority,
@@ -208,7 +616,7 @@ pub struct InitializeFeeAccounts<'info> {
 
     /// Initialize MEV bounty fee account
     #[account(
-        init,
+        init_if_needed,
         payer = payer,
         token::mint = token_mint,
         token::authority = fee_collection_authority,
@@ -219,15 +627,25 @@ pub struct InitializeFeeAccounts<'info> {
 
     /// Initialize fee collection account
     #[account(
-        init,
+        init_if_needed,
         payer = payer,
         token::mint = token_mint,
         token::authority = fee_collection_authority,
-        seeds = [b"fee_collection", token_mint.key().as_ref()],
+        seeds = [FEE_COLLECTION_PDA_SEED, token_mint.key().as_ref()],
         bump
     )]
     pub fee_collection_account: Account<'info, TokenAccount>,
 
+    /// Initialize fee ledger
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + FeeLedger::SIZE,
+        seeds = [FEE_LEDGER_PDA_SEED, token_mint.key().as_ref()],
+        bump
+    )]
+    pub fee_ledger: Account<'info, FeeLedger>,
+
     /// The token mint for the fee accounts
     pub token_mint: Account<'info, Mint>,
 
@@ -240,13 +658,23 @@ pub struct InitializeFeeAccounts<'info> {
     pub rent: Sysvar<'info, Rent>,
 }
 
-pub fn initialize_fee_accounts(ctx: Context<InitializeFeeAccounts>) -> Result<()> {
+pub fn initialize_mint_fees(ctx: Context<InitializeMintFees>) -> Result<()> {
+    let fee_ledger = &mut ctx.accounts.fee_ledger;
+    fee_ledger.mint = ctx.accounts.token_mint.key();
+    fee_ledger.authority_bump = ctx.bumps.fee_collection_authority;
+    fee_ledger.collection_bump = ctx.bumps.fee_collection_account;
+    fee_ledger.stakers_bump = ctx.bumps.liquidity_staker_account;
+    fee_ledger.treasury_bump = ctx.bumps.treasury_account;
+    fee_ledger.bounty_bump = ctx.bumps.bounty_account;
+    fee_ledger.bump = ctx.bumps.fee_ledger;
+    
     msg!("Fee accounts initialized for mint: {}", ctx.accounts.token_mint.key());
     msg!("  Fee collection authority: {}", ctx.accounts.fee_collection_authority.key());
     msg!("  Liquidity stakers account: {}", ctx.accounts.liquidity_staker_account.key());
     msg!("  Treasury account: {}", ctx.accounts.treasury_account.key());
     msg!("  MEV bounty account: {}", ctx.accounts.bounty_account.key());
     msg!("  Fee collection account: {}", ctx.accounts.fee_collection_account.key());
+    msg!("  Fee ledger: {}", ctx.accounts.fee_ledger.key());
     
     emit!(FeeAccountsInitialized {
         token_mint: ctx.accounts.token_mint.key(),
@@ -274,6 +702,18 @@ pub struct FeeDistributed {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct FeesSwept {
+    pub token_mint: Pubkey,
+    pub total_fee: u64,
+    pub liquidity_stakers_fee: u64,
+    pub treasury_fee: u64,
+    pub mev_bounty_fee: u64,
+    pub keeper_reward: u64,
+    pub caller: Pubkey,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct FeeAccountsInitialized {
     pub token_mint: Pubkey,
@@ -283,4 +723,47 @@ pub struct FeeAccountsInitialized {
     pub mev_bounty: Pubkey,
     pub fee_collection: Pubkey,
     pub timestamp: i64,
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sweep_pays_keeper_before_splitting() {
+        let (keeper_reward, stakers_fee, treasury_fee, bounty_fee) = split_sweep(10_000, 100).unwrap();
+
+        assert_eq!(keeper_reward, 100);
+        assert_eq!((stakers_fee, treasury_fee, bounty_fee), (4950, 2970, 1980));
+        assert_eq!(keeper_reward + stakers_fee + treasury_fee + bounty_fee, 10_000);
+    }
+
+    #[test]
+    fn split_dust_goes_to_bounty_pool() {
+        let (stakers_fee, treasury_fee, bounty_fee) = split_fee(7).unwrap();
+
+        assert_eq!((stakers_fee, treasury_fee, bounty_fee), (3, 2, 2));
+        assert_eq!(split_sweep(7, 0).unwrap(), (0, 3, 2, 2));
+    }
+
+    #[test]
+    fn sweeps_are_spaced_by_interval() {
+        let mut ledger = FeeLedger {
+            mint: Pubkey::default(),
+            total_collected: 0,
+            total_distributed: 0,
+            last_sweep_at: 0,
+            pending_staker_rewards: 0,
+            authority_bump: 0,
+            collection_bump: 0,
+            stakers_bump: 0,
+            treasury_bump: 0,
+            bounty_bump: 0,
+            bump: 0,
+        };
+        assert!(ledger.sweep_due(1_000, 3600));
+
+        ledger.last_sweep_at = 1_000;
+        assert!(!ledger.sweep_due(4_599, 3600));
+        assert!(ledger.sweep_due(4_600, 3600));
+    }
+}
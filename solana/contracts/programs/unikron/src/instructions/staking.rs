@@ -0,0 +1,402 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer, Mint};
+use crate::{state::*, errors::ErrorCode, constants::*};
+
+// ==================== STAKE POOL ====================
+
+#[derive(Accounts)]
+pub struct InitializeStakePool<'info> {
+    #[account(
+        seeds = [CONFIG_PDA_SEED],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + StakePool::SIZE,
+        seeds = [STAKE_POOL_PDA_SEED],
+        bump
+    )]
+    pub stake_pool: Box<Account<'info, StakePool>>,
+
+    /// Vault holding staked tokens, owned by the stake pool PDA
+    #[account(
+        init,
+        payer = admin,
+        token::mint = stake_mint,
+        token::authority = stake_pool,
+        seeds = [STAKE_VAULT_PDA_SEED],
+        bump
+    )]
+    pub stake_vault: Box<Account<'info, TokenAccount>>,
+
+    pub stake_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn handle_initialize_stake_pool(ctx: Context<InitializeStakePool>) -> Result<()> {
+    let stake_pool = &mut ctx.accounts.stake_pool;
+    stake_pool.stake_mint = ctx.accounts.stake_mint.key();
+    stake_pool.total_staked = 0;
+    stake_pool.reward_mints = Vec::new();
+    stake_pool.bump = ctx.bumps.stake_pool;
+
+    msg!("Stake pool initialized for mint: {}", stake_pool.stake_mint);
+
+    Ok(())
+}
+
+// ==================== REWARD POOLS ====================
+
+/// Attaches a fee mint to the stake pool. Kept apart from
+/// `initialize_mint_fees` so a mint can trade before staking is set up;
+/// until this has run, settling its fees holds the stakers' share in the
+/// fee ledger.
+#[derive(Accounts)]
+pub struct InitializeRewardPool<'info> {
+    #[account(
+        seeds = [CONFIG_PDA_SEED],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [STAKE_POOL_PDA_SEED],
+        bump = stake_pool.bump
+    )]
+    pub stake_pool: Box<Account<'info, StakePool>>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + RewardPool::SIZE,
+        seeds = [REWARD_POOL_PDA_SEED, fee_mint.key().as_ref()],
+        bump
+    )]
+    pub reward_pool: Box<Account<'info, RewardPool>>,
+
+    pub fee_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_initialize_reward_pool(ctx: Context<InitializeRewardPool>) -> Result<()> {
+    let fee_mint = ctx.accounts.fee_mint.key();
+
+    let reward_pool = &mut ctx.accounts.reward_pool;
+    reward_pool.mint = fee_mint;
+    reward_pool.last_update = Clock::get()?.unix_timestamp;
+    reward_pool.bump = ctx.bumps.reward_pool;
+    let index = ctx.accounts.stake_pool.register_reward_mint(fee_mint)?;
+
+    msg!("Reward pool {} initialized for mint: {}", index, fee_mint);
+
+    Ok(())
+}
+
+// ==================== STAKE / UNSTAKE ====================
+
+/// Changes a stake. The reward pool of every mint in
+/// `stake_pool.reward_mints` is passed, writable, in the same order as
+/// remaining accounts, so rewards earned at the old stake are streamed and
+/// checkpointed first.
+#[derive(Accounts)]
+pub struct UpdateStake<'info> {
+    #[account(
+        mut,
+        seeds = [STAKE_POOL_PDA_SEED],
+        bump = stake_pool.bump
+    )]
+    pub stake_pool: Box<Account<'info, StakePool>>,
+
+    #[account(
+        mut,
+        seeds = [STAKE_VAULT_PDA_SEED],
+        bump
+    )]
+    pub stake_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + StakePosition::SIZE,
+        seeds = [STAKE_POSITION_PDA_SEED, owner.key().as_ref()],
+        bump
+    )]
+    pub stake_position: Box<Account<'info, StakePosition>>,
+
+    #[account(
+        mut,
+        constraint = owner_token_account.mint == stake_pool.stake_mint,
+        constraint = owner_token_account.owner == owner.key()
+    )]
+    pub owner_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_stake<'info>(
+    ctx: Context<'_, '_, '_, 'info, UpdateStake<'info>>,
+    amount: u64,
+) -> Result<()> {
+    require!(amount > 0, ErrorCode::AmountTooSmall);
+
+    let accs = update_reward_pools(&ctx.accounts.stake_pool, ctx.remaining_accounts, ctx.program_id)?;
+
+    let position = &mut ctx.accounts.stake_position;
+    position.owner = ctx.accounts.owner.key();
+    position.bump = ctx.bumps.stake_position;
+    for (index, acc) in accs.iter().enumerate() {
+        position.checkpoint(index, *acc)?;
+    }
+    position.amount = position.amount
+        .checked_add(amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    position.reset_debts(&accs)?;
+
+    let stake_pool = &mut ctx.accounts.stake_pool;
+    stake_pool.total_staked = stake_pool.total_staked
+        .checked_add(amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let cpi = Transfer {
+        from: ctx.accounts.owner_token_account.to_account_info(),
+        to: ctx.accounts.stake_vault.to_account_info(),
+        authority: ctx.accounts.owner.to_account_info(),
+    };
+    token::transfer(CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi), amount)?;
+
+    emit!(Staked {
+        owner: ctx.accounts.owner.key(),
+        amount,
+        position_amount: ctx.accounts.stake_position.amount,
+        total_staked: ctx.accounts.stake_pool.total_staked,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn handle_unstake<'info>(
+    ctx: Context<'_, '_, '_, 'info, UpdateStake<'info>>,
+    amount: u64,
+) -> Result<()> {
+    require!(amount > 0, ErrorCode::AmountTooSmall);
+    require!(ctx.accounts.stake_position.amount >= amount, ErrorCode::InsufficientBalance);
+
+    let accs = update_reward_pools(&ctx.accounts.stake_pool, ctx.remaining_accounts, ctx.program_id)?;
+
+    let position = &mut ctx.accounts.stake_position;
+    for (index, acc) in accs.iter().enumerate() {
+        position.checkpoint(index, *acc)?;
+    }
+    position.amount -= amount;
+    position.reset_debts(&accs)?;
+
+    let stake_pool = &mut ctx.accounts.stake_pool;
+    stake_pool.total_staked = stake_pool.total_staked
+        .checked_sub(amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let pool_seeds = &[STAKE_POOL_PDA_SEED, &[ctx.accounts.stake_pool.bump]];
+    let signer_seeds = &[&pool_seeds[..]];
+
+    let cpi = Transfer {
+        from: ctx.accounts.stake_vault.to_account_info(),
+        to: ctx.accounts.owner_token_account.to_account_info(),
+        authority: ctx.accounts.stake_pool.to_account_info(),
+    };
+    token::transfer(
+        CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi, signer_seeds),
+        amount,
+    )?;
+
+    emit!(Unstaked {
+        owner: ctx.accounts.owner.key(),
+        amount,
+        position_amount: ctx.accounts.stake_position.amount,
+        total_staked: ctx.accounts.stake_pool.total_staked,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Bring every reward pool up to date at the current stake and return its
+/// accumulator, reading the pools from the remaining accounts in
+/// `stake_pool.reward_mints` order
+fn update_reward_pools(
+    stake_pool: &StakePool,
+    remaining_accounts: &[AccountInfo],
+    program_id: &Pubkey,
+) -> Result<Vec<u128>> {
+    require!(
+        remaining_accounts.len() == stake_pool.reward_mints.len(),
+        ErrorCode::InvalidRewardPool
+    );
+    let now = Clock::get()?.unix_timestamp;
+
+    stake_pool.reward_mints
+        .iter()
+        .zip(remaining_accounts.iter())
+        .map(|(mint, account)| {
+            require_keys_eq!(*account.owner, *program_id, ErrorCode::InvalidRewardPool);
+            require!(account.is_writable, ErrorCode::InvalidRewardPool);
+            let mut pool = RewardPool::try_deserialize(&mut &account.try_borrow_data()?[..])
+                .map_err(|_| ErrorCode::InvalidRewardPool)?;
+            require_keys_eq!(pool.mint, *mint, ErrorCode::InvalidRewardPool);
+
+            let expected = Pubkey::create_program_address(
+                &[REWARD_POOL_PDA_SEED, mint.as_ref(), &[pool.bump]],
+                program_id,
+            ).map_err(|_| ErrorCode::InvalidRewardPool)?;
+            require_keys_eq!(account.key(), expected, ErrorCode::InvalidRewardPool);
+
+            pool.update(stake_pool.total_staked, now)?;
+            pool.try_serialize(&mut &mut account.try_borrow_mut_data()?[..])?;
+
+            Ok(pool.acc_reward_per_share)
+        })
+        .collect()
+}
+
+// ==================== CLAIM ====================
+
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    #[account(seeds = [STAKE_POOL_PDA_SEED], bump = stake_pool.bump)]
+    pub stake_pool: Box<Account<'info, StakePool>>,
+
+    #[account(
+        mut,
+        seeds = [STAKE_POSITION_PDA_SEED, owner.key().as_ref()],
+        bump = stake_position.bump,
+        has_one = owner @ ErrorCode::Unauthorized
+    )]
+    pub stake_position: Box<Account<'info, StakePosition>>,
+
+    #[account(
+        mut,
+        seeds = [REWARD_POOL_PDA_SEED, fee_mint.key().as_ref()],
+        bump = reward_pool.bump
+    )]
+    pub reward_pool: Box<Account<'info, RewardPool>>,
+
+    /// Stakers' share of `fee_mint` fees, paid out by claims
+    #[account(
+        mut,
+        seeds = [LIQUIDITY_STAKER_PDA_SEED, fee_mint.key().as_ref()],
+        bump
+    )]
+    pub liquidity_staker_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Fee collection authority PDA of `fee_mint`, owner of the stakers account
+    #[account(
+        seeds = [FEE_AUTHORITY_PDA_SEED, fee_mint.key().as_ref()],
+        bump
+    )]
+    pub fee_collection_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        constraint = owner_token_account.mint == fee_mint.key(),
+        constraint = owner_token_account.owner == owner.key()
+    )]
+    pub owner_token_account: Box<Account<'info, TokenAccount>>,
+
+    pub fee_mint: Box<Account<'info, Mint>>,
+
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handle_claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
+    let fee_mint = ctx.accounts.fee_mint.key();
+    let index = ctx.accounts.stake_pool.reward_index(&fee_mint)?;
+
+    ctx.accounts.reward_pool.update(ctx.accounts.stake_pool.total_staked, Clock::get()?.unix_timestamp)?;
+
+    let position = &mut ctx.accounts.stake_position;
+    position.checkpoint(index, ctx.accounts.reward_pool.acc_reward_per_share)?;
+    let amount = position.rewards[index].pending;
+    require!(amount > 0, ErrorCode::AmountTooSmall);
+    position.rewards[index].pending = 0;
+
+    let reward_pool = &mut ctx.accounts.reward_pool;
+    reward_pool.total_claimed = reward_pool.total_claimed
+        .checked_add(amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let authority_seeds = &[
+        FEE_AUTHORITY_PDA_SEED,
+        fee_mint.as_ref(),
+        &[ctx.bumps.fee_collection_authority],
+    ];
+    let signer_seeds = &[&authority_seeds[..]];
+
+    let cpi = Transfer {
+        from: ctx.accounts.liquidity_staker_account.to_account_info(),
+        to: ctx.accounts.owner_token_account.to_account_info(),
+        authority: ctx.accounts.fee_collection_authority.to_account_info(),
+    };
+    token::transfer(
+        CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi, signer_seeds),
+        amount,
+    )?;
+
+    emit!(RewardsClaimed {
+        owner: ctx.accounts.owner.key(),
+        fee_mint,
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// ==================== EVENTS ====================
+
+#[event]
+pub struct Staked {
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub position_amount: u64,
+    pub total_staked: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct Unstaked {
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub position_amount: u64,
+    pub total_staked: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardsClaimed {
+    pub owner: Pubkey,
+    pub fee_mint: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
@@ -6,26 +6,168 @@ declare_id!("2bgpPzHUWu9jRAMUcF2Kex4dKti6U554hkhpkBi4EpHK");
 pub mod constants;
 pub mod errors;
 pub mod instructions;
+pub mod math;
+pub mod oracle;
 pub mod state;
+#[cfg(test)]
+mod test_utils;
 
+use instructions::admin::{
+    InitializeProtocol, UpdateConfig, RegisterRelayer, SetRelayerStatus, InitializeMintConfig, UpdateMintConfig,
+    SetMintVolumeLimit, handle_initialize_protocol, handle_set_relayer_fee_cap,
+    handle_set_surplus_share, handle_set_referral_cap, handle_set_fee_tiers,
+    handle_set_user_volume_limit, handle_set_sweep_params, handle_set_bounty_committee,
+    handle_register_relayer, handle_set_relayer_status,
+    handle_initialize_mint_config, handle_update_mint_config, handle_set_mint_volume_limit,
+    InitializeDenylist, UpdateDenylist, handle_initialize_denylist, handle_add_to_denylist,
+    handle_remove_from_denylist,
+};
 use instructions::commit_trade::{CommitTrade, handle_commit};
 use instructions::reveal_trade::{RevealTrade, handle_reveal};
-use instructions::settle_trade::{SettleFee, InitializeFeeAccounts, handler as handle_settle, initialize_fee_accounts};
+use instructions::execute_slice::{ExecuteSlice, handle_execute_slice};
+use instructions::match_intents::{MatchIntents, handle_match};
+use instructions::referral::{InitializeReferrerStats, handle_initialize_referrer_stats};
+use instructions::batch_auction::{
+    OpenBatch, RevealToBatch, SettleBatch, ClaimBatchFill,
+    handle_open_batch, handle_reveal_to_batch, handle_settle_batch, handle_claim_batch_fill,
+};
+use instructions::settle_trade::{SettleFee, SweepFees, SettleMany, InitializeMintFees, handler as handle_settle};
+use instructions::staking::{
+    InitializeStakePool, InitializeRewardPool, UpdateStake, ClaimRewards,
+    handle_initialize_stake_pool, handle_initialize_reward_pool, handle_stake, handle_unstake,
+    handle_claim_rewards,
+};
 
-use state::{SwapIntent, TradeIntentData};
+use instructions::bounty::{
+    SubmitBountyClaim, VoteBountyClaim, PayBountyClaim,
+    handle_submit_bounty_claim, handle_vote_bounty_claim, handle_pay_bounty_claim,
+};
+
+use state::{TradeIntentData, FeeTier, DenylistKind, BountyEvidence};
 
 use instructions::*;
-use state::*;
 
 #[program]
 pub mod unikron {
     use super::*;
 
-    /// Initialize fee accounts for a specific token mint
-    pub fn initialize_fee_accounts(
-        ctx: Context<InitializeFeeAccounts>,
+    /// Initialize the global protocol config and its admin
+    pub fn initialize_protocol(ctx: Context<InitializeProtocol>) -> Result<()> {
+        handle_initialize_protocol(ctx)
+    }
+
+    /// Set the cap on relayer fees, in bps of the amount they are paid from
+    pub fn set_relayer_fee_cap(ctx: Context<UpdateConfig>, max_relayer_fee_bps: u16) -> Result<()> {
+        handle_set_relayer_fee_cap(ctx, max_relayer_fee_bps)
+    }
+
+    /// Set the share of output above `quoted_out` paid to the MEV bounty pool
+    pub fn set_surplus_share(ctx: Context<UpdateConfig>, surplus_bounty_bps: u16) -> Result<()> {
+        handle_set_surplus_share(ctx, surplus_bounty_bps)
+    }
+
+    /// Set the cap on an intent's referral fee
+    pub fn set_referral_cap(ctx: Context<UpdateConfig>, max_referral_bps: u16) -> Result<()> {
+        handle_set_referral_cap(ctx, max_referral_bps)
+    }
+
+    /// Replace the volume-tiered protocol fee discount table
+    pub fn set_fee_tiers(ctx: Context<UpdateConfig>, fee_tiers: Vec<FeeTier>) -> Result<()> {
+        handle_set_fee_tiers(ctx, fee_tiers)
+    }
+
+    /// Set the maximum notional a user may trade per day
+    pub fn set_user_volume_limit(
+        ctx: Context<UpdateConfig>,
+        max_user_daily_notional: u64,
     ) -> Result<()> {
-        instructions::settle_trade::initialize_fee_accounts(ctx)
+        handle_set_user_volume_limit(ctx, max_user_daily_notional)
+    }
+
+    /// Set the keeper reward and minimum interval of fee sweeps, and how
+    /// long settled staker rewards take to stream to stakers
+    pub fn set_sweep_params(
+        ctx: Context<UpdateConfig>,
+        keeper_reward_bps: u16,
+        min_sweep_interval_secs: i64,
+        reward_stream_secs: i64,
+    ) -> Result<()> {
+        handle_set_sweep_params(ctx, keeper_reward_bps, min_sweep_interval_secs, reward_stream_secs)
+    }
+
+    /// Set the committee voting on MEV bounty claims and its approval threshold
+    pub fn set_bounty_committee(
+        ctx: Context<UpdateConfig>,
+        members: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        handle_set_bounty_committee(ctx, members, threshold)
+    }
+
+    /// Register a relayer that may fill open intents
+    pub fn register_relayer(ctx: Context<RegisterRelayer>) -> Result<()> {
+        handle_register_relayer(ctx)
+    }
+
+    /// Activate or deactivate a registered relayer
+    pub fn set_relayer_status(ctx: Context<SetRelayerStatus>, active: bool) -> Result<()> {
+        handle_set_relayer_status(ctx, active)
+    }
+
+    /// Create the risk configuration for a token mint
+    pub fn initialize_mint_config(
+        ctx: Context<InitializeMintConfig>,
+        oracle: Pubkey,
+        max_deviation_bps: u16,
+        max_staleness_secs: u64,
+    ) -> Result<()> {
+        handle_initialize_mint_config(ctx, oracle, max_deviation_bps, max_staleness_secs)
+    }
+
+    /// Update the risk configuration for a token mint
+    pub fn update_mint_config(
+        ctx: Context<UpdateMintConfig>,
+        oracle: Pubkey,
+        max_deviation_bps: u16,
+        max_staleness_secs: u64,
+    ) -> Result<()> {
+        handle_update_mint_config(ctx, oracle, max_deviation_bps, max_staleness_secs)
+    }
+
+    /// Set the per-epoch volume limit of a token mint
+    pub fn set_mint_volume_limit(
+        ctx: Context<SetMintVolumeLimit>,
+        volume_limit: u64,
+        volume_epoch_secs: u64,
+    ) -> Result<()> {
+        handle_set_mint_volume_limit(ctx, volume_limit, volume_epoch_secs)
+    }
+
+    /// Create the empty compliance denylist
+    pub fn initialize_denylist(ctx: Context<InitializeDenylist>) -> Result<()> {
+        handle_initialize_denylist(ctx)
+    }
+
+    /// Add a wallet or mint to the denylist
+    pub fn add_to_denylist(ctx: Context<UpdateDenylist>, kind: DenylistKind, key: Pubkey) -> Result<()> {
+        handle_add_to_denylist(ctx, kind, key)
+    }
+
+    /// Remove a wallet or mint from the denylist
+    pub fn remove_from_denylist(ctx: Context<UpdateDenylist>, kind: DenylistKind, key: Pubkey) -> Result<()> {
+        handle_remove_from_denylist(ctx, kind, key)
+    }
+
+    /// Initialize the fee accounts of a token mint; safe to call again
+    pub fn initialize_mint_fees(
+        ctx: Context<InitializeMintFees>,
+    ) -> Result<()> {
+        instructions::settle_trade::initialize_mint_fees(ctx)
+    }
+
+    /// Create the earnings record of a referrer in one mint
+    pub fn initialize_referrer_stats(ctx: Context<InitializeReferrerStats>) -> Result<()> {
+        handle_initialize_referrer_stats(ctx)
     }
 
     /// Commit a trade intent hash to the blockchain
@@ -38,21 +180,135 @@ pub mod unikron {
         handle_commit(ctx, intent_hash, nonce, expiry)
     }
 
-    /// Reveal and execute a committed trade
-    pub fn reveal_trade(
-        ctx: Context<RevealTrade>,
+    /// Reveal and execute a committed trade, or part of it for partial intents
+    pub fn reveal_trade<'info>(
+        ctx: Context<'_, '_, '_, 'info, RevealTrade<'info>>,
+        intent: TradeIntentData,
+        expected_hash: [u8; 32],
+        signature: [u8; 64],
+        fill_amount: u64,
+    ) -> Result<()> {
+        handle_reveal(ctx, intent, expected_hash, signature, fill_amount)
+    }
+
+    /// Execute the next slice of a recurring intent on the user's behalf
+    pub fn execute_slice(
+        ctx: Context<ExecuteSlice>,
+        intent: TradeIntentData,
+        expected_hash: [u8; 32],
+        signature: [u8; 64],
+    ) -> Result<()> {
+        handle_execute_slice(ctx, intent, expected_hash, signature)
+    }
+
+    /// Reveal two opposing intents together and settle them peer-to-peer
+    pub fn match_intents(
+        ctx: Context<MatchIntents>,
+        intent_a: TradeIntentData,
+        expected_hash_a: [u8; 32],
+        signature_a: [u8; 64],
+        intent_b: TradeIntentData,
+        expected_hash_b: [u8; 32],
+        signature_b: [u8; 64],
+    ) -> Result<()> {
+        handle_match(ctx, intent_a, expected_hash_a, signature_a, intent_b, expected_hash_b, signature_b)
+    }
+
+    /// Open the batch for a token pair and batch window
+    pub fn open_batch(ctx: Context<OpenBatch>, epoch: u64) -> Result<()> {
+        handle_open_batch(ctx, epoch)
+    }
+
+    /// Reveal an intent into the current batch, escrowing its input
+    pub fn reveal_to_batch(
+        ctx: Context<RevealToBatch>,
         intent: TradeIntentData,
         expected_hash: [u8; 32],
         signature: [u8; 64],
     ) -> Result<()> {
-        handle_reveal(ctx, intent, expected_hash, signature)
+        handle_reveal_to_batch(ctx, intent, expected_hash, signature)
+    }
+
+    /// Settle a closed batch at a single clearing price
+    pub fn settle_batch(ctx: Context<SettleBatch>) -> Result<()> {
+        handle_settle_batch(ctx)
     }
 
-    /// Distribute collected fees to different pools
+    /// Claim the fill and refund of a settled batch order
+    pub fn claim_batch_fill(ctx: Context<ClaimBatchFill>, order_index: u8) -> Result<()> {
+        handle_claim_batch_fill(ctx, order_index)
+    }
+
+    /// Distribute a chosen amount of collected fees to different pools; admin only
     pub fn settle_trade(
         ctx: Context<SettleFee>, 
         fee_amount: u64
     ) -> Result<()> {
         handle_settle(ctx, fee_amount)
     }
+
+    /// Settle fees of several mints in one transaction, one group of
+    /// remaining accounts per entry of `fee_amounts`
+    pub fn settle_many<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SettleMany<'info>>,
+        fee_amounts: Vec<u64>,
+    ) -> Result<()> {
+        instructions::settle_trade::settle_many(ctx, fee_amounts)
+    }
+
+    /// Distribute the whole fee balance of a mint, paying the caller a keeper reward
+    pub fn sweep_fees(ctx: Context<SweepFees>) -> Result<()> {
+        instructions::settle_trade::sweep_fees(ctx)
+    }
+
+    /// Create the stake pool that earns the liquidity staker share of fees
+    pub fn initialize_stake_pool(ctx: Context<InitializeStakePool>) -> Result<()> {
+        handle_initialize_stake_pool(ctx)
+    }
+
+    /// Attach a fee mint's staker rewards to the stake pool
+    pub fn initialize_reward_pool(ctx: Context<InitializeRewardPool>) -> Result<()> {
+        handle_initialize_reward_pool(ctx)
+    }
+
+    /// Stake tokens, passing every reward pool as remaining accounts
+    pub fn stake<'info>(
+        ctx: Context<'_, '_, '_, 'info, UpdateStake<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        handle_stake(ctx, amount)
+    }
+
+    /// Unstake tokens, passing every reward pool as remaining accounts
+    pub fn unstake<'info>(
+        ctx: Context<'_, '_, '_, 'info, UpdateStake<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        handle_unstake(ctx, amount)
+    }
+
+    /// Claim accrued staker rewards in one fee mint
+    pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
+        handle_claim_rewards(ctx)
+    }
+
+    /// Submit a claim on a mint's MEV bounty pool with transaction evidence
+    pub fn submit_bounty_claim(
+        ctx: Context<SubmitBountyClaim>,
+        claim_id: u64,
+        amount: u64,
+        evidence: Vec<BountyEvidence>,
+    ) -> Result<()> {
+        handle_submit_bounty_claim(ctx, claim_id, amount, evidence)
+    }
+
+    /// Approve or reject a pending bounty claim as a committee member
+    pub fn vote_bounty_claim(ctx: Context<VoteBountyClaim>, approve: bool) -> Result<()> {
+        handle_vote_bounty_claim(ctx, approve)
+    }
+
+    /// Pay an approved bounty claim from the bounty pool
+    pub fn pay_bounty_claim(ctx: Context<PayBountyClaim>) -> Result<()> {
+        handle_pay_bounty_claim(ctx)
+    }
 }
\ No newline at end of file
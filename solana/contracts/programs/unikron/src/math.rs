@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+
+/// `amount * numerator / denominator`, rounded down
+pub fn mul_div(amount: u64, numerator: u64, denominator: u64) -> Result<u64> {
+    require!(denominator > 0, ErrorCode::MathOverflow);
+    let result = (amount as u128)
+        .checked_mul(numerator as u128)
+        .ok_or(ErrorCode::MathOverflow)?
+        / denominator as u128;
+    u64::try_from(result).map_err(|_| ErrorCode::MathOverflow.into())
+}
+
+/// `amount * numerator / denominator`, rounded up
+pub fn mul_div_ceil(amount: u64, numerator: u64, denominator: u64) -> Result<u64> {
+    require!(denominator > 0, ErrorCode::MathOverflow);
+    let product = (amount as u128)
+        .checked_mul(numerator as u128)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let result = product.div_ceil(denominator as u128);
+    u64::try_from(result).map_err(|_| ErrorCode::MathOverflow.into())
+}
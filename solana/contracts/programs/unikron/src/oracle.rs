@@ -0,0 +1,228 @@
+use anchor_lang::prelude::*;
+use crate::{errors::ErrorCode, constants::NOTIONAL_DECIMALS, state::MintConfig};
+
+// Pyth v2 price account layout
+const PYTH_MAGIC: u32 = 0xa1b2c3d4;
+const PYTH_VERSION: u32 = 2;
+const PYTH_ACCOUNT_TYPE_PRICE: u32 = 3;
+const PYTH_STATUS_TRADING: u32 = 1;
+const EXPO_OFFSET: usize = 20;
+const TIMESTAMP_OFFSET: usize = 96;
+const AGG_PRICE_OFFSET: usize = 208;
+const AGG_STATUS_OFFSET: usize = 224;
+const PRICE_ACCOUNT_MIN_LEN: usize = 240;
+
+/// Aggregate price read from an oracle account: `price * 10^expo`
+#[derive(Clone, Copy, Debug)]
+pub struct OraclePrice {
+    pub price: u64,
+    pub expo: i32,
+    pub publish_time: i64,
+}
+
+/// Parse the aggregate price of a Pyth v2 price account
+pub fn load_pyth_price(account: &AccountInfo) -> Result<OraclePrice> {
+    let data = account.try_borrow_data()?;
+    require!(data.len() >= PRICE_ACCOUNT_MIN_LEN, ErrorCode::InvalidOracle);
+
+    require!(read_u32(&data, 0) == PYTH_MAGIC, ErrorCode::InvalidOracle);
+    require!(read_u32(&data, 4) == PYTH_VERSION, ErrorCode::InvalidOracle);
+    require!(read_u32(&data, 8) == PYTH_ACCOUNT_TYPE_PRICE, ErrorCode::InvalidOracle);
+    require!(read_u32(&data, AGG_STATUS_OFFSET) == PYTH_STATUS_TRADING, ErrorCode::InvalidOracle);
+
+    let price = i64::from_le_bytes(data[AGG_PRICE_OFFSET..AGG_PRICE_OFFSET + 8].try_into().unwrap());
+    require!(price > 0, ErrorCode::InvalidOracle);
+
+    Ok(OraclePrice {
+        price: price as u64,
+        expo: read_u32(&data, EXPO_OFFSET) as i32,
+        publish_time: i64::from_le_bytes(data[TIMESTAMP_OFFSET..TIMESTAMP_OFFSET + 8].try_into().unwrap()),
+    })
+}
+
+/// Load the oracle configured for `config`, if any, and check its freshness
+pub fn load_configured_price(
+    config: &MintConfig,
+    oracle: Option<&AccountInfo>,
+    now: i64,
+) -> Result<Option<OraclePrice>> {
+    if !config.has_oracle() {
+        return Ok(None);
+    }
+
+    let oracle = oracle.ok_or(ErrorCode::InvalidOracle)?;
+    require_keys_eq!(oracle.key(), config.oracle, ErrorCode::InvalidOracle);
+
+    let price = load_pyth_price(oracle)?;
+    require!(
+        now.saturating_sub(price.publish_time) <= config.max_staleness_secs as i64,
+        ErrorCode::StaleOraclePrice
+    );
+
+    Ok(Some(price))
+}
+
+/// Oracle view of one side of a fill: the configured price, if any, with
+/// the mint's decimals and deviation limit
+#[derive(Clone, Copy, Debug)]
+pub struct MintPrice {
+    pub price: Option<OraclePrice>,
+    pub decimals: u8,
+    pub max_deviation_bps: u16,
+}
+
+impl MintPrice {
+    pub fn load(
+        config: &MintConfig,
+        oracle: Option<&AccountInfo>,
+        decimals: u8,
+        now: i64,
+    ) -> Result<Self> {
+        Ok(Self {
+            price: load_configured_price(config, oracle, now)?,
+            decimals,
+            max_deviation_bps: config.max_deviation_bps,
+        })
+    }
+}
+
+/// Reject fills whose effective price strays from the oracle price by more
+/// than the stricter of the two mints' limits. Applies when both mints have
+/// an oracle configured.
+pub fn check_price_deviation(
+    input: &MintPrice,
+    output: &MintPrice,
+    amount_in: u64,
+    amount_out: u64,
+) -> Result<()> {
+    require!(
+        within_deviation(input, output, amount_in, amount_out)?,
+        ErrorCode::OraclePriceDeviation
+    );
+    Ok(())
+}
+
+/// Whether `amount_out` for `amount_in` is within the stricter of the two
+/// mints' deviation limits; true when either side has no oracle
+pub fn within_deviation(
+    input: &MintPrice,
+    output: &MintPrice,
+    amount_in: u64,
+    amount_out: u64,
+) -> Result<bool> {
+    let (Some(price_in), Some(price_out)) = (input.price, output.price) else {
+        return Ok(true);
+    };
+
+    let expected = expected_out(amount_in, &price_in, input.decimals, &price_out, output.decimals)?;
+    let max_deviation = input.max_deviation_bps.min(output.max_deviation_bps) as u64;
+
+    Ok(deviation_bps(amount_out, expected)? <= max_deviation)
+}
+
+/// Output implied by the oracle prices for `amount_in` base units
+pub fn expected_out(
+    amount_in: u64,
+    price_in: &OraclePrice,
+    decimals_in: u8,
+    price_out: &OraclePrice,
+    decimals_out: u8,
+) -> Result<u64> {
+    let exponent = price_in.expo as i64 + decimals_out as i64 - price_out.expo as i64 - decimals_in as i64;
+    let scale = 10u128
+        .checked_pow(exponent.unsigned_abs() as u32)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let mut numerator = (amount_in as u128)
+        .checked_mul(price_in.price as u128)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let mut denominator = price_out.price as u128;
+    if exponent >= 0 {
+        numerator = numerator.checked_mul(scale).ok_or(ErrorCode::MathOverflow)?;
+    } else {
+        denominator = denominator.checked_mul(scale).ok_or(ErrorCode::MathOverflow)?;
+    }
+
+    u64::try_from(numerator / denominator).map_err(|_| ErrorCode::MathOverflow.into())
+}
+
+/// Oracle price of one whole `token_in` expressed in `token_out` base units
+pub fn unit_price(
+    price_in: &OraclePrice,
+    decimals_in: u8,
+    price_out: &OraclePrice,
+    decimals_out: u8,
+) -> Result<u64> {
+    let one = 10u64
+        .checked_pow(decimals_in as u32)
+        .ok_or(ErrorCode::MathOverflow)?;
+    expected_out(one, price_in, decimals_in, price_out, decimals_out)
+}
+
+/// Notional value of `amount` base units with `NOTIONAL_DECIMALS` decimals.
+/// Without an oracle price, one whole token counts as one unit of notional.
+pub fn notional_value(amount: u64, price: Option<&OraclePrice>, decimals: u8) -> Result<u64> {
+    let (price, expo) = price.map_or((1, 0), |p| (p.price, p.expo));
+    let exponent = expo as i64 + NOTIONAL_DECIMALS as i64 - decimals as i64;
+    let scale = 10u128
+        .checked_pow(exponent.unsigned_abs() as u32)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let value = (amount as u128)
+        .checked_mul(price as u128)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let value = if exponent >= 0 {
+        value.checked_mul(scale).ok_or(ErrorCode::MathOverflow)?
+    } else {
+        value / scale
+    };
+
+    Ok(u64::try_from(value).unwrap_or(u64::MAX))
+}
+
+/// Deviation of `actual` from `expected` in basis points
+pub fn deviation_bps(actual: u64, expected: u64) -> Result<u64> {
+    require!(expected > 0, ErrorCode::InvalidOracle);
+    let diff = actual.abs_diff(expected) as u128;
+    Ok(u64::try_from(diff * 10000 / expected as u128).unwrap_or(u64::MAX))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::mint_price;
+
+    #[test]
+    fn deviation_guard_uses_stricter_limit() {
+        // 1 A = 2 B, both with 6 decimals
+        let a = mint_price(200_000_000, 6, 100);
+        let b = mint_price(100_000_000, 6, 50);
+
+        assert!(within_deviation(&a, &b, 1_000_000, 2_000_000).unwrap());
+        assert!(within_deviation(&a, &b, 1_000_000, 1_990_000).unwrap());
+        // 1% off is within A's limit but not B's
+        assert!(!within_deviation(&a, &b, 1_000_000, 1_980_000).unwrap());
+        assert!(check_price_deviation(&a, &b, 1_000_000, 1_980_000).is_err());
+    }
+
+    #[test]
+    fn deviation_guard_skips_mints_without_oracle() {
+        let a = mint_price(200_000_000, 6, 100);
+        let b = MintPrice { price: None, decimals: 6, max_deviation_bps: 0 };
+
+        assert!(within_deviation(&a, &b, 1_000_000, 1).unwrap());
+    }
+
+    #[test]
+    fn expected_out_scales_decimals() {
+        let a = OraclePrice { price: 200_000_000, expo: -8, publish_time: 0 };
+        let b = OraclePrice { price: 100_000_000, expo: -8, publish_time: 0 };
+
+        assert_eq!(expected_out(1_000_000_000, &a, 9, &b, 6).unwrap(), 2_000_000);
+        assert_eq!(unit_price(&a, 6, &b, 6).unwrap(), 2_000_000);
+    }
+}
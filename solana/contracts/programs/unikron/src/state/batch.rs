@@ -0,0 +1,246 @@
+use anchor_lang::prelude::*;
+use crate::{errors::ErrorCode, constants::MAX_BATCH_ORDERS, math::{mul_div, mul_div_ceil}};
+
+/// Orders for one token pair revealed within one batch window, settled
+/// together at a single clearing price
+#[account]
+#[derive(Debug)]
+pub struct Batch {
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub epoch: u64,
+    pub settled: bool,
+    /// Clearing price in quote per base as `price_num / price_den`;
+    /// zero when nothing crossed and every order is refunded
+    pub price_num: u64,
+    pub price_den: u64,
+    /// Base offered by sellers whose limit the clearing price satisfies
+    pub base_eligible: u64,
+    /// Portion of `base_eligible` that was filled
+    pub base_filled: u64,
+    /// Quote offered by buyers whose limit the clearing price satisfies
+    pub quote_eligible: u64,
+    /// Portion of `quote_eligible` that was filled
+    pub quote_filled: u64,
+    pub orders: Vec<BatchOrder>,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct BatchOrder {
+    pub owner: Pubkey,
+    /// Owner of the account that receives the fill
+    pub recipient: Pubkey,
+    /// True when selling base for quote
+    pub sells_base: bool,
+    /// Input escrowed in the batch vault, net of protocol fee
+    pub amount_in: u64,
+    /// Protocol fee escrowed alongside `amount_in`, collected on the part
+    /// that fills and refunded on the rest
+    pub protocol_fee: u64,
+    pub min_out: u64,
+    pub claimed: bool,
+}
+
+impl BatchOrder {
+    pub const SIZE: usize = 32 + 32 + 1 + 8 + 8 + 8 + 1;
+
+    /// Whether this order's limit accepts `price_num / price_den`
+    fn accepts(&self, price_num: u64, price_den: u64) -> bool {
+        let (num, den) = (price_num as u128, price_den as u128);
+        if self.sells_base {
+            // min_out / amount_in <= price
+            (self.min_out as u128) * den <= (self.amount_in as u128) * num
+        } else {
+            // amount_in / min_out >= price
+            (self.amount_in as u128) * den >= (self.min_out as u128) * num
+        }
+    }
+
+    /// Limit price of this order in quote per base as `(num, den)`
+    fn limit_price(&self) -> (u64, u64) {
+        if self.sells_base {
+            (self.min_out, self.amount_in)
+        } else {
+            (self.amount_in, self.min_out)
+        }
+    }
+}
+
+impl Batch {
+    pub const SIZE: usize = 32 + 32 + 8 + 1 + 8 + 8 + 8 + 8 + 8 + 8
+        + 4 + MAX_BATCH_ORDERS * BatchOrder::SIZE + 1;
+
+    /// Pick the order limit price that maximises matched base volume, and
+    /// record the eligible and filled volume on each side at that price
+    pub fn clear(&mut self) -> Result<()> {
+        let mut best: Option<(u64, u64, u64, u64, u64, u64)> = None;
+        let mut best_volume: u64 = 0;
+
+        for candidate in self.orders.iter() {
+            let (num, den) = candidate.limit_price();
+            if num == 0 || den == 0 {
+                continue;
+            }
+
+            let (base_eligible, quote_eligible) = self.eligible_at(num, den)?;
+            let (base_filled, quote_filled) =
+                Self::fill_at(base_eligible, quote_eligible, num, den)?;
+
+            if base_filled > best_volume {
+                best_volume = base_filled;
+                best = Some((num, den, base_eligible, base_filled, quote_eligible, quote_filled));
+            }
+        }
+
+        if let Some((num, den, base_eligible, base_filled, quote_eligible, quote_filled)) = best {
+            self.price_num = num;
+            self.price_den = den;
+            self.base_eligible = base_eligible;
+            self.base_filled = base_filled;
+            self.quote_eligible = quote_eligible;
+            self.quote_filled = quote_filled;
+        }
+        self.settled = true;
+
+        Ok(())
+    }
+
+    /// Discard the clearing result so every order is refunded in full
+    pub fn refund_all(&mut self) {
+        self.price_num = 0;
+        self.price_den = 0;
+        self.base_eligible = 0;
+        self.base_filled = 0;
+        self.quote_eligible = 0;
+        self.quote_filled = 0;
+    }
+
+    /// Amounts owed to an order after settlement as `(base_out, quote_out,
+    /// protocol_fee)`, where `protocol_fee` is the share of the escrowed fee
+    /// collected on the filled input; the rest is refunded with the unfilled
+    /// input. Fills round against the order so the vaults always stay solvent.
+    pub fn claimable(&self, order: &BatchOrder) -> Result<(u64, u64, u64)> {
+        let crossed = self.price_num > 0 && order.accepts(self.price_num, self.price_den);
+
+        let (consumed, output) = if !crossed {
+            (0, 0)
+        } else if order.sells_base {
+            let filled = mul_div(order.amount_in, self.base_filled, self.base_eligible)?;
+            let consumed = mul_div_ceil(order.amount_in, self.base_filled, self.base_eligible)?;
+            (consumed, mul_div(filled, self.price_num, self.price_den)?)
+        } else {
+            let filled = mul_div(order.amount_in, self.quote_filled, self.quote_eligible)?;
+            let consumed = mul_div_ceil(order.amount_in, self.quote_filled, self.quote_eligible)?;
+            (consumed, mul_div(filled, self.price_den, self.price_num)?)
+        };
+
+        let fee = if consumed == 0 {
+            0
+        } else {
+            mul_div_ceil(order.protocol_fee, consumed, order.amount_in)?
+        };
+        let refund = order.amount_in - consumed + order.protocol_fee - fee;
+
+        Ok(if order.sells_base { (refund, output, fee) } else { (output, refund, fee) })
+    }
+
+    fn eligible_at(&self, price_num: u64, price_den: u64) -> Result<(u64, u64)> {
+        let mut base: u64 = 0;
+        let mut quote: u64 = 0;
+
+        for order in self.orders.iter().filter(|o| o.accepts(price_num, price_den)) {
+            if order.sells_base {
+                base = base.checked_add(order.amount_in).ok_or(ErrorCode::MathOverflow)?;
+            } else {
+                quote = quote.checked_add(order.amount_in).ok_or(ErrorCode::MathOverflow)?;
+            }
+        }
+
+        Ok((base, quote))
+    }
+
+    /// Filled volume on each side; the shorter side fills completely
+    fn fill_at(base_eligible: u64, quote_eligible: u64, price_num: u64, price_den: u64) -> Result<(u64, u64)> {
+        let quote_for_all_base = mul_div(base_eligible, price_num, price_den);
+
+        match quote_for_all_base {
+            Ok(quote_needed) if quote_needed <= quote_eligible => Ok((base_eligible, quote_needed)),
+            _ => {
+                let base_affordable = mul_div(quote_eligible, price_den, price_num)?;
+                Ok((base_affordable.min(base_eligible), quote_eligible))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::{batch, order};
+
+    #[test]
+    fn clears_at_price_maximising_volume() {
+        let mut batch = batch(vec![
+            order(true, 100, 100),
+            order(true, 100, 300),
+            order(false, 300, 150),
+        ]);
+        batch.clear().unwrap();
+
+        assert!(batch.settled);
+        assert_eq!((batch.price_num, batch.price_den), (100, 100));
+        assert_eq!((batch.base_filled, batch.quote_filled), (100, 100));
+
+        // The second seller's limit is above the price and is refunded with
+        // its fee; the buyer pays fee only on the third of its quote it spent
+        assert_eq!(batch.claimable(&batch.orders[0]).unwrap(), (0, 100, 1));
+        assert_eq!(batch.claimable(&batch.orders[1]).unwrap(), (101, 0, 0));
+        assert_eq!(batch.claimable(&batch.orders[2]).unwrap(), (100, 202, 1));
+    }
+
+    #[test]
+    fn pro_rata_fills_keep_vaults_solvent() {
+        let mut batch = batch(vec![
+            order(true, 100, 100),
+            order(true, 50, 50),
+            order(false, 100, 50),
+        ]);
+        batch.clear().unwrap();
+
+        let claims: Vec<(u64, u64, u64)> = batch.orders
+            .iter()
+            .map(|o| batch.claimable(o).unwrap())
+            .collect();
+        assert_eq!(claims, vec![(33, 66, 1), (16, 33, 0), (100, 0, 1)]);
+
+        // Payouts and collected fees never exceed what each vault escrowed
+        let (base_out, quote_out) = claims
+            .iter()
+            .zip(batch.orders.iter())
+            .fold((0, 0), |(base, quote), ((b, q, fee), order)| {
+                if order.sells_base { (base + b + fee, quote + q) } else { (base + b, quote + q + fee) }
+            });
+        assert!(base_out <= 151 && quote_out <= 101);
+    }
+
+    #[test]
+    fn uncrossed_orders_are_refunded() {
+        let mut batch = batch(vec![order(true, 100, 300), order(false, 100, 100)]);
+        batch.clear().unwrap();
+
+        assert_eq!(batch.price_num, 0);
+        assert_eq!(batch.claimable(&batch.orders[0]).unwrap(), (101, 0, 0));
+        assert_eq!(batch.claimable(&batch.orders[1]).unwrap(), (0, 101, 0));
+    }
+
+    #[test]
+    fn refund_all_discards_clearing_price() {
+        let mut batch = batch(vec![order(true, 100, 100), order(false, 100, 100)]);
+        batch.clear().unwrap();
+        assert!(batch.price_num > 0);
+
+        batch.refund_all();
+        assert_eq!(batch.claimable(&batch.orders[0]).unwrap(), (101, 0, 0));
+        assert_eq!(batch.claimable(&batch.orders[1]).unwrap(), (0, 101, 0));
+    }
+}
@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+use crate::{errors::ErrorCode, constants::{MAX_BOUNTY_EVIDENCE, MAX_COMMITTEE_MEMBERS}};
+
+/// A reporter's claim on the MEV bounty pool of one mint, resolved by the
+/// bounty committee in `Config`
+#[account]
+#[derive(Debug)]
+pub struct BountyClaim {
+    pub reporter: Pubkey,
+    pub claim_id: u64,
+    pub mint: Pubkey,
+    /// Bounty requested, paid from the mint's bounty pool once approved
+    pub amount: u64,
+    pub evidence: Vec<BountyEvidence>,
+    /// Votes of current committee members; votes of removed members are
+    /// dropped at the next vote, so both together never exceed the committee
+    pub approvals: Vec<Pubkey>,
+    pub rejections: Vec<Pubkey>,
+    pub status: BountyClaimStatus,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BountyEvidence {
+    /// Signature of the offending transaction
+    pub tx_signature: [u8; 64],
+    /// Value extracted by that transaction
+    pub amount: u64,
+}
+
+impl BountyEvidence {
+    pub const SIZE: usize = 64 + 8;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BountyClaimStatus {
+    Pending,
+    Approved,
+    Rejected,
+    Paid,
+}
+
+impl BountyClaim {
+    pub const SIZE: usize = 32 + 8 + 32 + 8
+        + 4 + MAX_BOUNTY_EVIDENCE * BountyEvidence::SIZE
+        + 4 + MAX_COMMITTEE_MEMBERS * 32
+        + 4 + MAX_COMMITTEE_MEMBERS * 32
+        + 1 + 8 + 1;
+
+    /// Record a vote of `member` of `committee` and resolve the claim once
+    /// `threshold` approvals are reached or can no longer be reached. Only
+    /// votes of the current committee count.
+    pub fn record_vote(
+        &mut self,
+        member: Pubkey,
+        approve: bool,
+        committee: &[Pubkey],
+        threshold: u8,
+    ) -> Result<()> {
+        require!(self.status == BountyClaimStatus::Pending, ErrorCode::BountyClaimNotPending);
+        require!(
+            !self.approvals.contains(&member) && !self.rejections.contains(&member),
+            ErrorCode::AlreadyVoted
+        );
+
+        self.approvals.retain(|voter| committee.contains(voter));
+        self.rejections.retain(|voter| committee.contains(voter));
+
+        if approve {
+            self.approvals.push(member);
+        } else {
+            self.rejections.push(member);
+        }
+
+        let threshold = threshold as usize;
+        if self.approvals.len() >= threshold {
+            self.status = BountyClaimStatus::Approved;
+        } else if self.rejections.len() > committee.len().saturating_sub(threshold) {
+            self.status = BountyClaimStatus::Rejected;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{claim, committee};
+
+    #[test]
+    fn claim_is_approved_at_threshold() {
+        let members = committee(3);
+        let mut claim = claim();
+
+        claim.record_vote(members[0], true, &members, 2).unwrap();
+        assert_eq!(claim.status, BountyClaimStatus::Pending);
+        assert!(claim.record_vote(members[0], false, &members, 2).is_err());
+
+        claim.record_vote(members[1], true, &members, 2).unwrap();
+        assert_eq!(claim.status, BountyClaimStatus::Approved);
+        assert!(claim.record_vote(members[2], true, &members, 2).is_err());
+    }
+
+    #[test]
+    fn votes_of_removed_members_do_not_count() {
+        let old = committee(MAX_COMMITTEE_MEMBERS);
+        let mut claim = claim();
+        for member in &old[..MAX_COMMITTEE_MEMBERS - 1] {
+            claim.record_vote(*member, false, &old, 1).unwrap();
+        }
+        assert_eq!(claim.status, BountyClaimStatus::Pending);
+
+        // After a full rotation the old rejections are dropped rather than
+        // rejecting the claim under the new threshold
+        let new = committee(MAX_COMMITTEE_MEMBERS);
+        claim.record_vote(new[0], true, &new, 2).unwrap();
+        assert_eq!((claim.approvals.len(), claim.rejections.len()), (1, 0));
+        assert_eq!(claim.status, BountyClaimStatus::Pending);
+
+        claim.record_vote(new[1], true, &new, 2).unwrap();
+        assert_eq!(claim.status, BountyClaimStatus::Approved);
+    }
+}
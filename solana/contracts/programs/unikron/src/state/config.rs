@@ -1,8 +1,57 @@
 use anchor_lang::prelude::*;
+use crate::constants::{MAX_FEE_TIERS, MAX_COMMITTEE_MEMBERS};
 
 #[account]
 pub struct Config {
+    pub admin: Pubkey,
     pub fee_bps: u16,
+    /// Cap on an intent's relayer fee, in bps of the amount it is paid from
+    pub max_relayer_fee_bps: u16,
+    /// Share of output above an intent's `quoted_out` paid to the MEV bounty pool
+    pub surplus_bounty_bps: u16,
+    /// Cap on an intent's `referral_bps`
+    pub max_referral_bps: u16,
+    /// Protocol fee discounts by rolling 30-day volume, ascending by `min_volume`
+    pub fee_tiers: Vec<FeeTier>,
+    /// Maximum notional a user may trade per day; zero disables the limit.
+    /// While set, fills need an oracle price for their input mint.
+    pub max_user_daily_notional: u64,
+    /// Share of a fee sweep paid to the caller, in bps of the swept balance
+    pub keeper_reward_bps: u16,
+    /// Minimum seconds between two sweeps of the same mint
+    pub min_sweep_interval_secs: i64,
+    /// Seconds over which settled staker rewards are streamed to stakers
+    pub reward_stream_secs: i64,
+    /// Members voting on MEV bounty claims
+    pub bounty_committee: Vec<Pubkey>,
+    /// Approvals needed to pay a bounty claim
+    pub bounty_threshold: u8,
     pub is_paused: bool,
     pub bump: u8,
-}
\ No newline at end of file
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FeeTier {
+    /// Rolling notional volume needed to reach this tier
+    pub min_volume: u64,
+    /// Discount on the protocol fee, in bps of the fee
+    pub discount_bps: u16,
+}
+
+impl FeeTier {
+    pub const SIZE: usize = 8 + 2;
+}
+
+impl Config {
+    pub const SIZE: usize = 32 + 2 + 2 + 2 + 2 + 4 + MAX_FEE_TIERS * FeeTier::SIZE + 8 + 2 + 8 + 8
+        + 4 + MAX_COMMITTEE_MEMBERS * 32 + 1 + 1 + 1;
+
+    /// Discount of the highest tier `volume` reaches
+    pub fn fee_discount_bps(&self, volume: u64) -> u16 {
+        self.fee_tiers
+            .iter()
+            .rev()
+            .find(|tier| volume >= tier.min_volume)
+            .map_or(0, |tier| tier.discount_bps)
+    }
+}
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use crate::{errors::ErrorCode, constants::MAX_DENYLIST_ENTRIES, state::{TradeIntentData, IntentKind}};
+
+/// Admin-managed compliance denylist. Both lists are kept sorted so
+/// membership checks are binary searches.
+#[account]
+#[derive(Debug)]
+pub struct Denylist {
+    pub wallets: Vec<Pubkey>,
+    pub mints: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DenylistKind {
+    Wallet,
+    Mint,
+}
+
+impl Denylist {
+    pub const SIZE: usize = 4 + MAX_DENYLIST_ENTRIES * 32 + 4 + MAX_DENYLIST_ENTRIES * 32 + 1;
+
+    pub fn is_wallet_listed(&self, wallet: &Pubkey) -> bool {
+        self.wallets.binary_search(wallet).is_ok()
+    }
+
+    pub fn is_mint_listed(&self, mint: &Pubkey) -> bool {
+        self.mints.binary_search(mint).is_ok()
+    }
+
+    /// Insert `key`, keeping the list sorted; returns false if already listed
+    pub fn add(&mut self, kind: DenylistKind, key: Pubkey) -> Result<bool> {
+        let list = self.list_mut(kind);
+        match list.binary_search(&key) {
+            Ok(_) => Ok(false),
+            Err(index) => {
+                require!(list.len() < MAX_DENYLIST_ENTRIES, ErrorCode::DenylistFull);
+                list.insert(index, key);
+                Ok(true)
+            }
+        }
+    }
+
+    /// Remove `key`; returns false if it was not listed
+    pub fn remove(&mut self, kind: DenylistKind, key: Pubkey) -> bool {
+        let list = self.list_mut(kind);
+        match list.binary_search(&key) {
+            Ok(index) => {
+                list.remove(index);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Refuse an intent whose user, recipient or any traded mint is listed
+    pub fn check_intent(&self, intent: &TradeIntentData) -> Result<()> {
+        require!(!self.is_wallet_listed(&intent.user), ErrorCode::Denylisted);
+        require!(!self.is_wallet_listed(&intent.recipient), ErrorCode::Denylisted);
+        require!(!self.is_mint_listed(&intent.token_in), ErrorCode::Denylisted);
+        require!(!self.is_mint_listed(&intent.token_out), ErrorCode::Denylisted);
+
+        for hop in intent.route.iter() {
+            require!(!self.is_mint_listed(&hop.mint), ErrorCode::Denylisted);
+        }
+        if let IntentKind::Basket { legs } = &intent.kind {
+            for leg in legs.iter() {
+                require!(!self.is_mint_listed(&leg.token_out), ErrorCode::Denylisted);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn list_mut(&mut self, kind: DenylistKind) -> &mut Vec<Pubkey> {
+        match kind {
+            DenylistKind::Wallet => &mut self.wallets,
+            DenylistKind::Mint => &mut self.mints,
+        }
+    }
+}
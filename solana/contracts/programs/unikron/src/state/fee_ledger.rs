@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+
+/// Running totals of protocol fees in one mint. `total_collected` less
+/// `total_distributed` is what the fee collection account should hold.
+#[account]
+#[derive(Debug)]
+pub struct FeeLedger {
+    pub mint: Pubkey,
+    pub total_collected: u64,
+    pub total_distributed: u64,
+    /// Time of the last `sweep_fees`, zero before the first
+    pub last_sweep_at: i64,
+    /// Stakers' share settled while the mint had no reward pool, queued
+    /// into the pool by the first settlement that passes one
+    pub pending_staker_rewards: u64,
+    /// Bumps of the mint's fee authority and fee token account PDAs, so
+    /// `settle_many` can check them without searching for each bump
+    pub authority_bump: u8,
+    pub collection_bump: u8,
+    pub stakers_bump: u8,
+    pub treasury_bump: u8,
+    pub bounty_bump: u8,
+    pub bump: u8,
+}
+
+impl FeeLedger {
+    pub const SIZE: usize = 32 + 8 + 8 + 8 + 8 + 5 + 1;
+
+    pub fn record_collected(&mut self, amount: u64) -> Result<()> {
+        self.total_collected = self.total_collected
+            .checked_add(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    pub fn record_distributed(&mut self, amount: u64) -> Result<()> {
+        self.total_distributed = self.total_distributed
+            .checked_add(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Whether `min_interval_secs` has passed since the last sweep
+    pub fn sweep_due(&self, now: i64, min_interval_secs: i64) -> bool {
+        self.last_sweep_at == 0 || now >= self.last_sweep_at.saturating_add(min_interval_secs)
+    }
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+
+/// Per-mint risk configuration managed by the protocol admin
+#[account]
+#[derive(Debug)]
+pub struct MintConfig {
+    pub mint: Pubkey,
+    /// Pyth price account for this mint; `Pubkey::default()` disables the guard
+    pub oracle: Pubkey,
+    /// Maximum deviation of a fill's effective price from the oracle price
+    pub max_deviation_bps: u16,
+    /// Maximum age of the oracle price at reveal time
+    pub max_staleness_secs: u64,
+    /// Maximum volume of this mint, in base units, per volume epoch; zero
+    /// disables the limit
+    pub volume_limit: u64,
+    pub volume_epoch_secs: u64,
+    /// Start of the current volume epoch
+    pub epoch_start: i64,
+    /// Volume of this mint traded in the current epoch
+    pub epoch_volume: u64,
+    pub bump: u8,
+}
+
+impl MintConfig {
+    pub const SIZE: usize = 32 + 32 + 2 + 8 + 8 + 8 + 8 + 8 + 1;
+
+    pub fn has_oracle(&self) -> bool {
+        self.oracle != Pubkey::default()
+    }
+
+    /// Add `amount` to the current epoch's volume, starting a new epoch when
+    /// the previous one has ended, and enforce the epoch limit
+    pub fn record_volume(&mut self, amount: u64, now: i64) -> Result<()> {
+        if self.volume_limit == 0 || self.volume_epoch_secs == 0 {
+            return Ok(());
+        }
+
+        if now >= self.epoch_start.saturating_add(self.volume_epoch_secs as i64) {
+            self.epoch_start = now - now.rem_euclid(self.volume_epoch_secs as i64);
+            self.epoch_volume = 0;
+        }
+
+        self.epoch_volume = self.epoch_volume
+            .checked_add(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(self.epoch_volume <= self.volume_limit, ErrorCode::VolumeLimitExceeded);
+
+        Ok(())
+    }
+}
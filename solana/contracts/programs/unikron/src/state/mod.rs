@@ -1,7 +1,25 @@
 pub mod trade_intent;
 pub mod config;
 pub mod fee_pools;
+pub mod relayer;
+pub mod batch;
+pub mod mint_config;
+pub mod referrer;
+pub mod user_stats;
+pub mod denylist;
+pub mod fee_ledger;
+pub mod staking;
+pub mod bounty;
 
 pub use trade_intent::*;
 pub use config::*;
-pub use fee_pools::*;
\ No newline at end of file
+pub use fee_pools::*;
+pub use relayer::*;
+pub use batch::*;
+pub use mint_config::*;
+pub use referrer::*;
+pub use user_stats::*;
+pub use denylist::*;
+pub use fee_ledger::*;
+pub use staking::*;
+pub use bounty::*;
@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+/// Cumulative referral earnings of one referrer in one mint
+#[account]
+#[derive(Debug)]
+pub struct ReferrerStats {
+    pub referrer: Pubkey,
+    pub mint: Pubkey,
+    pub total_earned: u64,
+    pub referred_trades: u64,
+    pub bump: u8,
+}
+
+impl ReferrerStats {
+    pub const SIZE: usize = 32 + 32 + 8 + 8 + 1;
+}
@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Debug)]
+pub struct RelayerRegistration {
+    pub relayer: Pubkey,
+    pub active: bool,
+    pub registered_at: i64,
+    pub bump: u8,
+}
+
+impl RelayerRegistration {
+    pub const SIZE: usize = 32 + 1 + 8 + 1;
+}
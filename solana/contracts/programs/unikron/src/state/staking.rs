@@ -0,0 +1,256 @@
+use anchor_lang::prelude::*;
+use crate::{errors::ErrorCode, constants::{MAX_REWARD_MINTS, REWARD_PRECISION}, math::mul_div};
+
+/// Global pool of staked tokens. Stakers earn the liquidity staker share of
+/// protocol fees in every mint listed in `reward_mints`.
+#[account]
+#[derive(Debug)]
+pub struct StakePool {
+    pub stake_mint: Pubkey,
+    pub total_staked: u64,
+    /// Fee mints with a reward pool, in registration order
+    pub reward_mints: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+impl StakePool {
+    pub const SIZE: usize = 32 + 8 + 4 + MAX_REWARD_MINTS * 32 + 1;
+
+    /// Index of `mint` in `reward_mints`, registering it if new
+    pub fn register_reward_mint(&mut self, mint: Pubkey) -> Result<usize> {
+        if let Some(index) = self.reward_mints.iter().position(|m| *m == mint) {
+            return Ok(index);
+        }
+        require!(self.reward_mints.len() < MAX_REWARD_MINTS, ErrorCode::RewardMintsFull);
+        self.reward_mints.push(mint);
+        Ok(self.reward_mints.len() - 1)
+    }
+
+    pub fn reward_index(&self, mint: &Pubkey) -> Result<usize> {
+        self.reward_mints
+            .iter()
+            .position(|m| m == mint)
+            .ok_or(ErrorCode::InvalidRewardPool.into())
+    }
+}
+
+/// Reward accumulator of one fee mint, paid out of that mint's liquidity
+/// staker account. Settled rewards are streamed into the accumulator over
+/// time rather than at once, so stake added just before a settlement and
+/// removed right after it earns nothing from that settlement.
+#[account]
+#[derive(Debug)]
+pub struct RewardPool {
+    pub mint: Pubkey,
+    /// Rewards per staked token, scaled by `REWARD_PRECISION`
+    pub acc_reward_per_share: u128,
+    /// Settled rewards not yet streamed into the accumulator
+    pub queued: u64,
+    /// Time by which `queued` is fully streamed
+    pub stream_end: i64,
+    /// Time the accumulator was last brought up to date
+    pub last_update: i64,
+    pub total_accrued: u64,
+    pub total_claimed: u64,
+    pub bump: u8,
+}
+
+impl RewardPool {
+    pub const SIZE: usize = 32 + 16 + 8 + 8 + 8 + 8 + 8 + 1;
+
+    /// Stream the share of `queued` due by `now` over `total_staked`. The
+    /// stream pauses while nothing is staked.
+    pub fn update(&mut self, total_staked: u64, now: i64) -> Result<()> {
+        let elapsed = now.saturating_sub(self.last_update);
+        if elapsed <= 0 {
+            return Ok(());
+        }
+        let remaining = self.stream_end.saturating_sub(self.last_update);
+        self.last_update = now;
+
+        if self.queued == 0 {
+            return Ok(());
+        }
+        if total_staked == 0 {
+            self.stream_end = self.stream_end.max(now - elapsed).saturating_add(elapsed);
+            return Ok(());
+        }
+
+        let released = if elapsed >= remaining {
+            self.queued
+        } else {
+            mul_div(self.queued, elapsed as u64, remaining as u64)?
+        };
+        let increment = (released as u128)
+            .checked_mul(REWARD_PRECISION)
+            .ok_or(ErrorCode::MathOverflow)?
+            / total_staked as u128;
+        self.acc_reward_per_share = self.acc_reward_per_share
+            .checked_add(increment)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.total_accrued = self.total_accrued
+            .checked_add(released)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.queued -= released;
+
+        Ok(())
+    }
+
+    /// Queue `amount` of newly settled rewards, streaming it together with
+    /// whatever is still queued over the next `stream_secs`
+    pub fn accrue(&mut self, amount: u64, total_staked: u64, now: i64, stream_secs: i64) -> Result<()> {
+        self.update(total_staked, now)?;
+        self.queued = self.queued
+            .checked_add(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.stream_end = now
+            .checked_add(stream_secs)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+}
+
+/// Stake of one owner and its reward checkpoints, indexed like
+/// `StakePool::reward_mints`
+#[account]
+#[derive(Debug)]
+pub struct StakePosition {
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub rewards: Vec<PositionReward>,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct PositionReward {
+    /// `amount * acc_reward_per_share` already accounted for
+    pub reward_debt: u128,
+    /// Accrued and not yet claimed
+    pub pending: u64,
+}
+
+impl PositionReward {
+    pub const SIZE: usize = 16 + 8;
+}
+
+impl StakePosition {
+    pub const SIZE: usize = 32 + 8 + 4 + MAX_REWARD_MINTS * PositionReward::SIZE + 1;
+
+    /// Move rewards earned since the last checkpoint of reward `index` into
+    /// `pending`. Mints registered after the position last changed start
+    /// from a zero debt, as the pool's accumulator started at zero.
+    pub fn checkpoint(&mut self, index: usize, acc_reward_per_share: u128) -> Result<()> {
+        if self.rewards.len() <= index {
+            self.rewards.resize(index + 1, PositionReward::default());
+        }
+
+        let accumulated = self.accumulated(acc_reward_per_share)?;
+        let reward = &mut self.rewards[index];
+        let earned = accumulated
+            .checked_sub(reward.reward_debt)
+            .ok_or(ErrorCode::MathOverflow)?
+            / REWARD_PRECISION;
+        reward.pending = reward.pending
+            .checked_add(u64::try_from(earned).map_err(|_| ErrorCode::MathOverflow)?)
+            .ok_or(ErrorCode::MathOverflow)?;
+        reward.reward_debt = reward.reward_debt
+            .checked_add(earned * REWARD_PRECISION)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        Ok(())
+    }
+
+    /// Rebase every reward debt after `amount` changed. Every reward must
+    /// have been checkpointed first.
+    pub fn reset_debts(&mut self, accs: &[u128]) -> Result<()> {
+        for (index, acc) in accs.iter().enumerate() {
+            self.rewards[index].reward_debt = self.accumulated(*acc)?;
+        }
+        Ok(())
+    }
+
+    fn accumulated(&self, acc_reward_per_share: u128) -> Result<u128> {
+        (self.amount as u128)
+            .checked_mul(acc_reward_per_share)
+            .ok_or(ErrorCode::MathOverflow.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{position, reward_pool};
+
+    #[test]
+    fn settled_rewards_stream_over_the_window() {
+        let mut pool = reward_pool();
+
+        pool.accrue(1000, 400, 100, 100).unwrap();
+        assert_eq!((pool.queued, pool.stream_end), (1000, 200));
+
+        // Nothing is released within the settling second, so stake added
+        // and removed around a settlement earns nothing from it
+        pool.update(400, 100).unwrap();
+        assert_eq!(pool.acc_reward_per_share, 0);
+
+        pool.update(400, 150).unwrap();
+        assert_eq!(pool.queued, 500);
+        assert_eq!(pool.acc_reward_per_share, 500 * REWARD_PRECISION / 400);
+
+        // A new settlement restarts the window for everything still queued
+        pool.accrue(500, 400, 150, 100).unwrap();
+        pool.update(400, 200).unwrap();
+        assert_eq!(pool.queued, 500);
+        pool.update(400, 300).unwrap();
+        assert_eq!(pool.queued, 0);
+        assert_eq!(pool.total_accrued, 1500);
+    }
+
+    #[test]
+    fn stream_pauses_while_nothing_is_staked() {
+        let mut pool = reward_pool();
+
+        pool.accrue(100, 0, 10, 100).unwrap();
+        pool.update(0, 60).unwrap();
+        assert_eq!((pool.queued, pool.stream_end), (100, 160));
+
+        pool.update(1000, 110).unwrap();
+        assert_eq!(pool.queued, 50);
+        assert_eq!(pool.total_accrued, 50);
+    }
+
+    #[test]
+    fn positions_earn_their_share_of_accrued_rewards() {
+        let mut pool = reward_pool();
+        let (mut a, mut b) = (position(300), position(100));
+
+        pool.accrue(1000, 400, 0, 0).unwrap();
+        pool.update(400, 1).unwrap();
+        a.checkpoint(0, pool.acc_reward_per_share).unwrap();
+        b.checkpoint(0, pool.acc_reward_per_share).unwrap();
+        assert_eq!(a.rewards[0].pending, 750);
+        assert_eq!(b.rewards[0].pending, 250);
+
+        // A claim empties `pending`; later rewards accrue from the checkpoint
+        a.rewards[0].pending = 0;
+        pool.accrue(400, 400, 1, 0).unwrap();
+        pool.update(400, 2).unwrap();
+        a.checkpoint(0, pool.acc_reward_per_share).unwrap();
+        assert_eq!(a.rewards[0].pending, 300);
+    }
+
+    #[test]
+    fn stake_changes_do_not_earn_past_rewards() {
+        let mut pool = reward_pool();
+        let mut a = position(100);
+
+        pool.accrue(1000, 100, 0, 0).unwrap();
+        pool.update(100, 1).unwrap();
+        a.checkpoint(0, pool.acc_reward_per_share).unwrap();
+        a.amount += 100;
+        a.reset_debts(&[pool.acc_reward_per_share]).unwrap();
+
+        a.checkpoint(0, pool.acc_reward_per_share).unwrap();
+        assert_eq!(a.rewards[0].pending, 1000);
+    }
+}
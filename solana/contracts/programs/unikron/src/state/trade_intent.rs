@@ -1,4 +1,9 @@
 use anchor_lang::prelude::*;
+use crate::{
+    errors::ErrorCode,
+    constants::{MAX_ROUTE_HOPS, MAX_BASKET_LEGS},
+    math::mul_div_ceil,
+};
 
 #[account]
 #[derive(Debug)]
@@ -8,11 +13,16 @@ pub struct SwapIntent {
     pub nonce: u64,
     pub expiry: u64,
     pub timestamp: i64,
+    pub commit_slot: u64,
+    /// Cumulative `amount_in` filled across reveals
+    pub filled_in: u64,
+    /// Time of the most recent fill, used to space recurring slices
+    pub last_fill_at: i64,
     pub revealed: bool,
 }
 
 impl SwapIntent {
-    pub const SIZE: usize = 8 + 32 + 32 + 8 + 8 + 8 + 1;
+    pub const SIZE: usize = 8 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 1;
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
@@ -22,8 +32,330 @@ pub struct TradeIntentData {
     pub expiry: u64,
     pub relayer: Pubkey,
     pub relayer_fee: u64,
+    /// Token the relayer fee is paid in
+    pub relayer_fee_token: FeeToken,
     pub token_in: Pubkey,
     pub token_out: Pubkey,
     pub amount_in: u64,
     pub min_out: u64,
-}
\ No newline at end of file
+    /// Output quoted to the user for `token_out`; on `reveal_trade` fills,
+    /// output above it is surplus shared with the MEV bounty pool. Zero
+    /// disables sharing.
+    pub quoted_out: u64,
+    /// Owner of the account that receives the output
+    pub recipient: Pubkey,
+    /// Frontend or partner credited with the flow; `Pubkey::default()` for none
+    pub referrer: Pubkey,
+    /// Referral fee in bps of the input, paid to `referrer` on top of it.
+    /// Only `reveal_trade` pays referrals; other fill paths reject intents
+    /// that carry one.
+    pub referral_bps: u16,
+    /// Slots after commit during which only `relayer` may fill
+    pub exclusivity_slots: u64,
+    pub kind: IntentKind,
+    /// Allow several reveals to each fill part of `amount_in`
+    pub allow_partial: bool,
+    /// Intermediate mints between `token_in` and `token_out`, in order.
+    /// Only `reveal_trade` fills routed intents.
+    pub route: Vec<RouteHop>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FeeToken {
+    /// Charged on top of `amount_in`
+    TokenIn,
+    /// Withheld from the output before delivery
+    TokenOut,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RouteHop {
+    pub mint: Pubkey,
+    /// Minimum amount of `mint` this hop must produce; zero for no floor
+    pub min_out: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum IntentKind {
+    /// Fixed `min_out` for the lifetime of the intent
+    Standard,
+    /// Required output decays linearly from `start_min_out` to `end_min_out`
+    /// between `start_time` and `end_time`
+    DutchAuction {
+        start_min_out: u64,
+        end_min_out: u64,
+        start_time: i64,
+        end_time: i64,
+    },
+    /// Buy exactly `amount_out`, spending at most `max_in`
+    ExactOut {
+        amount_out: u64,
+        max_in: u64,
+    },
+    /// Split `amount_in` across several output mints by weight
+    Basket {
+        legs: Vec<BasketLeg>,
+    },
+    /// Limit or stop order that may only be filled once the oracle price of
+    /// one whole `token_in`, in `token_out` base units, crosses `trigger_price`
+    Conditional {
+        trigger_price: u64,
+        direction: TriggerDirection,
+    },
+    /// TWAP / DCA: `amount_in` is sold in slices of `slice_amount_in`, at most
+    /// one per `interval_secs`, each required to return `slice_min_out`.
+    ///
+    /// Slices spend the input through an SPL delegation to the intent PDA,
+    /// and a token account has a single delegate. Approving another delegate
+    /// on the same input account, such as a second recurring intent, revokes
+    /// this one and stops its slices until it is approved again.
+    Recurring {
+        slice_amount_in: u64,
+        interval_secs: u64,
+        slice_min_out: u64,
+    },
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TriggerDirection {
+    /// Fill at or above the trigger, e.g. a take-profit limit sell
+    Above,
+    /// Fill at or below the trigger, e.g. a stop-loss
+    Below,
+}
+
+impl TriggerDirection {
+    pub fn is_crossed(&self, price: u64, trigger_price: u64) -> bool {
+        match self {
+            TriggerDirection::Above => price >= trigger_price,
+            TriggerDirection::Below => price <= trigger_price,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BasketLeg {
+    pub token_out: Pubkey,
+    pub weight_bps: u16,
+    pub min_out: u64,
+}
+
+impl TradeIntentData {
+    /// An intent whose relayer is the default pubkey may be filled by any
+    /// registered relayer; the first valid reveal wins.
+    pub fn is_open(&self) -> bool {
+        self.relayer == Pubkey::default()
+    }
+
+    /// Whether any registered relayer may fill at `slot`. An intent naming a
+    /// relayer with an exclusivity window opens up once the window has elapsed.
+    pub fn is_open_at(&self, commit_slot: u64, slot: u64) -> bool {
+        if self.is_open() {
+            return true;
+        }
+        self.exclusivity_slots > 0 && slot >= commit_slot.saturating_add(self.exclusivity_slots)
+    }
+
+    /// Whether the intent credits a referrer or carries a referral fee
+    pub fn has_referral(&self) -> bool {
+        self.referrer != Pubkey::default() || self.referral_bps > 0
+    }
+
+    /// Split a relayer fee into its `(token_in, token_out)` portions
+    pub fn split_relayer_fee(&self, relayer_fee: u64) -> (u64, u64) {
+        match self.relayer_fee_token {
+            FeeToken::TokenIn => (relayer_fee, 0),
+            FeeToken::TokenOut => (0, relayer_fee),
+        }
+    }
+
+    /// Check kind-specific parameters. Exact-out intents mirror `max_in` and
+    /// `amount_out` into `amount_in` and `min_out`, baskets mirror their first
+    /// leg into `token_out` and `min_out`, and recurring intents mirror
+    /// `slice_min_out` into `min_out`, so shared checks still apply.
+    pub fn validate_kind(&self) -> Result<()> {
+        require!(self.route.len() <= MAX_ROUTE_HOPS, ErrorCode::RouteTooLong);
+
+        match &self.kind {
+            IntentKind::Standard => {}
+            IntentKind::DutchAuction { start_min_out, end_min_out, start_time, end_time } => {
+                require!(end_time > start_time, ErrorCode::InvalidIntentParams);
+                require!(start_min_out >= end_min_out, ErrorCode::InvalidIntentParams);
+            }
+            IntentKind::ExactOut { amount_out, max_in } => {
+                require!(self.amount_in == *max_in, ErrorCode::InvalidIntentParams);
+                require!(self.min_out == *amount_out, ErrorCode::InvalidIntentParams);
+                require!(!self.allow_partial, ErrorCode::InvalidIntentParams);
+                require!(self.route.is_empty(), ErrorCode::InvalidIntentParams);
+            }
+            IntentKind::Basket { legs } => {
+                require!(
+                    legs.len() >= 2 && legs.len() <= MAX_BASKET_LEGS,
+                    ErrorCode::InvalidIntentParams
+                );
+                require!(self.route.is_empty(), ErrorCode::InvalidIntentParams);
+                require_keys_eq!(legs[0].token_out, self.token_out, ErrorCode::InvalidIntentParams);
+                require!(legs[0].min_out == self.min_out, ErrorCode::InvalidIntentParams);
+
+                let mut total_weight: u64 = 0;
+                for (i, leg) in legs.iter().enumerate() {
+                    require!(leg.weight_bps > 0, ErrorCode::InvalidIntentParams);
+                    require!(
+                        legs[..i].iter().all(|other| other.token_out != leg.token_out),
+                        ErrorCode::InvalidIntentParams
+                    );
+                    total_weight += leg.weight_bps as u64;
+                }
+                require!(total_weight == 10000, ErrorCode::InvalidIntentParams);
+            }
+            IntentKind::Conditional { trigger_price, .. } => {
+                require!(*trigger_price > 0, ErrorCode::InvalidIntentParams);
+            }
+            IntentKind::Recurring { slice_amount_in, interval_secs, slice_min_out } => {
+                require!(
+                    *slice_amount_in > 0 && *slice_amount_in <= self.amount_in,
+                    ErrorCode::InvalidIntentParams
+                );
+                require!(*interval_secs > 0, ErrorCode::InvalidIntentParams);
+                require!(self.min_out == *slice_min_out, ErrorCode::InvalidIntentParams);
+                require!(!self.allow_partial, ErrorCode::InvalidIntentParams);
+                require!(self.route.is_empty(), ErrorCode::InvalidIntentParams);
+            }
+        }
+        Ok(())
+    }
+
+    /// Input and required output of the next slice of a recurring intent
+    /// that has sold `filled_in` so far. The final slice takes whatever
+    /// remains and scales its floor to match.
+    pub fn next_slice(&self, filled_in: u64) -> Result<(u64, u64)> {
+        let IntentKind::Recurring { slice_amount_in, slice_min_out, .. } = self.kind else {
+            return Err(ErrorCode::UnsupportedIntentKind.into());
+        };
+
+        let remaining = self.amount_in
+            .checked_sub(filled_in)
+            .ok_or(ErrorCode::FillExceedsRemaining)?;
+        require!(remaining > 0, ErrorCode::FillExceedsRemaining);
+
+        let slice_in = slice_amount_in.min(remaining);
+        let required_out = mul_div_ceil(slice_min_out, slice_in, slice_amount_in)?;
+        Ok((slice_in, required_out))
+    }
+
+    /// Whether a recurring intent may execute a slice at `now`: the first
+    /// slice at any time, later ones at most once per interval
+    pub fn slice_due(&self, filled_in: u64, last_fill_at: i64, now: i64) -> bool {
+        let IntentKind::Recurring { interval_secs, .. } = self.kind else {
+            return false;
+        };
+        filled_in == 0 || now >= last_fill_at.saturating_add(interval_secs as i64)
+    }
+
+    /// Output the relayer must deliver at `now`. `min_out` is always a hard
+    /// floor; Dutch auctions may require more while they decay.
+    pub fn required_min_out(&self, now: i64) -> Result<u64> {
+        match self.kind {
+            IntentKind::Standard
+            | IntentKind::Basket { .. }
+            | IntentKind::Conditional { .. }
+            | IntentKind::Recurring { .. } => Ok(self.min_out),
+            IntentKind::ExactOut { amount_out, .. } => Ok(amount_out),
+            IntentKind::DutchAuction { start_min_out, end_min_out, start_time, end_time } => {
+                let decayed = if now <= start_time {
+                    start_min_out
+                } else if now >= end_time {
+                    end_min_out
+                } else {
+                    let range = start_min_out.saturating_sub(end_min_out) as u128;
+                    let elapsed = (now as i128 - start_time as i128) as u128;
+                    let duration = (end_time as i128 - start_time as i128) as u128;
+                    let decay = range
+                        .checked_mul(elapsed)
+                        .ok_or(ErrorCode::MathOverflow)?
+                        / duration;
+                    start_min_out - decay as u64
+                };
+
+                Ok(decayed.max(self.min_out))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::intent;
+
+    fn recurring() -> TradeIntentData {
+        let kind = IntentKind::Recurring {
+            slice_amount_in: 400,
+            interval_secs: 60,
+            slice_min_out: 390,
+        };
+        intent(1_000, 390, kind)
+    }
+
+    #[test]
+    fn final_slice_takes_remainder_at_scaled_floor() {
+        let intent = recurring();
+
+        assert_eq!(intent.next_slice(0).unwrap(), (400, 390));
+        assert_eq!(intent.next_slice(400).unwrap(), (400, 390));
+        // 200 left: half a slice, half the floor rounded up
+        assert_eq!(intent.next_slice(800).unwrap(), (200, 195));
+        assert!(intent.next_slice(1_000).is_err());
+    }
+
+    #[test]
+    fn slices_are_spaced_by_interval() {
+        let recurring = recurring();
+
+        assert!(recurring.slice_due(0, 0, 10));
+        assert!(!recurring.slice_due(400, 1_000, 1_059));
+        assert!(recurring.slice_due(400, 1_000, 1_060));
+        assert!(!intent(1_000, 390, IntentKind::Standard).slice_due(0, 0, 10));
+    }
+
+    #[test]
+    fn dutch_auction_decays_to_floor() {
+        let kind = IntentKind::DutchAuction {
+            start_min_out: 1_000,
+            end_min_out: 900,
+            start_time: 100,
+            end_time: 200,
+        };
+        let dutch = intent(1_000, 900, kind);
+
+        assert_eq!(dutch.required_min_out(50).unwrap(), 1_000);
+        assert_eq!(dutch.required_min_out(125).unwrap(), 975);
+        assert_eq!(dutch.required_min_out(150).unwrap(), 950);
+        assert_eq!(dutch.required_min_out(300).unwrap(), 900);
+    }
+
+    #[test]
+    fn min_out_is_a_hard_floor() {
+        let kind = IntentKind::DutchAuction {
+            start_min_out: 1_000,
+            end_min_out: 500,
+            start_time: 100,
+            end_time: 200,
+        };
+        let dutch = intent(1_000, 800, kind);
+        assert_eq!(dutch.required_min_out(190).unwrap(), 800);
+    }
+
+    #[test]
+    fn referral_terms_are_detected() {
+        let mut referred = intent(1_000, 990, IntentKind::Standard);
+        assert!(!referred.has_referral());
+
+        referred.referral_bps = 25;
+        assert!(referred.has_referral());
+
+        referred.referral_bps = 0;
+        referred.referrer = Pubkey::new_unique();
+        assert!(referred.has_referral());
+    }
+}
@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+use crate::{errors::ErrorCode, constants::STATS_WINDOW_DAYS};
+
+/// Rolling trading activity of one user, bucketed by day
+#[account]
+#[derive(Debug)]
+pub struct UserStats {
+    pub user: Pubkey,
+    /// Notional volume per day, indexed by `day % STATS_WINDOW_DAYS`
+    pub daily_volume: [u64; STATS_WINDOW_DAYS],
+    /// Day index (unix days) of the most recent update
+    pub last_day: i64,
+    pub trade_count: u64,
+    pub bump: u8,
+}
+
+impl UserStats {
+    pub const SIZE: usize = 32 + 8 * STATS_WINDOW_DAYS + 8 + 8 + 1;
+
+    /// Claim an account created on first use by a fill
+    pub fn initialize_if_new(&mut self, user: Pubkey, bump: u8) {
+        if self.user == Pubkey::default() {
+            self.user = user;
+            self.bump = bump;
+        }
+    }
+
+    /// Clear the buckets of days that have left the window since `last_day`
+    pub fn roll(&mut self, day: i64) {
+        if day <= self.last_day {
+            return;
+        }
+
+        if day - self.last_day >= STATS_WINDOW_DAYS as i64 {
+            self.daily_volume = [0; STATS_WINDOW_DAYS];
+        } else {
+            for d in self.last_day + 1..=day {
+                self.daily_volume[Self::bucket(d)] = 0;
+            }
+        }
+        self.last_day = day;
+    }
+
+    /// Volume over the window ending at `day`, without rolling the buckets
+    pub fn volume_at(&self, day: i64) -> u64 {
+        let first_day = (day - STATS_WINDOW_DAYS as i64 + 1).max(self.last_day - STATS_WINDOW_DAYS as i64 + 1);
+        (first_day..=self.last_day.min(day))
+            .fold(0u64, |total, d| total.saturating_add(self.daily_volume[Self::bucket(d)]))
+    }
+
+    /// Volume on `day` alone
+    pub fn volume_on(&self, day: i64) -> u64 {
+        if day > self.last_day || day <= self.last_day - STATS_WINDOW_DAYS as i64 {
+            return 0;
+        }
+        self.daily_volume[Self::bucket(day)]
+    }
+
+    /// Add a trade of `notional` to the bucket of `day`
+    pub fn record(&mut self, day: i64, notional: u64) -> Result<()> {
+        self.roll(day);
+        let bucket = &mut self.daily_volume[Self::bucket(day)];
+        *bucket = bucket.checked_add(notional).ok_or(ErrorCode::MathOverflow)?;
+        self.trade_count += 1;
+        Ok(())
+    }
+
+    fn bucket(day: i64) -> usize {
+        day.rem_euclid(STATS_WINDOW_DAYS as i64) as usize
+    }
+}
+
+/// Day index of a unix timestamp
+pub fn unix_day(timestamp: i64) -> i64 {
+    timestamp.div_euclid(86400)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::user_stats;
+
+    #[test]
+    fn volume_sums_the_window() {
+        let mut stats = user_stats();
+        let window = STATS_WINDOW_DAYS as i64;
+        stats.record(100, 10).unwrap();
+        stats.record(100, 5).unwrap();
+        stats.record(101, 20).unwrap();
+
+        assert_eq!(stats.volume_at(101), 35);
+        assert_eq!(stats.volume_on(100), 15);
+        assert_eq!(stats.trade_count, 3);
+
+        // Days drop out as the window moves past them, before any roll
+        assert_eq!(stats.volume_at(100 + window - 1), 35);
+        assert_eq!(stats.volume_at(100 + window), 20);
+        assert_eq!(stats.volume_at(101 + window), 0);
+        assert_eq!(stats.last_day, 101);
+    }
+
+    #[test]
+    fn rolling_clears_expired_buckets() {
+        let mut stats = user_stats();
+        let window = STATS_WINDOW_DAYS as i64;
+        stats.record(100, 10).unwrap();
+        stats.record(101, 20).unwrap();
+
+        stats.record(100 + window, 7).unwrap();
+        assert_eq!(stats.volume_on(100 + window), 7);
+        assert_eq!(stats.volume_at(100 + window), 27);
+
+        stats.record(200 + window, 1).unwrap();
+        assert_eq!(stats.volume_at(200 + window), 1);
+    }
+}
@@ -0,0 +1,150 @@
+use anchor_lang::prelude::*;
+use crate::{state::*, constants::*, oracle::{MintPrice, OraclePrice}};
+
+/// Intent between fresh mints and wallets, with every optional term unset
+pub fn intent(amount_in: u64, min_out: u64, kind: IntentKind) -> TradeIntentData {
+    TradeIntentData {
+        user: Pubkey::new_unique(),
+        nonce: 1,
+        expiry: 0,
+        relayer: Pubkey::default(),
+        relayer_fee: 0,
+        relayer_fee_token: FeeToken::TokenOut,
+        token_in: Pubkey::new_unique(),
+        token_out: Pubkey::new_unique(),
+        amount_in,
+        min_out,
+        quoted_out: 0,
+        recipient: Pubkey::new_unique(),
+        referrer: Pubkey::default(),
+        referral_bps: 0,
+        exclusivity_slots: 0,
+        kind,
+        allow_partial: false,
+        route: Vec::new(),
+    }
+}
+
+/// Protocol config at the default fee with no tiers or caps beyond the
+/// daily notional limit
+pub fn config(max_user_daily_notional: u64) -> Config {
+    Config {
+        admin: Pubkey::default(),
+        fee_bps: FEE_BASIS_POINTS as u16,
+        max_relayer_fee_bps: 0,
+        surplus_bounty_bps: 0,
+        max_referral_bps: 0,
+        fee_tiers: Vec::new(),
+        max_user_daily_notional,
+        keeper_reward_bps: 0,
+        min_sweep_interval_secs: 0,
+        reward_stream_secs: 0,
+        bounty_committee: Vec::new(),
+        bounty_threshold: 0,
+        is_paused: false,
+        bump: 0,
+    }
+}
+
+pub fn user_stats() -> UserStats {
+    UserStats {
+        user: Pubkey::default(),
+        daily_volume: [0; STATS_WINDOW_DAYS],
+        last_day: 0,
+        trade_count: 0,
+        bump: 0,
+    }
+}
+
+/// Mint with no oracle and hourly volume epochs
+pub fn mint_config(volume_limit: u64) -> MintConfig {
+    MintConfig {
+        mint: Pubkey::default(),
+        oracle: Pubkey::default(),
+        max_deviation_bps: 0,
+        max_staleness_secs: 0,
+        volume_limit,
+        volume_epoch_secs: 3600,
+        epoch_start: 0,
+        epoch_volume: 0,
+        bump: 0,
+    }
+}
+
+/// Reward pool with nothing accrued or queued
+pub fn reward_pool() -> RewardPool {
+    RewardPool {
+        mint: Pubkey::default(),
+        acc_reward_per_share: 0,
+        queued: 0,
+        stream_end: 0,
+        last_update: 0,
+        total_accrued: 0,
+        total_claimed: 0,
+        bump: 0,
+    }
+}
+
+pub fn position(amount: u64) -> StakePosition {
+    StakePosition { owner: Pubkey::default(), amount, rewards: Vec::new(), bump: 0 }
+}
+
+/// Pending claim for 1000 tokens with no votes
+pub fn claim() -> BountyClaim {
+    BountyClaim {
+        reporter: Pubkey::default(),
+        claim_id: 0,
+        mint: Pubkey::default(),
+        amount: 1000,
+        evidence: Vec::new(),
+        approvals: Vec::new(),
+        rejections: Vec::new(),
+        status: BountyClaimStatus::Pending,
+        created_at: 0,
+        bump: 0,
+    }
+}
+
+pub fn committee(size: usize) -> Vec<Pubkey> {
+    (0..size).map(|_| Pubkey::new_unique()).collect()
+}
+
+/// Batch order escrowing a 1% protocol fee alongside `amount_in`
+pub fn order(sells_base: bool, amount_in: u64, min_out: u64) -> BatchOrder {
+    BatchOrder {
+        owner: Pubkey::new_unique(),
+        recipient: Pubkey::new_unique(),
+        sells_base,
+        amount_in,
+        protocol_fee: amount_in / 100,
+        min_out,
+        claimed: false,
+    }
+}
+
+/// Unsettled batch holding `orders`
+pub fn batch(orders: Vec<BatchOrder>) -> Batch {
+    Batch {
+        base_mint: Pubkey::new_unique(),
+        quote_mint: Pubkey::new_unique(),
+        epoch: 0,
+        settled: false,
+        price_num: 0,
+        price_den: 0,
+        base_eligible: 0,
+        base_filled: 0,
+        quote_eligible: 0,
+        quote_filled: 0,
+        orders,
+        bump: 0,
+    }
+}
+
+/// Oracle price quoted with eight decimals
+pub fn mint_price(price: u64, decimals: u8, max_deviation_bps: u16) -> MintPrice {
+    MintPrice {
+        price: Some(OraclePrice { price, expo: -8, publish_time: 0 }),
+        decimals,
+        max_deviation_bps,
+    }
+}
@@ -1,54 +1,303 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
-import { Keypair, PublicKey, SystemProgram, SYSVAR_INSTRUCTIONS_PUBKEY } from "@solana/web3.js";
-import { 
-  TOKEN_PROGRAM_ID, 
-  createMint, 
-  createAssociatedTokenAccount, 
+import {
+  Ed25519Program,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  Transaction,
+} from "@solana/web3.js";
+import {
+  TOKEN_PROGRAM_ID,
+  createMint,
+  createAssociatedTokenAccount,
   mintTo,
-  getAssociatedTokenAddress 
+  approve,
+  getAssociatedTokenAddressSync,
 } from "@solana/spl-token";
+import * as ed from "@noble/ed25519";
 import { assert } from "chai";
 import * as crypto from "crypto";
 import { Unikron } from "../target/types/unikron";
 
 describe("unikron", () => {
   // Configure the client to use the local cluster
-  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
   const program = anchor.workspace.Unikron as Program<Unikron>;
-  const provider = anchor.getProvider();
 
   // Test accounts
   let user: Keypair;
+  // Counterparty of `user` in matches and batches
+  let user2: Keypair;
+  // Mint authority and the relayer filling intents from its own inventory
   let payer: Keypair;
   let tokenMintA: PublicKey;
   let tokenMintB: PublicKey;
   let userTokenAccountA: PublicKey;
   let userTokenAccountB: PublicKey;
-  let feeAccount: PublicKey;
-  let treasuryAccount: PublicKey;
-  let stakersAccount: PublicKey;
-  let bountyAccount: PublicKey;
+
+  const [configPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("config")],
+    program.programId
+  );
+  const [denylistPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("denylist")],
+    program.programId
+  );
+  const [stakePoolPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("stake_pool")],
+    program.programId
+  );
+  const [stakeVaultPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("stake_vault")],
+    program.programId
+  );
+
+  // Fee accounts of one mint, all PDAs created by initialize_mint_fees and
+  // initialize_reward_pool
+  const feePdas = (mint: PublicKey) => {
+    const derive = (seed: string) =>
+      PublicKey.findProgramAddressSync([Buffer.from(seed), mint.toBuffer()], program.programId)[0];
+    return {
+      authority: derive("fee_authority"),
+      collection: derive("fee_collection"),
+      ledger: derive("fee_ledger"),
+      stakers: derive("liq_stakers"),
+      treasury: derive("treasury"),
+      bounty: derive("mev_bounty"),
+      rewardPool: derive("reward_pool"),
+    };
+  };
+
+  const intentPda = (owner: PublicKey, nonce: anchor.BN) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("intent"), owner.toBuffer(), Buffer.from(nonce.toArray("le", 8))],
+      program.programId
+    )[0];
+  const userStatsPda = (owner: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("user_stats"), owner.toBuffer()],
+      program.programId
+    )[0];
+  const mintConfigPda = (mint: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("mint_config"), mint.toBuffer()],
+      program.programId
+    )[0];
+  const relayerPda = (relayer: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("relayer"), relayer.toBuffer()],
+      program.programId
+    )[0];
+
+  const ata = (mint: PublicKey, owner: PublicKey) => getAssociatedTokenAddressSync(mint, owner);
+
+  const tokenBalance = async (account: PublicKey): Promise<number> =>
+    Number((await provider.connection.getTokenAccountBalance(account)).value.amount);
+
+  // Pyth price account configured for each mint, filled in by the oracle test
+  const oracles = new Map<string, PublicKey>();
+  const oracleOf = (mint: PublicKey) => oracles.get(mint.toBase58()) ?? null;
 
   // Test data
-  const nonce = new anchor.BN(12345);
   const expiry = new anchor.BN(Math.floor(Date.now() / 1000) + 3600); // 1 hour from now
   const amountIn = new anchor.BN(1000000); // 1 token (6 decimals)
   const minOut = new anchor.BN(900000); // 0.9 tokens minimum
   const relayerFee = new anchor.BN(1000); // 0.001 tokens
+  let nextNonce = 1;
+
+  // Borsh-encode a TradeIntentData exactly as the program hashes it
+  const encodeIntent = (intent: any): Buffer =>
+    program.coder.types.encode("tradeIntentData", intent);
+
+  // Standard intent of `owner` selling 1 A for at least 0.9 B through
+  // `payer`, with `overrides` applied
+  const newIntent = (owner: Keypair, overrides: any = {}) => ({
+    user: owner.publicKey,
+    nonce: new anchor.BN(nextNonce++),
+    expiry,
+    relayer: payer.publicKey,
+    relayerFee: new anchor.BN(0),
+    relayerFeeToken: { tokenOut: {} },
+    tokenIn: tokenMintA,
+    tokenOut: tokenMintB,
+    amountIn,
+    minOut,
+    quotedOut: new anchor.BN(0),
+    recipient: owner.publicKey,
+    referrer: PublicKey.default,
+    referralBps: 0,
+    exclusivitySlots: new anchor.BN(0),
+    kind: { standard: {} },
+    allowPartial: false,
+    route: [],
+    ...overrides,
+  });
+
+  interface Committed {
+    intent: any;
+    owner: Keypair;
+    swapIntent: PublicKey;
+    hash: number[];
+    signature: number[];
+  }
+
+  // Commit an intent's hash and sign the hash as the owner's wallet does
+  const commit = async (owner: Keypair, intent: any): Promise<Committed> => {
+    const hash = crypto.createHash("sha256").update(encodeIntent(intent)).digest();
+    const swapIntent = intentPda(owner.publicKey, intent.nonce);
+
+    await program.methods
+      .commitTrade(Array.from(hash), intent.nonce, intent.expiry)
+      .accountsStrict({
+        swapIntent,
+        user: owner.publicKey,
+        denylist: denylistPda,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([owner])
+      .rpc();
+
+    const signature = await ed.signAsync(hash, owner.secretKey.slice(0, 32));
+    return {
+      intent,
+      owner,
+      swapIntent,
+      hash: Array.from(hash),
+      signature: Array.from(signature),
+    };
+  };
+
+  // Ed25519 program instruction verifying the owner's signature of the hash
+  const ed25519Ix = (committed: Committed) =>
+    Ed25519Program.createInstructionWithPublicKey({
+      publicKey: committed.owner.publicKey.toBytes(),
+      message: Buffer.from(committed.hash),
+      signature: Uint8Array.from(committed.signature),
+    });
+
+  // Every account of a reveal_trade of `committed` by `relayer`, with the
+  // optional relayer registration, bounty and referral accounts left out
+  const revealAccounts = (committed: Committed, relayer: PublicKey = payer.publicKey) => {
+    const { intent } = committed;
+    const fees = feePdas(intent.tokenIn);
+    return {
+      swapIntent: committed.swapIntent,
+      user: intent.user,
+      instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      config: configPda,
+      denylist: denylistPda,
+      userStats: userStatsPda(intent.user),
+      userTokenInAccount: ata(intent.tokenIn, intent.user),
+      userTokenOutAccount: ata(intent.tokenOut, intent.recipient),
+      relayerTokenInAccount: ata(intent.tokenIn, relayer),
+      relayerTokenOutAccount: ata(intent.tokenOut, relayer),
+      relayer,
+      relayerRegistration: null,
+      tokenInMint: intent.tokenIn,
+      tokenOutMint: intent.tokenOut,
+      tokenInConfig: mintConfigPda(intent.tokenIn),
+      tokenOutConfig: mintConfigPda(intent.tokenOut),
+      tokenInOracle: oracleOf(intent.tokenIn),
+      tokenOutOracle: oracleOf(intent.tokenOut),
+      feeCollectionAccount: fees.collection,
+      feeLedger: fees.ledger,
+      bountyAccount: null,
+      referrerTokenAccount: null,
+      referrerStats: null,
+      feeCollectionAuthority: fees.authority,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    };
+  };
+
+  interface RevealOptions {
+    fillAmount?: anchor.BN;
+    relayer?: Keypair;
+    // Overrides of `revealAccounts`
+    accounts?: any;
+    remainingAccounts?: anchor.web3.AccountMeta[];
+  }
+
+  // Reveal `committed` behind the ed25519 instruction it is checked against,
+  // filling all of it unless `fillAmount` is given
+  const reveal = async (committed: Committed, options: RevealOptions = {}) => {
+    const relayer = options.relayer ?? payer;
+    const revealIx = await program.methods
+      .revealTrade(
+        committed.intent,
+        committed.hash,
+        committed.signature,
+        options.fillAmount ?? committed.intent.amountIn
+      )
+      .accountsStrict({ ...revealAccounts(committed, relayer.publicKey), ...options.accounts })
+      .remainingAccounts(options.remainingAccounts ?? [])
+      .instruction();
+
+    const tx = new Transaction().add(ed25519Ix(committed), revealIx);
+    return provider.sendAndConfirm(tx, [committed.owner, relayer]);
+  };
+
+  // Execute the next slice of a recurring intent. Only the relayer signs;
+  // the user has approved the intent PDA as delegate of their input
+  const executeSlice = async (committed: Committed, accounts: any = {}) => {
+    const { bountyAccount, referrerTokenAccount, referrerStats, ...sliceAccounts } =
+      revealAccounts(committed);
+    const sliceIx = await program.methods
+      .executeSlice(committed.intent, committed.hash, committed.signature)
+      .accountsStrict({ ...sliceAccounts, ...accounts })
+      .instruction();
+
+    const tx = new Transaction().add(ed25519Ix(committed), sliceIx);
+    return provider.sendAndConfirm(tx, [payer]);
+  };
+
+  // Assert that `action` fails with the program error `code`
+  const expectError = async (action: Promise<unknown>, code: string) => {
+    let failure: any;
+    try {
+      await action;
+    } catch (error) {
+      failure = error;
+    }
+    assert.exists(failure, `Expected ${code}`);
+    const logs: string[] = failure.logs ?? [];
+    assert.include([failure.message, ...logs].join("\n"), code);
+  };
+
+  // Poll until the cluster reaches `slot`
+  const waitForSlot = async (slot: number) => {
+    while ((await provider.connection.getSlot()) < slot) {
+      await new Promise((resolve) => setTimeout(resolve, 200));
+    }
+  };
+
+  // Poll until the cluster clock reaches `unixTime`, read from the clock
+  // sysvar's `unix_timestamp`
+  const waitForTime = async (unixTime: number) => {
+    const clockTime = async () => {
+      const clock = await provider.connection.getAccountInfo(anchor.web3.SYSVAR_CLOCK_PUBKEY);
+      return new anchor.BN(clock.data.subarray(32, 40), "le").toNumber();
+    };
+    while ((await clockTime()) < unixTime) {
+      await new Promise((resolve) => setTimeout(resolve, 500));
+    }
+  };
 
   before(async () => {
     // Initialize keypairs
     user = Keypair.generate();
+    user2 = Keypair.generate();
     payer = Keypair.generate();
 
     // Airdrop SOL to accounts
-    await provider.connection.confirmTransaction(
-      await provider.connection.requestAirdrop(user.publicKey, 2 * anchor.web3.LAMPORTS_PER_SOL)
-    );
-    await provider.connection.confirmTransaction(
-      await provider.connection.requestAirdrop(payer.publicKey, 2 * anchor.web3.LAMPORTS_PER_SOL)
-    );
+    for (const wallet of [user, user2, payer]) {
+      await provider.connection.confirmTransaction(
+        await provider.connection.requestAirdrop(wallet.publicKey, 2 * anchor.web3.LAMPORTS_PER_SOL)
+      );
+    }
 
     // Create token mints
     tokenMintA = await createMint(
@@ -67,426 +316,1550 @@ describe("unikron", () => {
       6
     );
 
-    // Create associated token accounts
-    userTokenAccountA = await createAssociatedTokenAccount(
+    // Token accounts of both mints for the users and the relayer
+    for (const wallet of [user, user2, payer]) {
+      for (const mint of [tokenMintA, tokenMintB]) {
+        await createAssociatedTokenAccount(provider.connection, payer, mint, wallet.publicKey);
+      }
+    }
+    userTokenAccountA = ata(tokenMintA, user.publicKey);
+    userTokenAccountB = ata(tokenMintB, user.publicKey);
+
+    // Mint tokens to the users and relayer inventory
+    await mintTo(
       provider.connection,
       payer,
       tokenMintA,
-      user.publicKey
-    );
-
-    userTokenAccountB = await createAssociatedTokenAccount(
-      provider.connection,
+      userTokenAccountA,
       payer,
-      tokenMintB,
-      user.publicKey
+      1000000000 // 1000 tokens
     );
+    await mintTo(provider.connection, payer, tokenMintB, ata(tokenMintB, user2.publicKey), payer, 1000000000);
+    for (const mint of [tokenMintA, tokenMintB]) {
+      await mintTo(provider.connection, payer, mint, ata(mint, payer.publicKey), payer, 1000000000);
+    }
 
-    // Create fee distribution accounts
-    feeAccount = await createAssociatedTokenAccount(
-      provider.connection,
-      payer,
-      tokenMintA,
-      payer.publicKey
-    );
+    // Protocol config and the (empty) denylist every commit checks against.
+    // Only the upgrade authority may create the config and become admin.
+    const initializeProtocol = (admin: PublicKey, signers: Keypair[] = []) =>
+      program.methods
+        .initializeProtocol()
+        .accountsStrict({
+          config: configPda,
+          admin,
+          program: program.programId,
+          programData: PublicKey.findProgramAddressSync(
+            [program.programId.toBuffer()],
+            new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+          )[0],
+          systemProgram: SystemProgram.programId,
+        })
+        .signers(signers)
+        .rpc();
+    await expectError(initializeProtocol(user.publicKey, [user]), "Unauthorized");
+    await initializeProtocol(provider.publicKey);
 
-    treasuryAccount = await createAssociatedTokenAccount(
-      provider.connection,
-      payer,
-      tokenMintA,
-      payer.publicKey
-    );
+    await program.methods
+      .initializeDenylist()
+      .accountsStrict({
+        config: configPda,
+        denylist: denylistPda,
+        admin: provider.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  });
 
-    stakersAccount = await createAssociatedTokenAccount(
-      provider.connection,
-      payer,
-      tokenMintA,
-      payer.publicKey
-    );
+  it("Initializes fee accounts and reward pools per mint", async () => {
+    await program.methods
+      .initializeStakePool()
+      .accountsStrict({
+        config: configPda,
+        stakePool: stakePoolPda,
+        stakeVault: stakeVaultPda,
+        stakeMint: tokenMintB,
+        admin: provider.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
 
-    bountyAccount = await createAssociatedTokenAccount(
-      provider.connection,
-      payer,
-      tokenMintA,
-      payer.publicKey
-    );
+    for (const mint of [tokenMintA, tokenMintB]) {
+      const fees = feePdas(mint);
+      const accounts = {
+        feeCollectionAuthority: fees.authority,
+        liquidityStakerAccount: fees.stakers,
+        treasuryAccount: fees.treasury,
+        bountyAccount: fees.bounty,
+        feeCollectionAccount: fees.collection,
+        feeLedger: fees.ledger,
+        tokenMint: mint,
+        payer: provider.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      };
 
-    // Mint tokens to user
-    await mintTo(
-      provider.connection,
-      payer,
-      tokenMintA,
-      userTokenAccountA,
-      payer,
-      10000000 // 10 tokens
-    );
+      // Initialization is idempotent
+      await program.methods.initializeMintFees().accountsStrict(accounts).rpc();
+      await program.methods.initializeMintFees().accountsStrict(accounts).rpc();
 
-    // Mint some tokens to fee account for testing
-    await mintTo(
-      provider.connection,
-      payer,
-      tokenMintA,
-      feeAccount,
-      payer,
-      1000000 // 1 token for fees
-    );
+      await program.methods
+        .initializeRewardPool()
+        .accountsStrict({
+          config: configPda,
+          stakePool: stakePoolPda,
+          rewardPool: fees.rewardPool,
+          feeMint: mint,
+          admin: provider.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const ledger = await program.account.feeLedger.fetch(fees.ledger);
+      assert(ledger.mint.equals(mint));
+      assert.equal(ledger.totalCollected.toNumber(), 0);
+
+      const collection = await provider.connection.getParsedAccountInfo(fees.collection);
+      assert.equal((collection.value.data as any).parsed.info.owner, fees.authority.toBase58());
+    }
+
+    const stakePool = await program.account.stakePool.fetch(stakePoolPda);
+    assert.equal(stakePool.rewardMints.length, 2);
+    assert(stakePool.rewardMints[0].equals(tokenMintA));
   });
 
-  it("Creates a trade intent commitment", async () => {
-    // Create trade intent data
-    const tradeIntent = {
-      user: user.publicKey,
-      nonce: nonce,
-      expiry: expiry,
-      relayer: payer.publicKey,
-      relayerFee: relayerFee,
-      tokenIn: tokenMintA,
-      tokenOut: tokenMintB,
-      amountIn: amountIn,
-      minOut: minOut,
-    };
+  it("Configures Pyth oracles for traded mints", async () => {
+    // Fake Pyth price accounts loaded from tests/fixtures via Anchor.toml
+    const oracleA = new PublicKey("FDmVyPx9hUjV6SAHUxyU2PPMKBvHu4UyemT4kkB3BxJZ");
+    const oracleB = new PublicKey("6nmvGNg8WbXQPXeGssvDRnZZNSF5jQZNVPHQ6384iTwJ");
+    const maxDeviationBps = 100;
+    // Fixture prices carry a fixed publish time, so allow a very old price
+    const maxStalenessSecs = new anchor.BN(100 * 365 * 24 * 3600);
 
-    // Serialize and hash the trade intent
-    const serialized = Buffer.concat([
-      user.publicKey.toBuffer(),
-      Buffer.from(nonce.toArray("le", 8)),
-      Buffer.from(expiry.toArray("le", 8)),
-      payer.publicKey.toBuffer(),
-      Buffer.from(relayerFee.toArray("le", 8)),
-      tokenMintA.toBuffer(),
-      tokenMintB.toBuffer(),
-      Buffer.from(amountIn.toArray("le", 8)),
-      Buffer.from(minOut.toArray("le", 8)),
-    ]);
-
-    const intentHash = crypto.createHash('sha256').update(serialized).digest();
-    const intentHashArray = Array.from(intentHash);
-
-    // Derive PDA
-    const [swapIntentPda] = PublicKey.findProgramAddressSync(
-      [
-        Buffer.from("intent"),
-        user.publicKey.toBuffer(),
-        Buffer.from(nonce.toArray("le", 8))
-      ],
-      program.programId
-    );
+    const oracleInfo = await provider.connection.getAccountInfo(oracleA);
+    assert.equal(oracleInfo.data.readUInt32LE(0), 0xa1b2c3d4);
 
-    // Commit trade
-    const tx = await program.methods
-      .commitTrade(intentHashArray, nonce, expiry)
+    for (const [mint, oracle] of [[tokenMintA, oracleA], [tokenMintB, oracleB]]) {
+      await program.methods
+        .initializeMintConfig(oracle, maxDeviationBps, maxStalenessSecs)
+        .accountsStrict({
+          config: configPda,
+          mintConfig: mintConfigPda(mint),
+          mint,
+          admin: provider.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const mintConfig = await program.account.mintConfig.fetch(mintConfigPda(mint));
+      assert(mintConfig.oracle.equals(oracle));
+      assert.equal(mintConfig.maxDeviationBps, maxDeviationBps);
+      oracles.set(mint.toBase58(), oracle);
+    }
+  });
+
+  it("Sets fee tiers", async () => {
+    const feeTiers = [
+      { minVolume: new anchor.BN(100_000_000_000), discountBps: 2000 },
+      { minVolume: new anchor.BN(1_000_000_000_000), discountBps: 5000 },
+    ];
+
+    await program.methods
+      .setFeeTiers(feeTiers)
       .accountsStrict({
-        swapIntent: swapIntentPda,
-        user: user.publicKey,
-        systemProgram: SystemProgram.programId,
+        config: configPda,
+        admin: provider.publicKey,
       })
-      .signers([user])
       .rpc();
 
-    console.log("Commit transaction signature:", tx);
+    const config = await program.account.config.fetch(configPda);
+    assert.equal(config.feeTiers.length, 2);
+    assert.equal(config.feeTiers[1].discountBps, 5000);
+  });
+
+  it("Creates a trade intent commitment", async () => {
+    const tradeIntent = newIntent(user, { relayerFee });
+    const committed = await commit(user, tradeIntent);
 
     // Verify the committed data
-    const swapIntentAccount = await program.account.swapIntent.fetch(swapIntentPda);
+    const swapIntentAccount = await program.account.swapIntent.fetch(committed.swapIntent);
     assert(swapIntentAccount.user.equals(user.publicKey));
-    assert(swapIntentAccount.nonce.eq(nonce));
+    assert(swapIntentAccount.nonce.eq(tradeIntent.nonce));
     assert(swapIntentAccount.expiry.eq(expiry));
-    assert.deepEqual(Array.from(swapIntentAccount.intentHash), intentHashArray);
+    assert.deepEqual(Array.from(swapIntentAccount.intentHash), committed.hash);
     assert.equal(swapIntentAccount.revealed, false);
   });
 
   it("Reveals and executes trade", async () => {
-    // Create trade intent data (same as before)
-    const tradeIntent = {
-      user: user.publicKey,
-      nonce: nonce,
-      expiry: expiry,
-      relayer: payer.publicKey,
-      relayerFee: relayerFee,
-      tokenIn: tokenMintA,
-      tokenOut: tokenMintB,
-      amountIn: amountIn,
-      minOut: minOut,
-    };
+    const tradeIntent = newIntent(user, { relayerFee });
+    const committed = await commit(user, tradeIntent);
+    const fees = feePdas(tokenMintA);
 
-    // Hash the intent
-    const serialized = Buffer.concat([
-      user.publicKey.toBuffer(),
-      Buffer.from(nonce.toArray("le", 8)),
-      Buffer.from(expiry.toArray("le", 8)),
-      payer.publicKey.toBuffer(),
-      Buffer.from(relayerFee.toArray("le", 8)),
-      tokenMintA.toBuffer(),
-      tokenMintB.toBuffer(),
-      Buffer.from(amountIn.toArray("le", 8)),
-      Buffer.from(minOut.toArray("le", 8)),
-    ]);
-
-    const intentHash = crypto.createHash('sha256').update(serialized).digest();
-    const intentHashArray = Array.from(intentHash);
-
-    // Mock signature (64 bytes)
-    const mockSignature = new Array(64).fill(0);
-
-    // Derive PDA
-    const [swapIntentPda] = PublicKey.findProgramAddressSync(
-      [
-        Buffer.from("intent"),
-        user.publicKey.toBuffer(),
-        Buffer.from(nonce.toArray("le", 8))
-      ],
-      program.programId
-    );
+    const userInBefore = await tokenBalance(userTokenAccountA);
+    const userOutBefore = await tokenBalance(userTokenAccountB);
+    const feesBefore = await tokenBalance(fees.collection);
 
-    // Create ed25519 verify instruction first
-    const ed25519Instruction = anchor.web3.Ed25519Program.createInstructionWithPublicKey({
-      publicKey: user.publicKey.toBytes(),
-      message: serialized,
-      signature: new Uint8Array(mockSignature),
-    });
+    const txSig = await reveal(committed);
+    console.log("Reveal transaction signature:", txSig);
+
+    // Verify the trade was revealed
+    const swapIntentAccount = await program.account.swapIntent.fetch(committed.swapIntent);
+    assert.equal(swapIntentAccount.revealed, true);
+    assert(swapIntentAccount.filledIn.eq(amountIn));
+
+    // 0.1% protocol fee on the input; the relayer fee is withheld from the
+    // 1:1 output
+    assert.equal(userInBefore - (await tokenBalance(userTokenAccountA)), 1000000);
+    assert.equal((await tokenBalance(fees.collection)) - feesBefore, 1000);
+    assert.equal((await tokenBalance(userTokenAccountB)) - userOutBefore, 999000 - 1000);
+
+    // The first fill creates the user's stats
+    const stats = await program.account.userStats.fetch(userStatsPda(user.publicKey));
+    assert(stats.user.equals(user.publicKey));
+    assert.equal(stats.tradeCount.toNumber(), 1);
+  });
 
-    // Reveal trade with ed25519 instruction
-    const tx = new anchor.web3.Transaction();
-    tx.add(ed25519Instruction);
-    
+  it("Rejects reveals without the user's signature of the intent hash", async () => {
+    const committed = await commit(user, newIntent(user));
+
+    // A valid signature by another key does not authorise the intent
+    const forgedSignature = await ed.signAsync(Buffer.from(committed.hash), payer.secretKey.slice(0, 32));
     const revealIx = await program.methods
-      .revealTrade(tradeIntent, intentHashArray, mockSignature)
-      .accounts({
-        swapIntent: swapIntentPda,
-        user: user.publicKey,
-        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
-      })
+      .revealTrade(committed.intent, committed.hash, Array.from(forgedSignature), amountIn)
+      .accountsStrict(revealAccounts(committed))
       .instruction();
-    
-    tx.add(revealIx);
+    const forgedIx = Ed25519Program.createInstructionWithPublicKey({
+      publicKey: payer.publicKey.toBytes(),
+      message: Buffer.from(committed.hash),
+      signature: forgedSignature,
+    });
 
-    const txSig = await provider.sendAndConfirm(tx, [user]);
-    console.log("Reveal transaction signature:", txSig);
+    await expectError(
+      provider.sendAndConfirm(new Transaction().add(forgedIx, revealIx), [user, payer]),
+      "InvalidSignature"
+    );
 
-    // Verify the trade was revealed
-    const swapIntentAccount = await program.account.swapIntent.fetch(swapIntentPda);
-    assert.equal(swapIntentAccount.revealed, true);
+    // Nor does a reveal with no ed25519 instruction before it
+    await expectError(
+      program.methods
+        .revealTrade(committed.intent, committed.hash, committed.signature, amountIn)
+        .accountsStrict(revealAccounts(committed))
+        .signers([user, payer])
+        .rpc(),
+      "InvalidSignature"
+    );
   });
 
+  // Accounts of an admin settle_trade distributing fees of `mint`
+  const settleAccounts = (mint: PublicKey) => {
+    const fees = feePdas(mint);
+    return {
+      config: configPda,
+      feeCollectionAuthority: fees.authority,
+      sourceFeeAccount: fees.collection,
+      feeLedger: fees.ledger,
+      stakePool: stakePoolPda,
+      rewardPool: fees.rewardPool,
+      liquidityStakerAccount: fees.stakers,
+      treasuryAccount: fees.treasury,
+      bountyAccount: fees.bounty,
+      tokenMint: mint,
+      caller: provider.publicKey,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+  };
+
   it("Distributes fees correctly", async () => {
-    const feeAmount = new anchor.BN(10000); // 0.01 tokens
+    // The protocol fee of the reveal above
+    const feeAmount = new anchor.BN(1000);
+    const fees = feePdas(tokenMintA);
 
     // Get initial balances
-    const initialTreasuryBalance = await provider.connection.getTokenAccountBalance(treasuryAccount);
-    const initialStakersBalance = await provider.connection.getTokenAccountBalance(stakersAccount);
-    const initialBountyBalance = await provider.connection.getTokenAccountBalance(bountyAccount);
+    const initialSource = await tokenBalance(fees.collection);
+    const initialTreasury = await tokenBalance(fees.treasury);
+    const initialStakers = await tokenBalance(fees.stakers);
+    const initialBounty = await tokenBalance(fees.bounty);
 
-    // Settle fee
+    // Settle fee out of the fee collection PDA
     const tx = await program.methods
       .settleTrade(feeAmount)
-      .accountsStrict({
-        payer: payer.publicKey,
-        sourceFeeAccount: feeAccount,
-        liquidityStakerAccount: stakersAccount,
-        treasuryAccount: treasuryAccount,
-        bountyAccount: bountyAccount,
-        tokenProgram: TOKEN_PROGRAM_ID,
-      })
-      .signers([payer])
+      .accountsStrict(settleAccounts(tokenMintA))
       .rpc();
 
     console.log("Fee settlement transaction signature:", tx);
 
-    // Verify fee distribution
-    const finalTreasuryBalance = await provider.connection.getTokenAccountBalance(treasuryAccount);
-    const finalStakersBalance = await provider.connection.getTokenAccountBalance(stakersAccount);
-    const finalBountyBalance = await provider.connection.getTokenAccountBalance(bountyAccount);
-
     // Calculate expected distributions
     const expectedTreasuryFee = feeAmount.toNumber() * 30 / 100; // 30%
     const expectedStakersFee = feeAmount.toNumber() * 50 / 100;  // 50%
     const expectedBountyFee = feeAmount.toNumber() - expectedTreasuryFee - expectedStakersFee; // 20%
 
-    assert.equal(
-      finalTreasuryBalance.value.uiAmount - initialTreasuryBalance.value.uiAmount,
-      expectedTreasuryFee / 1000000 // Convert to UI amount
-    );
-    assert.equal(
-      finalStakersBalance.value.uiAmount - initialStakersBalance.value.uiAmount,
-      expectedStakersFee / 1000000
-    );
-    assert.equal(
-      finalBountyBalance.value.uiAmount - initialBountyBalance.value.uiAmount,
-      expectedBountyFee / 1000000
-    );
+    // Verify fee distribution
+    assert.equal(initialSource - (await tokenBalance(fees.collection)), feeAmount.toNumber());
+    assert.equal((await tokenBalance(fees.treasury)) - initialTreasury, expectedTreasuryFee);
+    assert.equal((await tokenBalance(fees.stakers)) - initialStakers, expectedStakersFee);
+    assert.equal((await tokenBalance(fees.bounty)) - initialBounty, expectedBountyFee);
+
+    // Only the admin may pick an amount to settle
+    try {
+      await program.methods
+        .settleTrade(feeAmount)
+        .accountsStrict({ ...settleAccounts(tokenMintA), caller: payer.publicKey })
+        .signers([payer])
+        .rpc();
+      assert.fail("Should have rejected a settlement by a non-admin");
+    } catch (error) {
+      assert(error.message.includes("Unauthorized"));
+    }
   });
 
   it("Full flow: commit -> reveal -> settle", async () => {
-    const testNonce = new anchor.BN(54321);
-    const testExpiry = new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
-    
     // 1. Create and commit intent
-    const tradeIntent = {
-      user: user.publicKey,
-      nonce: testNonce,
-      expiry: testExpiry,
-      relayer: payer.publicKey,
-      relayerFee: relayerFee,
-      tokenIn: tokenMintA,
-      tokenOut: tokenMintB,
-      amountIn: amountIn,
-      minOut: minOut,
-    };
+    const committed = await commit(user, newIntent(user, { relayerFee }));
+
+    // 2. Reveal
+    await reveal(committed);
+
+    // 3. Settle the fee the reveal collected
+    const feeAmount = new anchor.BN(1000);
+    await program.methods
+      .settleTrade(feeAmount)
+      .accountsStrict(settleAccounts(tokenMintA))
+      .rpc();
+
+    console.log("✅ Full flow completed successfully");
+  });
+
+  it("Tracks collected and distributed fees in the ledger", async () => {
+    const fees = feePdas(tokenMintA);
+    const ledgerBefore = await program.account.feeLedger.fetch(fees.ledger);
+    const balanceBefore = await tokenBalance(fees.collection);
+
+    // A reveal records its protocol fee as collected
+    await reveal(await commit(user, newIntent(user)));
 
-    const serialized = Buffer.concat([
-      user.publicKey.toBuffer(),
-      Buffer.from(testNonce.toArray("le", 8)),
-      Buffer.from(testExpiry.toArray("le", 8)),
-      payer.publicKey.toBuffer(),
-      Buffer.from(relayerFee.toArray("le", 8)),
-      tokenMintA.toBuffer(),
-      tokenMintB.toBuffer(),
-      Buffer.from(amountIn.toArray("le", 8)),
-      Buffer.from(minOut.toArray("le", 8)),
-    ]);
-
-    const intentHash = crypto.createHash('sha256').update(serialized).digest();
-    const intentHashArray = Array.from(intentHash);
-
-    const [testSwapIntentPda] = PublicKey.findProgramAddressSync(
+    const collected = await program.account.feeLedger.fetch(fees.ledger);
+    assert.equal(collected.totalCollected.sub(ledgerBefore.totalCollected).toNumber(), 1000);
+    assert(collected.totalDistributed.eq(ledgerBefore.totalDistributed));
+    assert.equal((await tokenBalance(fees.collection)) - balanceBefore, 1000);
+
+    // A settlement records what leaves the fee collection as distributed
+    await program.methods
+      .settleTrade(new anchor.BN(1000))
+      .accountsStrict(settleAccounts(tokenMintA))
+      .rpc();
+
+    const distributed = await program.account.feeLedger.fetch(fees.ledger);
+    assert(distributed.totalCollected.eq(collected.totalCollected));
+    assert.equal(distributed.totalDistributed.sub(collected.totalDistributed).toNumber(), 1000);
+    assert.equal(await tokenBalance(fees.collection), balanceBefore);
+  });
+
+  it("Rejects expired intents", async () => {
+    const expiredExpiry = new anchor.BN(Math.floor(Date.now() / 1000) - 3600); // 1 hour ago
+
+    // Commit expired intent
+    const committed = await commit(user, newIntent(user, { expiry: expiredExpiry }));
+
+    // Try to reveal - should fail
+    await expectError(reveal(committed), "IntentExpired");
+    console.log("✅ Correctly rejected expired intent");
+  });
+
+  it("Registers a relayer for open intents", async () => {
+    const [registrationPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("relayer"), payer.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .registerRelayer()
+      .accountsStrict({
+        config: configPda,
+        relayerRegistration: registrationPda,
+        relayer: payer.publicKey,
+        admin: provider.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const registration = await program.account.relayerRegistration.fetch(registrationPda);
+    assert(registration.relayer.equals(payer.publicKey));
+    assert.equal(registration.active, true);
+
+    await program.methods
+      .setRelayerStatus(false)
+      .accountsStrict({
+        config: configPda,
+        relayerRegistration: registrationPda,
+        admin: provider.publicKey,
+      })
+      .rpc();
+
+    const deactivated = await program.account.relayerRegistration.fetch(registrationPda);
+    assert.equal(deactivated.active, false);
+  });
+
+  it("Refuses commits from denylisted wallets", async () => {
+    await program.methods
+      .addToDenylist({ wallet: {} }, user.publicKey)
+      .accountsStrict({
+        config: configPda,
+        denylist: denylistPda,
+        admin: provider.publicKey,
+      })
+      .rpc();
+
+    const denylist = await program.account.denylist.fetch(denylistPda);
+    assert(denylist.wallets[0].equals(user.publicKey));
+
+    const deniedNonce = new anchor.BN(777);
+    const [deniedSwapIntentPda] = PublicKey.findProgramAddressSync(
       [
         Buffer.from("intent"),
         user.publicKey.toBuffer(),
-        Buffer.from(testNonce.toArray("le", 8))
+        Buffer.from(deniedNonce.toArray("le", 8))
+      ],
+      program.programId
+    );
+
+    try {
+      await program.methods
+        .commitTrade(Array(32).fill(1), deniedNonce, expiry)
+        .accountsStrict({
+          swapIntent: deniedSwapIntentPda,
+          user: user.publicKey,
+          denylist: denylistPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user])
+        .rpc();
+      assert.fail("Should have rejected denylisted wallet");
+    } catch (error) {
+      assert(error.message.includes("Denylisted"));
+    }
+
+    await program.methods
+      .removeFromDenylist({ wallet: {} }, user.publicKey)
+      .accountsStrict({
+        config: configPda,
+        denylist: denylistPda,
+        admin: provider.publicKey,
+      })
+      .rpc();
+  });
+
+  it("Stakes and unstakes into the stake pool", async () => {
+    const [stakePositionPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("stake_position"), user.publicKey.toBuffer()],
+      program.programId
+    );
+
+    // Every reward pool, in the stake pool's registration order
+    const rewardPools = [tokenMintA, tokenMintB].map((mint) => ({
+      pubkey: feePdas(mint).rewardPool,
+      isWritable: true,
+      isSigner: false,
+    }));
+
+    await mintTo(provider.connection, payer, tokenMintB, userTokenAccountB, payer, 2000000);
+
+    const stakeAccounts = {
+      stakePool: stakePoolPda,
+      stakeVault: stakeVaultPda,
+      stakePosition: stakePositionPda,
+      ownerTokenAccount: userTokenAccountB,
+      owner: user.publicKey,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    };
+
+    await program.methods
+      .stake(new anchor.BN(2000000))
+      .accountsStrict(stakeAccounts)
+      .remainingAccounts(rewardPools)
+      .signers([user])
+      .rpc();
+
+    await program.methods
+      .unstake(new anchor.BN(500000))
+      .accountsStrict(stakeAccounts)
+      .remainingAccounts(rewardPools)
+      .signers([user])
+      .rpc();
+
+    const position = await program.account.stakePosition.fetch(stakePositionPda);
+    assert.equal(position.amount.toNumber(), 1500000);
+
+    const stakePool = await program.account.stakePool.fetch(stakePoolPda);
+    assert.equal(stakePool.totalStaked.toNumber(), 1500000);
+  });
+
+  it("Approves bounty claims by committee vote", async () => {
+    await program.methods
+      .setBountyCommittee([provider.publicKey], 1)
+      .accountsStrict({
+        config: configPda,
+        admin: provider.publicKey,
+      })
+      .rpc();
+
+    const claimId = new anchor.BN(1);
+    const [bountyClaimPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("bounty_claim"),
+        user.publicKey.toBuffer(),
+        Buffer.from(claimId.toArray("le", 8))
       ],
       program.programId
     );
 
-    // Commit
     await program.methods
-      .commitTrade(intentHashArray, testNonce, testExpiry)
+      .submitBountyClaim(claimId, new anchor.BN(1000), [
+        { txSignature: Array(64).fill(7), amount: new anchor.BN(50000) },
+      ])
       .accountsStrict({
-        swapIntent: testSwapIntentPda,
-        user: user.publicKey,
+        bountyClaim: bountyClaimPda,
+        mint: tokenMintA,
+        reporter: user.publicKey,
         systemProgram: SystemProgram.programId,
       })
       .signers([user])
       .rpc();
 
-    // 2. Reveal
-    const mockSignature = new Array(64).fill(0);
-    const ed25519Instruction = anchor.web3.Ed25519Program.createInstructionWithPublicKey({
-      publicKey: user.publicKey.toBytes(),
-      message: serialized,
-      signature: new Uint8Array(mockSignature),
+    await program.methods
+      .voteBountyClaim(true)
+      .accountsStrict({
+        config: configPda,
+        bountyClaim: bountyClaimPda,
+        member: provider.publicKey,
+      })
+      .rpc();
+
+    const claim = await program.account.bountyClaim.fetch(bountyClaimPda);
+    assert.deepEqual(claim.status, { approved: {} });
+    assert(claim.approvals[0].equals(provider.publicKey));
+  });
+
+  it("Fills open intents by any active registered relayer", async () => {
+    const committed = await commit(user, newIntent(user, { relayer: PublicKey.default }));
+    const registration = { relayerRegistration: relayerPda(payer.publicKey) };
+
+    // An open intent needs the filler's registration, and an active one
+    await expectError(reveal(committed), "RelayerNotRegistered");
+    await expectError(reveal(committed, { accounts: registration }), "RelayerNotRegistered");
+
+    await program.methods
+      .setRelayerStatus(true)
+      .accountsStrict({
+        config: configPda,
+        relayerRegistration: relayerPda(payer.publicKey),
+        admin: provider.publicKey,
+      })
+      .rpc();
+
+    await reveal(committed, { accounts: registration });
+
+    const swapIntent = await program.account.swapIntent.fetch(committed.swapIntent);
+    assert.equal(swapIntent.revealed, true);
+  });
+
+  it("Opens exclusive intents to registered relayers after the window", async () => {
+    const registration = { relayerRegistration: relayerPda(payer.publicKey) };
+
+    // Only the named relayer may fill inside its window
+    const exclusive = await commit(
+      user,
+      newIntent(user, { relayer: user2.publicKey, exclusivitySlots: new anchor.BN(1000000) })
+    );
+    await expectError(reveal(exclusive, { accounts: registration }), "ExclusivityWindowActive");
+
+    // Once the window has passed, any active registered relayer may
+    const lapsed = await commit(
+      user,
+      newIntent(user, { relayer: user2.publicKey, exclusivitySlots: new anchor.BN(1) })
+    );
+    const { commitSlot } = await program.account.swapIntent.fetch(lapsed.swapIntent);
+    await waitForSlot(commitSlot.toNumber() + 1);
+
+    await reveal(lapsed, { accounts: registration });
+
+    const swapIntent = await program.account.swapIntent.fetch(lapsed.swapIntent);
+    assert.equal(swapIntent.revealed, true);
+  });
+
+  it("Decays the required output of Dutch auction intents", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const dutch = (startTime: number, endTime: number) => ({
+      dutchAuction: {
+        startMinOut: new anchor.BN(1000000),
+        endMinOut: new anchor.BN(950000),
+        startTime: new anchor.BN(startTime),
+        endTime: new anchor.BN(endTime),
+      },
     });
 
-    const revealTx = new anchor.web3.Transaction();
-    revealTx.add(ed25519Instruction);
-    
-    const revealIx = await program.methods
-      .revealTrade(tradeIntent, intentHashArray, mockSignature)
-      .accounts({
-        swapIntent: testSwapIntentPda,
-        user: user.publicKey,
-        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+    // Fully decayed: only the 0.95 end floor applies to the 0.999 output
+    const decayed = await commit(user, newIntent(user, { kind: dutch(now - 7200, now - 3600) }));
+    await reveal(decayed);
+    assert.equal((await program.account.swapIntent.fetch(decayed.swapIntent)).revealed, true);
+
+    // Not started: the full 1.0 start floor applies
+    const pending = await commit(user, newIntent(user, { kind: dutch(now + 1800, now + 3600) }));
+    await expectError(reveal(pending), "SlippageExceeded");
+
+    // The auction must run forwards in time
+    const inverted = await commit(user, newIntent(user, { kind: dutch(now + 3600, now + 1800) }));
+    await expectError(reveal(inverted), "InvalidIntentParams");
+  });
+
+  it("Fills partial intents across several reveals", async () => {
+    const committed = await commit(user, newIntent(user, { allowPartial: true }));
+
+    await reveal(committed, { fillAmount: new anchor.BN(400000) });
+    let swapIntent = await program.account.swapIntent.fetch(committed.swapIntent);
+    assert.equal(swapIntent.filledIn.toNumber(), 400000);
+    assert.equal(swapIntent.revealed, false);
+
+    // A fill may not exceed the unfilled remainder
+    await expectError(reveal(committed, { fillAmount: new anchor.BN(700000) }), "FillExceedsRemaining");
+
+    await reveal(committed, { fillAmount: new anchor.BN(600000) });
+    swapIntent = await program.account.swapIntent.fetch(committed.swapIntent);
+    assert(swapIntent.filledIn.eq(amountIn));
+    assert.equal(swapIntent.revealed, true);
+
+    // Intents that do not allow partial fills must be filled whole
+    const whole = await commit(user, newIntent(user));
+    await expectError(reveal(whole, { fillAmount: new anchor.BN(400000) }), "PartialFillNotAllowed");
+  });
+
+  it("Fills exact-output intents for at most max_in", async () => {
+    const exactOut = (amountOut: number, maxIn: number) =>
+      newIntent(user, {
+        amountIn: new anchor.BN(maxIn),
+        minOut: new anchor.BN(amountOut),
+        kind: { exactOut: { amountOut: new anchor.BN(amountOut), maxIn: new anchor.BN(maxIn) } },
+      });
+
+    const userInBefore = await tokenBalance(userTokenAccountA);
+    const userOutBefore = await tokenBalance(userTokenAccountB);
+
+    // 0.5 B out costs 0.5 A grossed up by the 0.1% fee; the rest of max_in
+    // stays with the user
+    const committed = await commit(user, exactOut(500000, 1000000));
+    await reveal(committed);
+
+    assert.equal(userInBefore - (await tokenBalance(userTokenAccountA)), 500501);
+    assert.equal((await tokenBalance(userTokenAccountB)) - userOutBefore, 500000);
+
+    // The fee pushes the input over a max_in of exactly amount_out
+    const tight = await commit(user, exactOut(500000, 500000));
+    await expectError(reveal(tight), "SlippageExceeded");
+  });
+
+  it("Fills multi-hop routes hop by hop", async () => {
+    const hopMint = await createMint(provider.connection, payer, payer.publicKey, null, 6);
+    const hop = (minOut: number) => ({ mint: hopMint, minOut: new anchor.BN(minOut) });
+    // Intermediate mints are passed in route order as remaining accounts
+    const hopAccounts = [{ pubkey: hopMint, isWritable: false, isSigner: false }];
+
+    const userOutBefore = await tokenBalance(userTokenAccountB);
+    const routed = await commit(user, newIntent(user, { route: [hop(950000)] }));
+    await expectError(reveal(routed), "InvalidTokenMint");
+    await reveal(routed, { remainingAccounts: hopAccounts });
+    assert.equal((await tokenBalance(userTokenAccountB)) - userOutBefore, 999000);
+
+    // Every hop enforces its own floor before the final min_out
+    const tight = await commit(user, newIntent(user, { route: [hop(1000000)] }));
+    await expectError(reveal(tight, { remainingAccounts: hopAccounts }), "HopSlippageExceeded");
+
+    // Loosening a hop minimum after commit breaks the hash
+    const tampered = { ...tight, intent: { ...tight.intent, route: [hop(0)] } };
+    await expectError(reveal(tampered, { remainingAccounts: hopAccounts }), "HashMismatch");
+
+    const tooLong = await commit(user, newIntent(user, { route: [hop(1), hop(1), hop(1), hop(1)] }));
+    await expectError(reveal(tooLong), "RouteTooLong");
+  });
+
+  it("Splits basket intents across several output mints", async () => {
+    const tokenMintC = await createMint(provider.connection, payer, payer.publicKey, null, 6);
+    const userTokenAccountC = await createAssociatedTokenAccount(
+      provider.connection, payer, tokenMintC, user.publicKey
+    );
+    const relayerTokenAccountC = await createAssociatedTokenAccount(
+      provider.connection, payer, tokenMintC, payer.publicKey
+    );
+    await mintTo(provider.connection, payer, tokenMintC, relayerTokenAccountC, payer, 1000000000);
+
+    // A 9-decimal mint that the B oracle prices at $1, so 1:1 fills in base
+    // units are off by a factor of 1000
+    const tokenMintE = await createMint(provider.connection, payer, payer.publicKey, null, 9);
+    await createAssociatedTokenAccount(provider.connection, payer, tokenMintE, user.publicKey);
+    const relayerTokenAccountE = await createAssociatedTokenAccount(
+      provider.connection, payer, tokenMintE, payer.publicKey
+    );
+    await mintTo(provider.connection, payer, tokenMintE, relayerTokenAccountE, payer, 1000000000);
+
+    // Every leg needs a mint config; C and E are priced by the B oracle
+    const oracleB = oracleOf(tokenMintB);
+    for (const mint of [tokenMintC, tokenMintE]) {
+      await program.methods
+        .initializeMintConfig(oracleB, 100, new anchor.BN(100 * 365 * 24 * 3600))
+        .accountsStrict({
+          config: configPda,
+          mintConfig: mintConfigPda(mint),
+          mint,
+          admin: provider.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    }
+
+    const basket = (minOutC: number, mintC = tokenMintC) =>
+      newIntent(user, {
+        minOut: new anchor.BN(590000),
+        kind: {
+          basket: {
+            legs: [
+              { tokenOut: tokenMintB, weightBps: 6000, minOut: new anchor.BN(590000) },
+              { tokenOut: mintC, weightBps: 4000, minOut: new anchor.BN(minOutC) },
+            ],
+          },
+        },
+      });
+
+    // Legs after the first pass the recipient and relayer accounts they are
+    // delivered through, and the mint, mint config and oracle they are
+    // checked against
+    const legAccounts = (mint: PublicKey) =>
+      [ata(mint, user.publicKey), ata(mint, payer.publicKey), mint, mintConfigPda(mint), oracleB].map(
+        (pubkey, i) => ({ pubkey, isWritable: i < 2 || i === 3, isSigner: false })
+      );
+
+    const userOutBefore = await tokenBalance(userTokenAccountB);
+    const committed = await commit(user, basket(390000));
+    await expectError(reveal(committed), "InvalidTokenMint");
+    await expectError(
+      reveal(committed, { remainingAccounts: legAccounts(tokenMintC).slice(0, 2) }),
+      "InvalidTokenMint"
+    );
+    await reveal(committed, { remainingAccounts: legAccounts(tokenMintC) });
+
+    // 0.999 A after fees, split 60/40
+    assert.equal((await tokenBalance(userTokenAccountB)) - userOutBefore, 599400);
+    assert.equal(await tokenBalance(userTokenAccountC), 399600);
+
+    // Every leg enforces its own floor and oracle guard
+    const short = await commit(user, basket(400000));
+    await expectError(reveal(short, { remainingAccounts: legAccounts(tokenMintC) }), "SlippageExceeded");
+    const mispriced = await commit(user, basket(390000, tokenMintE));
+    await expectError(
+      reveal(mispriced, { remainingAccounts: legAccounts(tokenMintE) }),
+      "OraclePriceDeviation"
+    );
+  });
+
+  it("Matches opposing intents peer to peer at the midpoint", async () => {
+    const match = async (a: Committed, b: Committed) => {
+      const feesA = feePdas(tokenMintA);
+      const feesB = feePdas(tokenMintB);
+      const matchIx = await program.methods
+        .matchIntents(a.intent, a.hash, a.signature, b.intent, b.hash, b.signature)
+        .accountsStrict({
+          swapIntentA: a.swapIntent,
+          swapIntentB: b.swapIntent,
+          userA: a.owner.publicKey,
+          userB: b.owner.publicKey,
+          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+          config: configPda,
+          denylist: denylistPda,
+          userAStats: userStatsPda(a.owner.publicKey),
+          userBStats: userStatsPda(b.owner.publicKey),
+          userATokenInAccount: ata(tokenMintA, a.owner.publicKey),
+          userATokenOutAccount: ata(tokenMintB, a.intent.recipient),
+          userBTokenInAccount: ata(tokenMintB, b.owner.publicKey),
+          userBTokenOutAccount: ata(tokenMintA, b.intent.recipient),
+          relayerTokenAAccount: ata(tokenMintA, payer.publicKey),
+          relayerTokenBAccount: ata(tokenMintB, payer.publicKey),
+          relayer: payer.publicKey,
+          relayerRegistration: null,
+          tokenAMint: tokenMintA,
+          tokenBMint: tokenMintB,
+          tokenAConfig: mintConfigPda(tokenMintA),
+          tokenBConfig: mintConfigPda(tokenMintB),
+          tokenAOracle: oracleOf(tokenMintA),
+          tokenBOracle: oracleOf(tokenMintB),
+          feeCollectionAAccount: feesA.collection,
+          feeLedgerA: feesA.ledger,
+          feeCollectionBAccount: feesB.collection,
+          feeLedgerB: feesB.ledger,
+          feeCollectionAAuthority: feesA.authority,
+          feeCollectionBAuthority: feesB.authority,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .instruction();
+
+      const tx = new Transaction().add(ed25519Ix(a), ed25519Ix(b), matchIx);
+      return provider.sendAndConfirm(tx, [a.owner, b.owner, payer]);
+    };
+    const sellB = (minOutA: number) =>
+      newIntent(user2, { tokenIn: tokenMintB, tokenOut: tokenMintA, minOut: new anchor.BN(minOutA) });
+
+    const userOutBefore = await tokenBalance(userTokenAccountB);
+    const user2OutBefore = await tokenBalance(ata(tokenMintA, user2.publicKey));
+
+    const a = await commit(user, newIntent(user, { minOut: new anchor.BN(990000) }));
+    const b = await commit(user2, sellB(990000));
+    await match(a, b);
+
+    // Each side gets at least its limit, and both intents are spent
+    assert.isAtLeast((await tokenBalance(userTokenAccountB)) - userOutBefore, 990000);
+    assert.isAtLeast((await tokenBalance(ata(tokenMintA, user2.publicKey))) - user2OutBefore, 990000);
+    assert.equal((await program.account.swapIntent.fetch(a.swapIntent)).revealed, true);
+    assert.equal((await program.account.swapIntent.fetch(b.swapIntent)).revealed, true);
+
+    // Limits that do not cross cannot be matched
+    const askA = await commit(user, newIntent(user, { minOut: new anchor.BN(1000000) }));
+    const askB = await commit(user2, sellB(1000000));
+    await expectError(match(askA, askB), "IntentsNotCompatible");
+
+    // Both sides from one wallet would share a stats account
+    const selfA = await commit(user, newIntent(user, { minOut: new anchor.BN(990000) }));
+    const selfB = await commit(
+      user,
+      newIntent(user, { tokenIn: tokenMintB, tokenOut: tokenMintA, minOut: new anchor.BN(990000) })
+    );
+    await expectError(match(selfA, selfB), "SelfMatch");
+  });
+
+  it("Settles batch auctions at a uniform clearing price", async () => {
+    const windowSlots = 150;
+    // Pair mints in canonical order
+    const [baseMint, quoteMint] =
+      Buffer.compare(tokenMintA.toBuffer(), tokenMintB.toBuffer()) < 0
+        ? [tokenMintA, tokenMintB]
+        : [tokenMintB, tokenMintA];
+
+    // Batch fills are pro rata, so only intents allowing partial fills enter
+    const seller = await commit(user, newIntent(user, { minOut: new anchor.BN(995000), allowPartial: true }));
+    const buyer = await commit(
+      user2,
+      newIntent(user2, {
+        tokenIn: tokenMintB,
+        tokenOut: tokenMintA,
+        minOut: new anchor.BN(995000),
+        allowPartial: true,
       })
-      .instruction();
-    
-    revealTx.add(revealIx);
-    await provider.sendAndConfirm(revealTx, [user]);
+    );
+    const wholeOrder = await commit(user, newIntent(user, { minOut: new anchor.BN(995000) }));
+
+    // Open the batch early enough in its window to reveal both orders into it
+    while ((await provider.connection.getSlot()) % windowSlots > windowSlots - 50) {
+      await new Promise((resolve) => setTimeout(resolve, 200));
+    }
+    const epoch = new anchor.BN(Math.floor((await provider.connection.getSlot()) / windowSlots));
+    const [batchPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("batch"), baseMint.toBuffer(), quoteMint.toBuffer(), Buffer.from(epoch.toArray("le", 8))],
+      program.programId
+    );
+    const vaultPda = (mint: PublicKey) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("batch_vault"), batchPda.toBuffer(), mint.toBuffer()],
+        program.programId
+      )[0];
 
-    // 3. Settle fees
-    const feeAmount = new anchor.BN(5000);
     await program.methods
-      .settleTrade(feeAmount)
+      .openBatch(epoch)
       .accountsStrict({
-        payer: payer.publicKey,
-        sourceFeeAccount: feeAccount,
-        liquidityStakerAccount: stakersAccount,
-        treasuryAccount: treasuryAccount,
-        bountyAccount: bountyAccount,
+        batch: batchPda,
+        baseVault: vaultPda(baseMint),
+        quoteVault: vaultPda(quoteMint),
+        baseMint,
+        quoteMint,
+        payer: provider.publicKey,
         tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
       })
-      .signers([payer])
       .rpc();
 
-    console.log("✅ Full flow completed successfully");
+    const revealToBatch = async (committed: Committed) => {
+      const { intent } = committed;
+      const revealIx = await program.methods
+        .revealToBatch(intent, committed.hash, committed.signature)
+        .accountsStrict({
+          swapIntent: committed.swapIntent,
+          user: intent.user,
+          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+          config: configPda,
+          denylist: denylistPda,
+          userStats: userStatsPda(intent.user),
+          batch: batchPda,
+          vaultIn: vaultPda(intent.tokenIn),
+          userTokenInAccount: ata(intent.tokenIn, intent.user),
+          tokenInMint: intent.tokenIn,
+          tokenInConfig: mintConfigPda(intent.tokenIn),
+          tokenInOracle: oracleOf(intent.tokenIn),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .instruction();
+      await provider.sendAndConfirm(new Transaction().add(ed25519Ix(committed), revealIx), [committed.owner]);
+    };
+    await expectError(revealToBatch(wholeOrder), "PartialFillNotAllowed");
+    await revealToBatch(seller);
+    await revealToBatch(buyer);
+
+    const settle = () =>
+      program.methods
+        .settleBatch()
+        .accountsStrict({
+          batch: batchPda,
+          baseMint,
+          quoteMint,
+          baseConfig: mintConfigPda(baseMint),
+          quoteConfig: mintConfigPda(quoteMint),
+          baseOracle: oracleOf(baseMint),
+          quoteOracle: oracleOf(quoteMint),
+          caller: payer.publicKey,
+        })
+        .signers([payer])
+        .rpc();
+
+    // Orders clear only once the window has closed
+    await expectError(settle(), "BatchWindowOpen");
+    await waitForSlot((epoch.toNumber() + 1) * windowSlots);
+    await settle();
+
+    // The escrowed protocol fee of the input mint is collected on claim
+    const claim = (owner: Keypair, orderIndex: number, mintIn: PublicKey) =>
+      program.methods
+        .claimBatchFill(orderIndex)
+        .accountsStrict({
+          batch: batchPda,
+          baseVault: vaultPda(baseMint),
+          quoteVault: vaultPda(quoteMint),
+          baseAccount: ata(baseMint, owner.publicKey),
+          quoteAccount: ata(quoteMint, owner.publicKey),
+          feeCollectionAccount: feePdas(mintIn).collection,
+          feeLedger: feePdas(mintIn).ledger,
+          owner: owner.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([owner])
+        .rpc();
+
+    const userBefore = await tokenBalance(userTokenAccountB);
+    const user2Before = await tokenBalance(ata(tokenMintA, user2.publicKey));
+    const feesBefore = await tokenBalance(feePdas(tokenMintA).collection);
+    await expectError(claim(user, 0, tokenMintB), "InvalidTokenMint");
+    await claim(user, 0, tokenMintA);
+    await claim(user2, 1, tokenMintB);
+
+    // Both orders net 0.999 after fees and cross; the batch clears at the
+    // base seller's limit, filling the seller in full and refunding the
+    // buyer's unspent quote
+    const batch = await program.account.batch.fetch(batchPda);
+    assert.equal(batch.settled, true);
+    assert(batch.orders.every((order) => order.claimed));
+    const userOut = (await tokenBalance(userTokenAccountB)) - userBefore;
+    const user2Out = (await tokenBalance(ata(tokenMintA, user2.publicKey))) - user2Before;
+    if (baseMint.equals(tokenMintA)) {
+      assert.equal(userOut, 995000);
+      assert.equal(user2Out, 999000);
+    } else {
+      assert.equal(userOut, 999000);
+      assert.equal(user2Out, 995000);
+    }
+
+    // Fees are collected on the input each order spent: all of the base
+    // seller's, and 0.995 of the buyer's 0.999 quote
+    assert.equal(
+      (await tokenBalance(feePdas(tokenMintA).collection)) - feesBefore,
+      baseMint.equals(tokenMintA) ? 1000 : 996
+    );
+
+    await expectError(claim(user, 0, tokenMintA), "AlreadyClaimed");
   });
 
-  it("Rejects expired intents", async () => {
-    const expiredNonce = new anchor.BN(99999);
-    const expiredExpiry = new anchor.BN(Math.floor(Date.now() / 1000) - 3600); // 1 hour ago
+  it("Guards reveal prices with the configured oracles", async () => {
+    // A 9-decimal mint priced at $1 by the B oracle, so a 1:1 fill in base
+    // units from 6-decimal A is off by a factor of 1000
+    const tokenMintD = await createMint(provider.connection, payer, payer.publicKey, null, 9);
+    await createAssociatedTokenAccount(provider.connection, payer, tokenMintD, user.publicKey);
+    const relayerTokenAccountD = await createAssociatedTokenAccount(
+      provider.connection, payer, tokenMintD, payer.publicKey
+    );
+    await mintTo(provider.connection, payer, tokenMintD, relayerTokenAccountD, payer, 1000000000);
 
-    const tradeIntent = {
-      user: user.publicKey,
-      nonce: expiredNonce,
-      expiry: expiredExpiry,
-      relayer: payer.publicKey,
-      relayerFee: relayerFee,
-      tokenIn: tokenMintA,
-      tokenOut: tokenMintB,
-      amountIn: amountIn,
-      minOut: minOut,
+    const oracleB = oracleOf(tokenMintB);
+    await program.methods
+      .initializeMintConfig(oracleB, 100, new anchor.BN(100 * 365 * 24 * 3600))
+      .accountsStrict({
+        config: configPda,
+        mintConfig: mintConfigPda(tokenMintD),
+        mint: tokenMintD,
+        admin: provider.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    oracles.set(tokenMintD.toBase58(), oracleB);
+
+    const mispriced = await commit(user, newIntent(user, { tokenOut: tokenMintD }));
+    await expectError(reveal(mispriced), "OraclePriceDeviation");
+
+    // A mint with an oracle configured cannot be filled without it, or
+    // against another price account
+    const committed = await commit(user, newIntent(user));
+    await expectError(reveal(committed, { accounts: { tokenInOracle: null } }), "InvalidOracle");
+    await expectError(reveal(committed, { accounts: { tokenInOracle: oracleB } }), "InvalidOracle");
+  });
+
+  it("Fills conditional intents only once the oracle crosses the trigger", async () => {
+    // Both oracles price their mint at $1, so 1 A is worth 1_000_000 B base units
+    const conditional = (triggerPrice: number, direction: any) =>
+      newIntent(user, {
+        kind: { conditional: { triggerPrice: new anchor.BN(triggerPrice), direction } },
+      });
+
+    const takeProfit = await commit(user, conditional(900000, { above: {} }));
+    await reveal(takeProfit);
+    assert.equal((await program.account.swapIntent.fetch(takeProfit.swapIntent)).revealed, true);
+
+    const limit = await commit(user, conditional(1100000, { above: {} }));
+    await expectError(reveal(limit), "TriggerNotReached");
+
+    const stopLoss = await commit(user, conditional(900000, { below: {} }));
+    await expectError(reveal(stopLoss), "TriggerNotReached");
+  });
+
+  it("Executes recurring intents slice by slice", async () => {
+    const committed = await commit(
+      user,
+      newIntent(user, {
+        minOut: new anchor.BN(390000),
+        kind: {
+          recurring: {
+            sliceAmountIn: new anchor.BN(400000),
+            intervalSecs: new anchor.BN(2),
+            sliceMinOut: new anchor.BN(390000),
+          },
+        },
+      })
+    );
+
+    // Slices move the input under the intent PDA's delegate authority
+    await expectError(executeSlice(committed), "SliceDelegateMissing");
+    await approve(
+      provider.connection, payer, userTokenAccountA, committed.swapIntent, user, 1000000
+    );
+
+    // Recurring intents cannot be revealed in one go
+    await expectError(reveal(committed), "UnsupportedIntentKind");
+
+    const userInBefore = await tokenBalance(userTokenAccountA);
+    await executeSlice(committed);
+    let swapIntent = await program.account.swapIntent.fetch(committed.swapIntent);
+    assert.equal(swapIntent.filledIn.toNumber(), 400000);
+    assert.equal(userInBefore - (await tokenBalance(userTokenAccountA)), 400000);
+
+    // At most one slice per interval
+    await expectError(executeSlice(committed), "SliceIntervalNotElapsed");
+
+    await waitForTime(swapIntent.lastFillAt.toNumber() + 2);
+    await executeSlice(committed);
+    swapIntent = await program.account.swapIntent.fetch(committed.swapIntent);
+    assert.equal(swapIntent.filledIn.toNumber(), 800000);
+
+    // The final slice takes the 0.2 A remainder against a floor scaled to
+    // match, then closes the intent
+    const userOutBefore = await tokenBalance(userTokenAccountB);
+    await waitForTime(swapIntent.lastFillAt.toNumber() + 2);
+    await executeSlice(committed);
+    assert.equal(userInBefore - (await tokenBalance(userTokenAccountA)), 1000000);
+    assert.equal((await tokenBalance(userTokenAccountB)) - userOutBefore, 199800);
+    assert.isNull(await program.account.swapIntent.fetchNullable(committed.swapIntent));
+  });
+
+  it("Delivers output to the intent's recipient", async () => {
+    const committed = await commit(user, newIntent(user, { recipient: user2.publicKey }));
+    const recipientAccount = ata(tokenMintB, user2.publicKey);
+
+    // The output account must belong to the committed recipient
+    await expectError(
+      reveal(committed, { accounts: { userTokenOutAccount: userTokenAccountB } }),
+      "InvalidRecipient"
+    );
+
+    const userOutBefore = await tokenBalance(userTokenAccountB);
+    const recipientBefore = await tokenBalance(recipientAccount);
+    await reveal(committed);
+
+    assert.equal((await tokenBalance(recipientAccount)) - recipientBefore, 999000);
+    assert.equal(await tokenBalance(userTokenAccountB), userOutBefore);
+  });
+
+  it("Charges the relayer fee in the committed token, up to the cap", async () => {
+    const relayerInAccount = ata(tokenMintA, payer.publicKey);
+
+    // A token-in fee is charged on top of amount_in
+    let userInBefore = await tokenBalance(userTokenAccountA);
+    let userOutBefore = await tokenBalance(userTokenAccountB);
+    const relayerInBefore = await tokenBalance(relayerInAccount);
+    await reveal(
+      await commit(user, newIntent(user, { relayerFee: new anchor.BN(2000), relayerFeeToken: { tokenIn: {} } }))
+    );
+    assert.equal(userInBefore - (await tokenBalance(userTokenAccountA)), 1002000);
+    assert.equal((await tokenBalance(relayerInAccount)) - relayerInBefore, 999000 + 2000);
+    assert.equal((await tokenBalance(userTokenAccountB)) - userOutBefore, 999000);
+
+    // A token-out fee is withheld from the output
+    userInBefore = await tokenBalance(userTokenAccountA);
+    userOutBefore = await tokenBalance(userTokenAccountB);
+    await reveal(
+      await commit(user, newIntent(user, { relayerFee: new anchor.BN(3000), relayerFeeToken: { tokenOut: {} } }))
+    );
+    assert.equal(userInBefore - (await tokenBalance(userTokenAccountA)), 1000000);
+    assert.equal((await tokenBalance(userTokenAccountB)) - userOutBefore, 996000);
+
+    // The default cap is 10% of amount_in for token-in fees
+    const greedy = await commit(
+      user,
+      newIntent(user, { relayerFee: new anchor.BN(100001), relayerFeeToken: { tokenIn: {} } })
+    );
+    await expectError(reveal(greedy), "RelayerFeeTooHigh");
+  });
+
+  it("Shares surplus over the quote with the MEV bounty pool", async () => {
+    const bountyB = feePdas(tokenMintB).bounty;
+    const quoted = await commit(user, newIntent(user, { quotedOut: new anchor.BN(950000) }));
+
+    // Surplus needs the token_out bounty pool to land in
+    await expectError(reveal(quoted), "BountyAccountRequired");
+
+    const userOutBefore = await tokenBalance(userTokenAccountB);
+    const bountyBefore = await tokenBalance(bountyB);
+    await reveal(quoted, { accounts: { bountyAccount: bountyB } });
+
+    // Half of the 0.049 B beaten over the quote goes to the pool
+    assert.equal((await tokenBalance(bountyB)) - bountyBefore, 24500);
+    assert.equal((await tokenBalance(userTokenAccountB)) - userOutBefore, 999000 - 24500);
+  });
+
+  it("Pays referral fees to the committed referrer", async () => {
+    const [referrerStatsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("referrer"), user2.publicKey.toBuffer(), tokenMintA.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .initializeReferrerStats()
+      .accountsStrict({
+        referrerStats: referrerStatsPda,
+        referrer: user2.publicKey,
+        mint: tokenMintA,
+        payer: provider.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const referred = (referralBps: number) =>
+      newIntent(user, { referrer: user2.publicKey, referralBps });
+    const referrerAccounts = {
+      referrerTokenAccount: ata(tokenMintA, user2.publicKey),
+      referrerStats: referrerStatsPda,
     };
 
-    const serialized = Buffer.concat([
-      user.publicKey.toBuffer(),
-      Buffer.from(expiredNonce.toArray("le", 8)),
-      Buffer.from(expiredExpiry.toArray("le", 8)),
-      payer.publicKey.toBuffer(),
-      Buffer.from(relayerFee.toArray("le", 8)),
-      tokenMintA.toBuffer(),
-      tokenMintB.toBuffer(),
-      Buffer.from(amountIn.toArray("le", 8)),
-      Buffer.from(minOut.toArray("le", 8)),
-    ]);
-
-    const intentHash = crypto.createHash('sha256').update(serialized).digest();
-    const intentHashArray = Array.from(intentHash);
-
-    const [expiredSwapIntentPda] = PublicKey.findProgramAddressSync(
-      [
-        Buffer.from("intent"),
-        user.publicKey.toBuffer(),
-        Buffer.from(expiredNonce.toArray("le", 8))
-      ],
+    const userInBefore = await tokenBalance(userTokenAccountA);
+    const referrerBefore = await tokenBalance(referrerAccounts.referrerTokenAccount);
+    const committed = await commit(user, referred(30));
+    await expectError(reveal(committed), "InvalidReferrer");
+    await reveal(committed, { accounts: referrerAccounts });
+
+    // 30 bps of the input, paid by the user on top
+    assert.equal((await tokenBalance(referrerAccounts.referrerTokenAccount)) - referrerBefore, 3000);
+    assert.equal(userInBefore - (await tokenBalance(userTokenAccountA)), 1003000);
+    const stats = await program.account.referrerStats.fetch(referrerStatsPda);
+    assert.equal(stats.totalEarned.toNumber(), 3000);
+    assert.equal(stats.referredTrades.toNumber(), 1);
+
+    // The default cap is 50 bps
+    const greedy = await commit(user, referred(60));
+    await expectError(reveal(greedy, { accounts: referrerAccounts }), "ReferralFeeTooHigh");
+
+    // Slices cannot pay referrals, so referred recurring intents are refused
+    const recurring = await commit(
+      user,
+      newIntent(user, {
+        referrer: user2.publicKey,
+        referralBps: 30,
+        kind: {
+          recurring: {
+            sliceAmountIn: new anchor.BN(500000),
+            intervalSecs: new anchor.BN(60),
+            sliceMinOut: minOut,
+          },
+        },
+      })
+    );
+    await expectError(executeSlice(recurring), "ReferralNotSupported");
+  });
+
+  it("Discounts the protocol fee by rolling volume tier", async () => {
+    const setFeeTiers = (feeTiers: any[]) =>
+      program.methods
+        .setFeeTiers(feeTiers)
+        .accountsStrict({ config: configPda, admin: provider.publicKey })
+        .rpc();
+
+    // Tiers must be strictly ascending in volume
+    await expectError(
+      setFeeTiers([
+        { minVolume: new anchor.BN(1), discountBps: 1000 },
+        { minVolume: new anchor.BN(1), discountBps: 2000 },
+      ]),
+      "InvalidConfigParams"
+    );
+
+    // Any volume so far earns half off the 0.1% fee
+    await setFeeTiers([{ minVolume: new anchor.BN(1), discountBps: 5000 }]);
+    const fees = feePdas(tokenMintA);
+    const feesBefore = await tokenBalance(fees.collection);
+    const userOutBefore = await tokenBalance(userTokenAccountB);
+
+    await reveal(await commit(user, newIntent(user)));
+
+    assert.equal((await tokenBalance(fees.collection)) - feesBefore, 500);
+    assert.equal((await tokenBalance(userTokenAccountB)) - userOutBefore, 999500);
+
+    await setFeeTiers([]);
+  });
+
+  it("Enforces per-user and per-mint volume limits", async () => {
+    const setUserVolumeLimit = (limit: number) =>
+      program.methods
+        .setUserVolumeLimit(new anchor.BN(limit))
+        .accountsStrict({ config: configPda, admin: provider.publicKey })
+        .rpc();
+    const setMintVolumeLimit = (mint: PublicKey, limit: number, epochSecs: number) =>
+      program.methods
+        .setMintVolumeLimit(new anchor.BN(limit), new anchor.BN(epochSecs))
+        .accountsStrict({ config: configPda, mintConfig: mintConfigPda(mint), admin: provider.publicKey })
+        .rpc();
+
+    // The user's notional today is already above a limit of one unit
+    await setUserVolumeLimit(1);
+    const committed = await commit(user, newIntent(user));
+    await expectError(reveal(committed), "VolumeLimitExceeded");
+
+    await setUserVolumeLimit(1000000000000000);
+    await reveal(committed);
+    await setUserVolumeLimit(0);
+
+    // Output counts against the limit of token_out too
+    await setMintVolumeLimit(tokenMintB, 500000, 3600);
+    const overMint = await commit(user, newIntent(user));
+    await expectError(reveal(overMint), "VolumeLimitExceeded");
+
+    const mintConfig = await program.account.mintConfig.fetch(mintConfigPda(tokenMintB));
+    assert.equal(mintConfig.volumeLimit.toNumber(), 500000);
+    await setMintVolumeLimit(tokenMintB, 0, 0);
+  });
+
+  it("Refuses reveals touching denylisted mints or recipients", async () => {
+    const denylistAccounts = { config: configPda, denylist: denylistPda, admin: provider.publicKey };
+    const add = (kind: any, key: PublicKey) =>
+      program.methods.addToDenylist(kind, key).accountsStrict(denylistAccounts).rpc();
+    const remove = (kind: any, key: PublicKey) =>
+      program.methods.removeFromDenylist(kind, key).accountsStrict(denylistAccounts).rpc();
+
+    // Listing takes effect on intents committed before it
+    const committed = await commit(user, newIntent(user, { recipient: user2.publicKey }));
+
+    await add({ mint: {} }, tokenMintB);
+    await expectError(reveal(committed), "Denylisted");
+    await remove({ mint: {} }, tokenMintB);
+
+    await add({ wallet: {} }, user2.publicKey);
+    await expectError(reveal(committed), "Denylisted");
+    await remove({ wallet: {} }, user2.publicKey);
+
+    await reveal(committed);
+    assert.equal((await program.account.swapIntent.fetch(committed.swapIntent)).revealed, true);
+  });
+
+  it("Keeps each mint's fees under its own authority", async () => {
+    const feesA = feePdas(tokenMintA);
+    const feesB = feePdas(tokenMintB);
+    const committed = await commit(user, newIntent(user));
+
+    // A fees can only be collected by, and settled from, the A authority
+    await expectError(
+      reveal(committed, { accounts: { feeCollectionAuthority: feesB.authority } }),
+      "ConstraintRaw"
+    );
+    await expectError(
+      program.methods
+        .settleTrade(new anchor.BN(1))
+        .accountsStrict({ ...settleAccounts(tokenMintA), feeCollectionAuthority: feesB.authority })
+        .rpc(),
+      "ConstraintSeeds"
+    );
+
+    const feesBefore = await tokenBalance(feesA.collection);
+    await reveal(committed);
+    assert.equal((await tokenBalance(feesA.collection)) - feesBefore, 1000);
+  });
+
+  it("Only collects fees into the canonical fee collection PDA", async () => {
+    const committed = await commit(user, newIntent(user));
+
+    // Any other A account, even one the relayer controls, is refused
+    await expectError(
+      reveal(committed, { accounts: { feeCollectionAccount: ata(tokenMintA, payer.publicKey) } }),
+      "ConstraintSeeds"
+    );
+    await expectError(
+      reveal(committed, { accounts: { feeCollectionAccount: feePdas(tokenMintB).collection } }),
+      "ConstraintSeeds"
+    );
+  });
+
+  it("Sweeps a mint's whole fee balance for a keeper reward", async () => {
+    const setSweepParams = (keeperRewardBps: number, minIntervalSecs: number, streamSecs = 86400) =>
+      program.methods
+        .setSweepParams(keeperRewardBps, new anchor.BN(minIntervalSecs), new anchor.BN(streamSecs))
+        .accountsStrict({ config: configPda, admin: provider.publicKey })
+        .rpc();
+
+    // Keeper rewards are capped at 5%
+    await expectError(setSweepParams(501, 3600), "InvalidConfigParams");
+    await setSweepParams(100, 3600);
+
+    const fees = feePdas(tokenMintA);
+    const keeperAccount = ata(tokenMintA, user2.publicKey);
+    const sweep = () =>
+      program.methods
+        .sweepFees()
+        .accountsStrict({
+          config: configPda,
+          feeCollectionAuthority: fees.authority,
+          sourceFeeAccount: fees.collection,
+          feeLedger: fees.ledger,
+          stakePool: stakePoolPda,
+          rewardPool: fees.rewardPool,
+          liquidityStakerAccount: fees.stakers,
+          treasuryAccount: fees.treasury,
+          bountyAccount: fees.bounty,
+          callerTokenAccount: keeperAccount,
+          tokenMint: tokenMintA,
+          caller: user2.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user2])
+        .rpc();
+
+    const balance = await tokenBalance(fees.collection);
+    assert.isAbove(balance, 0);
+    const keeperBefore = await tokenBalance(keeperAccount);
+    const stakersBefore = await tokenBalance(fees.stakers);
+
+    // Anyone may sweep; the keeper takes 1% and the rest splits 50/30/20
+    await sweep();
+    const keeperReward = Math.floor(balance / 100);
+    assert.equal((await tokenBalance(keeperAccount)) - keeperBefore, keeperReward);
+    assert.equal(
+      (await tokenBalance(fees.stakers)) - stakersBefore,
+      Math.floor((balance - keeperReward) / 2)
+    );
+    assert.equal(await tokenBalance(fees.collection), 0);
+
+    const ledger = await program.account.feeLedger.fetch(fees.ledger);
+    assert.isAbove(ledger.lastSweepAt.toNumber(), 0);
+
+    // Fill again so the next sweep only fails on the interval
+    await reveal(await commit(user, newIntent(user)));
+    await expectError(sweep(), "SweepTooSoon");
+
+    // Later tests stream settled staker rewards within a second
+    await setSweepParams(0, 0, 0);
+  });
+
+  it("Settles fees of several mints in one transaction", async () => {
+    // One group of 8 remaining accounts per mint
+    const settleGroup = (mint: PublicKey, overrides: any = {}) => {
+      const fees = { ...feePdas(mint), ...overrides };
+      return [
+        { pubkey: fees.authority, isWritable: false, isSigner: false },
+        { pubkey: fees.collection, isWritable: true, isSigner: false },
+        { pubkey: fees.ledger, isWritable: true, isSigner: false },
+        { pubkey: fees.rewardPool, isWritable: true, isSigner: false },
+        { pubkey: fees.stakers, isWritable: true, isSigner: false },
+        { pubkey: fees.treasury, isWritable: true, isSigner: false },
+        { pubkey: fees.bounty, isWritable: true, isSigner: false },
+        { pubkey: mint, isWritable: false, isSigner: false },
+      ];
+    };
+    const settleMany = (amounts: number[], remainingAccounts: anchor.web3.AccountMeta[]) =>
+      program.methods
+        .settleMany(amounts.map((amount) => new anchor.BN(amount)))
+        .accountsStrict({
+          config: configPda,
+          stakePool: stakePoolPda,
+          caller: provider.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .remainingAccounts(remainingAccounts)
+        .rpc();
+
+    const feesA = feePdas(tokenMintA);
+    const feesB = feePdas(tokenMintB);
+    const sourceBefore = [await tokenBalance(feesA.collection), await tokenBalance(feesB.collection)];
+    const treasuryBefore = [await tokenBalance(feesA.treasury), await tokenBalance(feesB.treasury)];
+    assert.isAtLeast(Math.min(sourceBefore[0], sourceBefore[1]), 500);
+
+    await settleMany([500, 500], [...settleGroup(tokenMintA), ...settleGroup(tokenMintB)]);
+
+    assert.equal(sourceBefore[0] - (await tokenBalance(feesA.collection)), 500);
+    assert.equal(sourceBefore[1] - (await tokenBalance(feesB.collection)), 500);
+    assert.equal((await tokenBalance(feesA.treasury)) - treasuryBefore[0], 150);
+    assert.equal((await tokenBalance(feesB.treasury)) - treasuryBefore[1], 150);
+
+    // Groups must be complete and hold the PDAs of their own mint
+    await expectError(
+      settleMany([500], settleGroup(tokenMintA).slice(0, 7)),
+      "InvalidSettlementAccounts"
+    );
+    await expectError(
+      settleMany([500], settleGroup(tokenMintA, { treasury: feesB.treasury })),
+      "InvalidSettlementAccounts"
+    );
+  });
+
+  it("Pays settled staker fees out to stakers", async () => {
+    const fees = feePdas(tokenMintA);
+    const [stakePositionPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("stake_position"), user.publicKey.toBuffer()],
       program.programId
     );
 
-    // Commit expired intent
+    // Without a reward pool, settling holds the stakers' half in the ledger
+    await reveal(await commit(user, newIntent(user)));
+    await reveal(await commit(user, newIntent(user)));
+    await program.methods
+      .settleTrade(new anchor.BN(1000))
+      .accountsStrict({ ...settleAccounts(tokenMintA), stakePool: null, rewardPool: null })
+      .rpc();
+    const ledger = await program.account.feeLedger.fetch(fees.ledger);
+    assert.equal(ledger.pendingStakerRewards.toNumber(), 500);
+
+    // The next settlement with the pool queues both halves into its stream
+    await program.methods
+      .settleTrade(new anchor.BN(1000))
+      .accountsStrict(settleAccounts(tokenMintA))
+      .rpc();
+    const pool = await program.account.rewardPool.fetch(fees.rewardPool);
+    assert.isAtLeast(pool.queued.toNumber(), 1000);
+    assert.equal((await program.account.feeLedger.fetch(fees.ledger)).pendingStakerRewards.toNumber(), 0);
+    await waitForTime(pool.lastUpdate.toNumber() + 1);
+
+    const claim = () =>
+      program.methods
+        .claimRewards()
+        .accountsStrict({
+          stakePool: stakePoolPda,
+          stakePosition: stakePositionPda,
+          rewardPool: fees.rewardPool,
+          liquidityStakerAccount: fees.stakers,
+          feeCollectionAuthority: fees.authority,
+          ownerTokenAccount: userTokenAccountA,
+          feeMint: tokenMintA,
+          owner: user.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user])
+        .rpc();
+
+    // The user is the only staker, so is owed everything streamed since staking
+    const userBefore = await tokenBalance(userTokenAccountA);
+    await claim();
+    const claimed = (await tokenBalance(userTokenAccountA)) - userBefore;
+    const claimedPool = await program.account.rewardPool.fetch(fees.rewardPool);
+    assert.isAtLeast(claimed, 999);
+    assert.isAtMost(claimed, claimedPool.totalAccrued.toNumber());
+    assert.equal(claimedPool.queued.toNumber(), 0);
+    assert.equal(claimedPool.totalClaimed.sub(pool.totalClaimed).toNumber(), claimed);
+
+    await expectError(claim(), "AmountTooSmall");
+  });
+
+  it("Pays approved bounty claims from the bounty pool", async () => {
+    const bountyClaimPda = (claimId: anchor.BN) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("bounty_claim"), user.publicKey.toBuffer(), Buffer.from(claimId.toArray("le", 8))],
+        program.programId
+      )[0];
+    const fees = feePdas(tokenMintA);
+    const pay = (claim: PublicKey) =>
+      program.methods
+        .payBountyClaim()
+        .accountsStrict({
+          bountyClaim: claim,
+          bountyAccount: fees.bounty,
+          feeCollectionAuthority: fees.authority,
+          reporterTokenAccount: userTokenAccountA,
+          mint: tokenMintA,
+          caller: payer.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([payer])
+        .rpc();
+
+    // Claim 1 was approved by the committee above; settlements have funded the pool
+    const approved = bountyClaimPda(new anchor.BN(1));
+    assert.isAtLeast(await tokenBalance(fees.bounty), 1000);
+    const userBefore = await tokenBalance(userTokenAccountA);
+    await pay(approved);
+
+    assert.equal((await tokenBalance(userTokenAccountA)) - userBefore, 1000);
+    const paid = await program.account.bountyClaim.fetch(approved);
+    assert.deepEqual(paid.status, { paid: {} });
+    await expectError(pay(approved), "BountyClaimNotApproved");
+
+    // A single rejection by a one-member committee rejects the claim
+    const claimId = new anchor.BN(2);
     await program.methods
-      .commitTrade(intentHashArray, expiredNonce, expiredExpiry)
+      .submitBountyClaim(claimId, new anchor.BN(1000), [
+        { txSignature: Array(64).fill(9), amount: new anchor.BN(50000) },
+      ])
       .accountsStrict({
-        swapIntent: expiredSwapIntentPda,
-        user: user.publicKey,
+        bountyClaim: bountyClaimPda(claimId),
+        mint: tokenMintA,
+        reporter: user.publicKey,
         systemProgram: SystemProgram.programId,
       })
       .signers([user])
       .rpc();
 
-    // Try to reveal - should fail
-    const mockSignature = new Array(64).fill(0);
-    const ed25519Instruction = anchor.web3.Ed25519Program.createInstructionWithPublicKey({
-      publicKey: user.publicKey.toBytes(),
-      message: serialized,
-      signature: new Uint8Array(mockSignature),
-    });
+    const vote = (member: Keypair | null, approve: boolean) =>
+      program.methods
+        .voteBountyClaim(approve)
+        .accountsStrict({
+          config: configPda,
+          bountyClaim: bountyClaimPda(claimId),
+          member: member ? member.publicKey : provider.publicKey,
+        })
+        .signers(member ? [member] : [])
+        .rpc();
 
-    const revealTx = new anchor.web3.Transaction();
-    revealTx.add(ed25519Instruction);
-    
-    const revealIx = await program.methods
-      .revealTrade(tradeIntent, intentHashArray, mockSignature)
-      .accounts({
-        swapIntent: expiredSwapIntentPda,
-        user: user.publicKey,
-        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
-      })
-      .instruction();
-    
-    revealTx.add(revealIx);
+    await expectError(vote(user2, true), "NotCommitteeMember");
+    await vote(null, false);
 
-    try {
-      await provider.sendAndConfirm(revealTx, [user]);
-      assert.fail("Should have thrown an error for expired intent");
-    } catch (error) {
-      assert(error.message.includes("IntentExpired") || error.message.includes("Trade intent expired"));
-      console.log("✅ Correctly rejected expired intent");
-    }
+    const rejected = await program.account.bountyClaim.fetch(bountyClaimPda(claimId));
+    assert.deepEqual(rejected.status, { rejected: {} });
+    await expectError(pay(bountyClaimPda(claimId)), "BountyClaimNotApproved");
+    await expectError(vote(null, true), "BountyClaimNotPending");
   });
-});
\ No newline at end of file
+});